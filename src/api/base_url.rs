@@ -0,0 +1,194 @@
+//! the single definition of which API base urls each environment accepts
+
+use super::client::Env;
+
+// DEV: allow localhost, any 127.x.x.x loopback, or 0.0.0.0 (http or https, any port)
+const DEV_PATTERN: &str =
+    r"^https?://(localhost|127(\.(25[0-5]|2[0-4]\d|1?\d?\d)){3}|0\.0\.0\.0)(:\d+)?(/.*)?$";
+
+// RELEASE: only allow https://*projectlighthouse.io
+const RELEASE_PATTERN: &str = r"^https://([a-zA-Z0-9-]+\.)*projectlighthouse\.io(/.*)?$";
+
+#[derive(Debug)]
+pub(crate) struct LighthouseAPIClientBaseURL(pub(crate) String);
+
+impl LighthouseAPIClientBaseURL {
+    pub fn from(base_url: &str, environment: Env) -> Result<Self, String> {
+        let pattern = match environment {
+            Env::DEV => DEV_PATTERN,
+            Env::RELEASE => RELEASE_PATTERN,
+        };
+
+        let re = regex::Regex::new(pattern).map_err(|e| format!("invalid regex pattern: {}", e))?;
+
+        if re.is_match(base_url) {
+            Ok(LighthouseAPIClientBaseURL(base_url.to_string()))
+        } else {
+            let err_msg = match environment {
+                Env::DEV => {
+                    "invalid URL: must be localhost or a loopback address in DEV environment"
+                }
+                Env::RELEASE => {
+                    "invalid URL: must be https://*.projectlighthouse.io in RELEASE environment"
+                }
+            };
+            Err(err_msg.to_string())
+        }
+    }
+
+    pub fn default_for_env(environment: Env) -> Self {
+        let url = match environment {
+            Env::DEV => "http://localhost:8000",
+            Env::RELEASE => "https://projectlighthouse.io",
+        };
+        LighthouseAPIClientBaseURL(url.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lighthouse_api_base_url_dev_env() {
+        // Valid localhost URLs in DEV
+        assert!(LighthouseAPIClientBaseURL::from("http://localhost", Env::DEV).is_ok());
+        assert!(LighthouseAPIClientBaseURL::from("https://localhost", Env::DEV).is_ok());
+        assert!(LighthouseAPIClientBaseURL::from("http://localhost:8080", Env::DEV).is_ok());
+        assert!(LighthouseAPIClientBaseURL::from("https://localhost:3000/api", Env::DEV).is_ok());
+
+        // projectlighthouse.io NOT allowed in DEV
+        assert!(
+            LighthouseAPIClientBaseURL::from("https://projectlighthouse.io", Env::DEV).is_err()
+        );
+        assert!(
+            LighthouseAPIClientBaseURL::from("https://projectlighthouse.io", Env::DEV).is_err()
+        );
+
+        // Invalid URLs in DEV
+        assert!(LighthouseAPIClientBaseURL::from("ftp://localhost", Env::DEV).is_err()); // wrong scheme
+        assert!(LighthouseAPIClientBaseURL::from("https://example.com", Env::DEV).is_err());
+        // wrong domain
+    }
+
+    #[test]
+    fn test_lighthouse_api_base_url_release_env() {
+        // Valid projectlighthouse.io URLs in RELEASE (https only)
+        assert!(
+            LighthouseAPIClientBaseURL::from("https://projectlighthouse.io", Env::RELEASE).is_ok()
+        );
+        assert!(
+            LighthouseAPIClientBaseURL::from("https://projectlighthouse.io/api", Env::RELEASE)
+                .is_ok()
+        );
+        assert!(
+            LighthouseAPIClientBaseURL::from("https://projectlighthouse.io", Env::RELEASE).is_ok()
+        );
+        assert!(
+            LighthouseAPIClientBaseURL::from("https://projectlighthouse.io/v1", Env::RELEASE)
+                .is_ok()
+        );
+
+        // localhost NOT allowed in RELEASE
+        assert!(LighthouseAPIClientBaseURL::from("http://localhost", Env::RELEASE).is_err());
+        assert!(LighthouseAPIClientBaseURL::from("https://localhost:8080", Env::RELEASE).is_err());
+
+        // Invalid URLs in RELEASE
+        assert!(
+            LighthouseAPIClientBaseURL::from("http://projectlighthouse.io", Env::RELEASE).is_err()
+        ); // http not allowed
+        assert!(LighthouseAPIClientBaseURL::from("https://example.com", Env::RELEASE).is_err());
+        // wrong domain
+    }
+
+    #[test]
+    fn test_lighthouse_api_base_url_loopback_ip() {
+        assert!(LighthouseAPIClientBaseURL::from("http://127.0.0.1:8000", Env::DEV).is_ok());
+        assert!(LighthouseAPIClientBaseURL::from("http://127.0.0.1", Env::DEV).is_ok());
+        assert!(LighthouseAPIClientBaseURL::from("http://127.1.2.3:8000/api", Env::DEV).is_ok());
+
+        // not a valid loopback address
+        assert!(LighthouseAPIClientBaseURL::from("http://127.0.0.256", Env::DEV).is_err());
+        assert!(LighthouseAPIClientBaseURL::from("http://127.0.0.1.evil.com", Env::DEV).is_err());
+
+        // RELEASE still rejects it
+        assert!(LighthouseAPIClientBaseURL::from("http://127.0.0.1:8000", Env::RELEASE).is_err());
+        assert!(LighthouseAPIClientBaseURL::from("https://127.0.0.1:8000", Env::RELEASE).is_err());
+    }
+
+    #[test]
+    fn test_lighthouse_api_base_url_dev_with_paths() {
+        // Various path combinations
+        assert!(LighthouseAPIClientBaseURL::from("http://localhost/", Env::DEV).is_ok());
+        assert!(LighthouseAPIClientBaseURL::from("http://localhost/api/v1", Env::DEV).is_ok());
+        assert!(LighthouseAPIClientBaseURL::from(
+            "http://localhost:8080/api/v1/exercises",
+            Env::DEV
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_lighthouse_api_base_url_release_subdomains() {
+        // Multiple subdomain levels
+        assert!(LighthouseAPIClientBaseURL::from(
+            "https://api.v2.projectlighthouse.io",
+            Env::RELEASE
+        )
+        .is_ok());
+        assert!(LighthouseAPIClientBaseURL::from(
+            "https://staging.api.projectlighthouse.io",
+            Env::RELEASE
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_lighthouse_api_base_url_error_messages() {
+        let dev_err =
+            LighthouseAPIClientBaseURL::from("https://example.com", Env::DEV).unwrap_err();
+        assert!(dev_err.contains("localhost"));
+        assert!(dev_err.contains("DEV"));
+
+        let release_err =
+            LighthouseAPIClientBaseURL::from("http://localhost", Env::RELEASE).unwrap_err();
+        assert!(release_err.contains("projectlighthouse.io"));
+        assert!(release_err.contains("RELEASE"));
+    }
+
+    #[test]
+    fn test_lighthouse_api_base_url_default_for_env_dev() {
+        let url = LighthouseAPIClientBaseURL::default_for_env(Env::DEV);
+        assert_eq!(url.0, "http://localhost:8000");
+    }
+
+    #[test]
+    fn test_lighthouse_api_base_url_default_for_env_release() {
+        let url = LighthouseAPIClientBaseURL::default_for_env(Env::RELEASE);
+        assert_eq!(url.0, "https://projectlighthouse.io");
+    }
+
+    #[test]
+    fn test_dev_pattern_covers_all_local_hosts() {
+        for url in [
+            "http://localhost:8000",
+            "http://0.0.0.0:8000",
+            "http://127.0.0.1:8000",
+            "http://127.10.0.1:8000",
+        ] {
+            assert!(
+                LighthouseAPIClientBaseURL::from(url, Env::DEV).is_ok(),
+                "{} should be accepted in DEV",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn test_default_urls_pass_their_own_validation() {
+        for env in [Env::DEV, Env::RELEASE] {
+            let url = LighthouseAPIClientBaseURL::default_for_env(env);
+            assert!(LighthouseAPIClientBaseURL::from(&url.0, env).is_ok());
+        }
+    }
+}
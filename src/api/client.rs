@@ -1,28 +1,126 @@
-use color_eyre::eyre::{eyre, Result};
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{eyre, Report, Result};
 use core::fmt;
-use reqwest::{header::HeaderMap, Client};
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    Client, RequestBuilder, Response, StatusCode,
+};
 use secrecy::{ExposeSecret, SecretString};
 use serde::de::DeserializeOwned;
-use std::{collections::HashMap, env};
+use std::{collections::HashMap, env, sync::OnceLock, time::Duration};
+use tokio::sync::OnceCell;
 
-use crate::{config::Config, VERSION};
+use crate::{
+    config::{Config, Settings},
+    VERSION,
+};
 
+use super::base_url::LighthouseAPIClientBaseURL;
 use super::types::{
-    ApiError, ApiUser, HealthCheckResponse, HintsResponse, Lab, PaginatedResponse,
+    ApiRequestError, ApiUser, HealthCheckResponse, HintsResponse, Lab, PaginatedResponse,
     SubmitAnswerRequest, SubmitAnswerResponse, SubmitAttemptRequest, SubmitAttemptResponse,
     UnlockHintResponse,
 };
 
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// how many times a rate-limited (429/503) request is retried
+const MAX_RETRIES: u32 = 2;
+/// wait used when a 429/503 carries no usable Retry-After
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
+/// never sleep longer than this, whatever the server asks for
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// page size used when the caller doesn't ask for one
+pub const DEFAULT_PER_PAGE: i32 = 50;
+/// largest page size the API serves
+const MAX_PER_PAGE: i32 = 100;
+
+/// query params for a paginated listing; per_page is clamped to [1, 100] and page to >= 1
+fn paging_params(page: Option<i32>, per_page: Option<i32>) -> HashMap<String, String> {
+    let mut query_params = HashMap::new();
+    if let Some(p) = page {
+        query_params.insert("page".to_string(), p.max(1).to_string());
+    }
+    let per_page = per_page.unwrap_or(DEFAULT_PER_PAGE).clamp(1, MAX_PER_PAGE);
+    query_params.insert("per_page".to_string(), per_page.to_string());
+    query_params
+}
+
+/// parse a Retry-After value: delay in seconds or an HTTP-date
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = at.with_timezone(&Utc) - now;
+    Some(delay.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// delay before retrying a 429/503, or None when the response is not retryable
+fn retry_delay(response: &Response) -> Option<Duration> {
+    let status = response.status();
+    if status != StatusCode::TOO_MANY_REQUESTS && status != StatusCode::SERVICE_UNAVAILABLE {
+        return None;
+    }
+    let delay = response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| parse_retry_after(v, Utc::now()))
+        .unwrap_or(DEFAULT_RETRY_DELAY);
+    Some(delay.min(MAX_RETRY_DELAY))
+}
+
+/// "luxctl/<version> (+https://projectlighthouse.io)", sent with every outbound request
+pub fn user_agent() -> String {
+    format!("luxctl/{} (+https://projectlighthouse.io)", VERSION)
+}
+
+/// overall request timeout: LUX_API_TIMEOUT_MS when it parses, else REQUEST_TIMEOUT
+fn request_timeout(env_value: Option<String>) -> Duration {
+    env_value
+        .and_then(|ms| ms.trim().parse::<u64>().ok())
+        .filter(|ms| *ms > 0)
+        .map(Duration::from_millis)
+        .unwrap_or(REQUEST_TIMEOUT)
+}
+
+fn build_client(timeout: Duration) -> Client {
+    Client::builder()
+        .user_agent(user_agent())
+        .connect_timeout(CONNECT_TIMEOUT.min(timeout))
+        .timeout(timeout)
+        .build()
+        .unwrap_or_else(|e| {
+            log::warn!("failed to configure http client: {}", e);
+            Client::new()
+        })
+}
+
+/// one pooled client for the API and any other outbound HTTP (e.g. Dockerfile downloads).
+/// `Client` is reference counted, so clones share the connection pool
+pub fn shared_client() -> Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT
+        .get_or_init(|| build_client(request_timeout(env::var("LUX_API_TIMEOUT_MS").ok())))
+        .clone()
+}
+
 pub struct LighthouseAPIClient {
     base_url: String,
     api_version: String,
     env: Env,
     client: Client,
     token: Option<SecretString>,
+    /// the authenticated user, fetched once per process by `me()`
+    user: OnceCell<ApiUser>,
 }
 
 impl LighthouseAPIClient {
-    fn new(
+    pub(crate) fn new(
         base_url: LighthouseAPIClientBaseURL,
         api_version: &str,
         env: Env,
@@ -32,18 +130,61 @@ impl LighthouseAPIClient {
             base_url: base_url.0,
             api_version: api_version.to_string(),
             env,
-            client: Client::new(),
+            client: shared_client(),
             token,
+            user: OnceCell::new(),
         }
     }
 
     pub fn from_config(config: &Config) -> LighthouseAPIClient {
         LighthouseAPIClient {
             token: Some(SecretString::from(config.expose_token())),
-            ..Default::default()
+            ..Self::from_settings(config.settings())
         }
     }
 
+    /// like `from_config`, but fails on an invalid base url instead of falling back
+    pub fn try_from_config(config: &Config) -> Result<LighthouseAPIClient> {
+        Ok(LighthouseAPIClient {
+            token: Some(SecretString::from(config.expose_token())),
+            ..Self::try_from_settings(config.settings())?
+        })
+    }
+
+    /// like `default`, but surfaces settings and base url validation errors
+    pub fn try_default() -> Result<LighthouseAPIClient> {
+        let settings = Settings::load()?;
+        Self::try_from_settings(&settings)
+    }
+
+    fn try_from_settings(settings: &Settings) -> Result<LighthouseAPIClient> {
+        let luxctl_env = resolve_env(env::var("LUXCTL_ENV").ok(), settings.env.as_deref());
+        let base_url = try_resolve_base_url(
+            env::var("LUXCTL_API_BASE_URL").ok(),
+            settings.api_base_url.as_deref(),
+            luxctl_env,
+        )
+        .map_err(|e| eyre!("{}", e))?;
+
+        log::debug!("initiating lighthouse api with {}", base_url.0);
+
+        Ok(LighthouseAPIClient::new(base_url, "v1", luxctl_env, None))
+    }
+
+    /// build an unauthenticated client, env vars take precedence over settings
+    fn from_settings(settings: &Settings) -> LighthouseAPIClient {
+        let luxctl_env = resolve_env(env::var("LUXCTL_ENV").ok(), settings.env.as_deref());
+        let base_url = resolve_base_url(
+            env::var("LUXCTL_API_BASE_URL").ok(),
+            settings.api_base_url.as_deref(),
+            luxctl_env,
+        );
+
+        log::debug!("initiating lighthouse api with {}", base_url.0);
+
+        LighthouseAPIClient::new(base_url, "v1", luxctl_env, None)
+    }
+
     fn auth_headers(&self) -> Result<HeaderMap> {
         let token = self
             .token
@@ -59,6 +200,59 @@ impl LighthouseAPIClient {
         Ok(headers)
     }
 
+    /// scrub the auth token from text before it is logged or printed
+    fn redact(&self, text: &str) -> String {
+        match self.token {
+            Some(ref token) => redact(text, token.expose_secret()),
+            None => text.to_string(),
+        }
+    }
+
+    /// turn a non-success response into an `ApiRequestError` carrying the status and API message
+    async fn error_from_response(&self, response: reqwest::Response) -> color_eyre::eyre::Report {
+        let status = response.status().as_u16();
+        let error_text = match response.text().await {
+            Ok(text) => text,
+            Err(e) => return eyre!("{}", self.redact(&e.to_string())),
+        };
+        let mut error = ApiRequestError::from_body(status, &error_text);
+        error.message = self.redact(&error.message);
+        color_eyre::eyre::Report::new(error)
+    }
+
+    /// send a request, retrying 429/503 responses after their Retry-After delay
+    async fn send_with_retry(&self, request: RequestBuilder) -> Result<Response> {
+        let mut attempt = 0;
+        loop {
+            // json bodies always clone; anything else is sent once without retries
+            let Some(current) = request.try_clone() else {
+                return request
+                    .send()
+                    .await
+                    .map_err(|e| eyre!("{}", self.redact(&e.to_string())));
+            };
+            let response = current
+                .send()
+                .await
+                .map_err(|e| eyre!("{}", self.redact(&e.to_string())))?;
+
+            match retry_delay(&response) {
+                Some(delay) if attempt < MAX_RETRIES => {
+                    attempt += 1;
+                    log::warn!(
+                        "api returned {}, retrying in {:?} (attempt {}/{})",
+                        response.status().as_u16(),
+                        delay,
+                        attempt,
+                        MAX_RETRIES
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                _ => return Ok(response),
+            }
+        }
+    }
+
     // when we deserialize JSON, we're creating owned data. But
     // there are two Deserialize traits:
     //
@@ -96,17 +290,16 @@ impl LighthouseAPIClient {
             request = request.headers(headers);
         }
 
-        let response = request.send().await?;
+        let response = self.send_with_retry(request).await?;
 
         if !response.status().is_success() {
-            let error_text = response.text().await?;
-            let message = serde_json::from_str::<ApiError>(&error_text)
-                .map(|e| e.message)
-                .unwrap_or(error_text);
-            return Err(eyre!("{}", message));
+            return Err(self.error_from_response(response).await);
         }
 
-        let data = response.json::<T>().await?;
+        let data = response
+            .json::<T>()
+            .await
+            .map_err(|e| eyre!("{}", self.redact(&e.to_string())))?;
         Ok(data)
     }
 
@@ -124,28 +317,51 @@ impl LighthouseAPIClient {
             request = request.headers(headers);
         }
 
-        let response = request.send().await?;
+        let response = self.send_with_retry(request).await?;
 
         if !response.status().is_success() {
-            let error_text = response.text().await?;
-            let message = serde_json::from_str::<ApiError>(&error_text)
-                .map(|e| e.message)
-                .unwrap_or(error_text);
-            return Err(eyre!("{}", message));
+            return Err(self.error_from_response(response).await);
         }
 
-        let data = response.json::<T>().await?;
+        let data = response
+            .json::<T>()
+            .await
+            .map_err(|e| eyre!("{}", self.redact(&e.to_string())))?;
         Ok(data)
     }
 }
 
+/// user-facing message for a failed `lab_by_slug`: a 404 reads as "not found",
+/// a request that never got a response as "failed to reach server"
+pub fn lab_fetch_error(slug: &str, err: &Report) -> String {
+    match err.downcast_ref::<ApiRequestError>() {
+        Some(api) if api.is_not_found() => format!("lab '{}' not found", slug),
+        Some(api) => format!("failed to fetch lab '{}': {}", slug, api),
+        None => format!("failed to reach server: {}", err),
+    }
+}
+
+/// replace every occurrence of `secret` in `text` with `***`
+pub fn redact(text: &str, secret: &str) -> String {
+    if secret.is_empty() {
+        return text.to_string();
+    }
+    text.replace(secret, "***")
+}
+
 impl LighthouseAPIClient {
     /// unauthenticated healthcheck endpoint
     pub async fn healthcheck(&self) -> Result<HealthCheckResponse> {
         self.get::<HealthCheckResponse>("health", None, None).await
     }
 
+    /// the authenticated user; later calls on the same client reuse the first response
     pub async fn me(&self) -> Result<ApiUser> {
+        self.user.get_or_try_init(|| self.me_fresh()).await.cloned()
+    }
+
+    /// like `me`, but always asks the server
+    pub async fn me_fresh(&self) -> Result<ApiUser> {
         let headers = self.auth_headers()?;
         self.get::<ApiUser>("user", None, Some(headers)).await
     }
@@ -157,12 +373,7 @@ impl LighthouseAPIClient {
     ) -> Result<PaginatedResponse<Lab>> {
         let headers = self.auth_headers()?;
 
-        let mut query_params = HashMap::new();
-        if let Some(p) = page {
-            query_params.insert("page".to_string(), p.to_string());
-        }
-        query_params.insert("per_page".to_string(), per_page.unwrap_or(50).to_string());
-
+        let query_params = paging_params(page, per_page);
         self.get::<PaginatedResponse<Lab>>("labs", Some(query_params), Some(headers))
             .await
     }
@@ -229,79 +440,64 @@ impl fmt::Display for Env {
     }
 }
 
-#[derive(Debug)]
-struct LighthouseAPIClientBaseURL(String);
-
-impl LighthouseAPIClientBaseURL {
-    pub fn from(base_url: &str, environment: Env) -> Result<Self, String> {
-        let pattern = match environment {
-            // DEV: allow localhost, 127.0.0.1, or 0.0.0.0 (http or https, any port)
-            Env::DEV => r"^https?://(localhost|127\.0\.0\.1|0\.0\.0\.0)(:\d+)?(/.*)?$",
-            // RELEASE: only allow https://*projectlighthouse.io
-            Env::RELEASE => r"^https://([a-zA-Z0-9-]+\.)*projectlighthouse\.io(/.*)?$",
-        };
-
-        let re = regex::Regex::new(pattern).map_err(|e| format!("invalid regex pattern: {}", e))?;
-
-        if re.is_match(base_url) {
-            Ok(LighthouseAPIClientBaseURL(base_url.to_string()))
-        } else {
-            let err_msg = match environment {
-                Env::DEV => "invalid URL: must be localhost in DEV environment",
-                Env::RELEASE => {
-                    "invalid URL: must be https://*.projectlighthouse.io in RELEASE environment"
-                }
-            };
-            Err(err_msg.to_string())
-        }
-    }
-
-    pub fn default_for_env(environment: Env) -> Self {
-        let url = match environment {
-            Env::DEV => "http://localhost:8000",
-            Env::RELEASE => "https://projectlighthouse.io",
-        };
-        LighthouseAPIClientBaseURL(url.to_string())
-    }
-}
-
 impl Default for LighthouseAPIClient {
     fn default() -> Self {
-        // 1. get the env first from LUXCTL_ENV, it should map to the enum Env::DEV or Env::RELEASE
-        // 2. default based on build type: DEV for debug builds, RELEASE for release builds
-        #[cfg(debug_assertions)]
-        let default_env = Env::DEV;
-        #[cfg(not(debug_assertions))]
-        let default_env = Env::RELEASE;
-
-        let luxctl_env = match env::var("LUXCTL_ENV") {
-            Ok(val) => match val.to_uppercase().as_str() {
-                "RELEASE" => Env::RELEASE,
-                "DEV" => Env::DEV,
-                _ => default_env,
-            },
-            Err(_) => default_env,
-        };
+        // settings file is optional; a broken one shouldn't stop unauthenticated calls
+        let settings = Settings::load().unwrap_or_else(|e| {
+            log::warn!("{}. ignoring settings file.", e);
+            Settings::default()
+        });
+        Self::from_settings(&settings)
+    }
+}
 
-        // 3. get base_url from env var or use default for the environment
-        let base_url = match env::var("LUXCTL_API_BASE_URL") {
-            Ok(val) => {
-                // Validate the URL if provided
-                match LighthouseAPIClientBaseURL::from(&val, luxctl_env) {
-                    Ok(url) => url,
-                    Err(e) => {
-                        log::warn!("invalid LUXCTL_API_BASE_URL: {}. using default.", e);
-                        LighthouseAPIClientBaseURL::default_for_env(luxctl_env)
-                    }
-                }
-            }
-            Err(_) => LighthouseAPIClientBaseURL::default_for_env(luxctl_env),
-        };
+/// pick the environment: LUXCTL_ENV, then the settings file, then the build type
+pub(crate) fn resolve_env(env_var: Option<String>, file_env: Option<&str>) -> Env {
+    // default based on build type: DEV for debug builds, RELEASE for release builds
+    #[cfg(debug_assertions)]
+    let default_env = Env::DEV;
+    #[cfg(not(debug_assertions))]
+    let default_env = Env::RELEASE;
+
+    let parse = |val: &str| match val.to_uppercase().as_str() {
+        "RELEASE" => Some(Env::RELEASE),
+        "DEV" => Some(Env::DEV),
+        _ => None,
+    };
+
+    env_var
+        .as_deref()
+        .and_then(parse)
+        .or_else(|| file_env.and_then(parse))
+        .unwrap_or(default_env)
+}
 
-        log::debug!("initiating lighthouse api with {}", base_url.0);
+/// pick the base url: LUXCTL_API_BASE_URL, then the settings file, then the env default
+fn try_resolve_base_url(
+    env_var: Option<String>,
+    file_url: Option<&str>,
+    environment: Env,
+) -> Result<LighthouseAPIClientBaseURL, String> {
+    let (source, candidate) = match (env_var, file_url) {
+        (Some(val), _) => ("LUXCTL_API_BASE_URL", val),
+        (None, Some(val)) => ("api_base_url", val.to_string()),
+        (None, None) => return Ok(LighthouseAPIClientBaseURL::default_for_env(environment)),
+    };
+
+    LighthouseAPIClientBaseURL::from(&candidate, environment)
+        .map_err(|e| format!("invalid {}: {}", source, e))
+}
 
-        LighthouseAPIClient::new(base_url, "v1", luxctl_env, None)
-    }
+/// same as `try_resolve_base_url`, but invalid values fall back to the env default
+fn resolve_base_url(
+    env_var: Option<String>,
+    file_url: Option<&str>,
+    environment: Env,
+) -> LighthouseAPIClientBaseURL {
+    try_resolve_base_url(env_var, file_url, environment).unwrap_or_else(|e| {
+        log::warn!("{}. using default.", e);
+        LighthouseAPIClientBaseURL::default_for_env(environment)
+    })
 }
 
 impl fmt::Display for LighthouseAPIClient {
@@ -316,6 +512,7 @@ impl fmt::Display for LighthouseAPIClient {
 
 #[cfg(test)]
 mod tests {
+    use super::super::types::ApiErrorCategory;
     use super::*;
     use std::sync::Mutex;
 
@@ -357,125 +554,282 @@ mod tests {
         result
     }
 
-    #[test]
-    fn test_env_display_dev() {
-        assert_eq!(format!("{}", Env::DEV), "dev");
+    // serve every connection with `handler(raw request)` and point a client at it
+    async fn mock_api<F>(mut handler: F) -> (LighthouseAPIClient, u16)
+    where
+        F: FnMut(&str) -> String + Send + 'static,
+    {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let response = handler(&String::from_utf8_lossy(&buf[..n]));
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let base_url =
+            LighthouseAPIClientBaseURL::from(&format!("http://127.0.0.1:{}", port), Env::DEV)
+                .unwrap();
+        let client = LighthouseAPIClient::new(
+            base_url,
+            "v1",
+            Env::DEV,
+            Some(SecretString::from("token".to_string())),
+        );
+        (client, port)
+    }
+
+    fn json_reply(status: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+    }
+
+    fn rate_limited(retry_after: u64) -> String {
+        format!(
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            retry_after
+        )
+    }
+
+    #[tokio::test]
+    async fn test_shared_client_sends_user_agent() {
+        // echo the raw request back as the body
+        let (_, port) = mock_api(|request| {
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                request.len(),
+                request
+            )
+        })
+        .await;
+
+        let echoed = shared_client()
+            .get(format!("http://127.0.0.1:{}/", port))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap()
+            .to_lowercase();
+        assert!(echoed.contains(&format!(
+            "user-agent: luxctl/{} (+https://projectlighthouse.io)",
+            VERSION
+        )));
     }
 
     #[test]
-    fn test_env_display_release() {
-        assert_eq!(format!("{}", Env::RELEASE), "release");
+    fn test_paging_params_defaults() {
+        let params = paging_params(None, None);
+        assert_eq!(params.get("page"), None);
+        assert_eq!(params["per_page"], DEFAULT_PER_PAGE.to_string());
+
+        let params = paging_params(Some(3), Some(20));
+        assert_eq!(params["page"], "3");
+        assert_eq!(params["per_page"], "20");
     }
 
     #[test]
-    fn test_env_clone() {
-        let env = Env::DEV;
-        let cloned = env;
-        assert_eq!(format!("{}", cloned), "dev");
+    fn test_paging_params_clamps_out_of_range() {
+        let params = paging_params(Some(0), Some(0));
+        assert_eq!(params["page"], "1");
+        assert_eq!(params["per_page"], "1");
+
+        let params = paging_params(Some(-4), Some(500));
+        assert_eq!(params["page"], "1");
+        assert_eq!(params["per_page"], "100");
+
+        assert_eq!(paging_params(None, Some(-10))["per_page"], "1");
     }
 
     #[test]
-    fn test_lighthouse_api_base_url_dev_env() {
-        // Valid localhost URLs in DEV
-        assert!(LighthouseAPIClientBaseURL::from("http://localhost", Env::DEV).is_ok());
-        assert!(LighthouseAPIClientBaseURL::from("https://localhost", Env::DEV).is_ok());
-        assert!(LighthouseAPIClientBaseURL::from("http://localhost:8080", Env::DEV).is_ok());
-        assert!(LighthouseAPIClientBaseURL::from("https://localhost:3000/api", Env::DEV).is_ok());
-
-        // projectlighthouse.io NOT allowed in DEV
-        assert!(
-            LighthouseAPIClientBaseURL::from("https://projectlighthouse.io", Env::DEV).is_err()
+    fn test_parse_retry_after() {
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2026 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(parse_retry_after("2", now), Some(Duration::from_secs(2)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2026 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
         );
-        assert!(
-            LighthouseAPIClientBaseURL::from("https://projectlighthouse.io", Env::DEV).is_err()
+        // dates in the past mean "retry now"
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2026 07:27:00 GMT", now),
+            Some(Duration::ZERO)
         );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_delays_next_attempt() {
+        // first request is rate limited, the second succeeds
+        let mut served = 0;
+        let (client, _) = mock_api(move |_| {
+            served += 1;
+            if served == 1 {
+                rate_limited(2)
+            } else {
+                json_reply(
+                    "200 OK",
+                    r#"{"status":"ok","app":"lighthouse","version":"1.0"}"#,
+                )
+            }
+        })
+        .await;
 
-        // Invalid URLs in DEV
-        assert!(LighthouseAPIClientBaseURL::from("ftp://localhost", Env::DEV).is_err()); // wrong scheme
-        assert!(LighthouseAPIClientBaseURL::from("https://example.com", Env::DEV).is_err());
-        // wrong domain
+        let started = std::time::Instant::now();
+        let health = client.healthcheck().await.unwrap();
+        assert_eq!(health.status, "ok");
+        assert!(started.elapsed() >= Duration::from_millis(1900));
     }
 
-    #[test]
-    fn test_lighthouse_api_base_url_release_env() {
-        // Valid projectlighthouse.io URLs in RELEASE (https only)
-        assert!(
-            LighthouseAPIClientBaseURL::from("https://projectlighthouse.io", Env::RELEASE).is_ok()
-        );
-        assert!(
-            LighthouseAPIClientBaseURL::from("https://projectlighthouse.io/api", Env::RELEASE)
-                .is_ok()
+    #[tokio::test]
+    async fn test_lab_by_slug_not_found() {
+        let (client, _) = mock_api(|_| {
+            json_reply(
+                "404 Not Found",
+                r#"{"message":"No query results for model [Lab]."}"#,
+            )
+        })
+        .await;
+
+        let err = client.lab_by_slug("no-such-lab").await.unwrap_err();
+        let api_err = err.downcast_ref::<ApiRequestError>().unwrap();
+        assert_eq!(api_err.category, ApiErrorCategory::NotFound);
+        assert!(api_err.is_not_found());
+        assert_eq!(
+            lab_fetch_error("no-such-lab", &err),
+            "lab 'no-such-lab' not found"
         );
-        assert!(
-            LighthouseAPIClientBaseURL::from("https://projectlighthouse.io", Env::RELEASE).is_ok()
+    }
+
+    #[test]
+    fn test_lab_fetch_error_messages() {
+        let server = Report::new(ApiRequestError::from_body(500, "boom"));
+        assert_eq!(
+            lab_fetch_error("tcp-echo", &server),
+            "failed to fetch lab 'tcp-echo': boom"
         );
-        assert!(
-            LighthouseAPIClientBaseURL::from("https://projectlighthouse.io/v1", Env::RELEASE)
-                .is_ok()
+
+        let network = eyre!("connection refused");
+        assert_eq!(
+            lab_fetch_error("tcp-echo", &network),
+            "failed to reach server: connection refused"
         );
+    }
+
+    #[tokio::test]
+    async fn test_me_is_cached_until_me_fresh() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&hits);
+        let (client, _) = mock_api(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            json_reply(
+                "200 OK",
+                r#"{"id":1,"name":"ada","email":"ada@example.com"}"#,
+            )
+        })
+        .await;
+
+        assert_eq!(client.me().await.unwrap().name, "ada");
+        assert_eq!(client.me().await.unwrap().name, "ada");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        client.me_fresh().await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
 
-        // localhost NOT allowed in RELEASE
-        assert!(LighthouseAPIClientBaseURL::from("http://localhost", Env::RELEASE).is_err());
-        assert!(LighthouseAPIClientBaseURL::from("https://localhost:8080", Env::RELEASE).is_err());
+    #[tokio::test]
+    async fn test_submit_attempt_retry_reuses_idempotency_key() {
+        use super::super::types::TaskOutcome;
+        use tokio::sync::mpsc;
+
+        // rate limit the first submission, accept the second; forward each body
+        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+        let mut served = 0;
+        let (client, _) = mock_api(move |request| {
+            let body = request.split("\r\n\r\n").nth(1).unwrap_or("");
+            let _ = tx.send(body.to_string());
+            served += 1;
+            if served == 1 {
+                rate_limited(0)
+            } else {
+                json_reply(
+                    "200 OK",
+                    r#"{"message":"ok","data":{"id":1,"task_id":3,"lab_id":1,"task_outcome":"passed","points_achieved":10,"is_reattempt":false,"created_at":"2026-01-01"}}"#,
+                )
+            }
+        })
+        .await;
+        let request = SubmitAttemptRequest {
+            lab_slug: "tcp-echo-server".to_string(),
+            task_id: 3,
+            task_outcome: TaskOutcome::Passed,
+            points_achieved: None,
+            task_outcome_context: None,
+            idempotency_key: uuid::Uuid::new_v4(),
+        };
+        client.submit_attempt(&request).await.unwrap();
 
-        // Invalid URLs in RELEASE
-        assert!(
-            LighthouseAPIClientBaseURL::from("http://projectlighthouse.io", Env::RELEASE).is_err()
-        ); // http not allowed
-        assert!(LighthouseAPIClientBaseURL::from("https://example.com", Env::RELEASE).is_err());
-        // wrong domain
+        let key = |body: String| -> String {
+            let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+            json["idempotency_key"].as_str().unwrap().to_string()
+        };
+        let first = key(rx.recv().await.unwrap());
+        let second = key(rx.recv().await.unwrap());
+        assert_eq!(first, second);
+        assert_eq!(first, request.idempotency_key.to_string());
     }
 
     #[test]
-    fn test_lighthouse_api_base_url_dev_with_paths() {
-        // Various path combinations
-        assert!(LighthouseAPIClientBaseURL::from("http://localhost/", Env::DEV).is_ok());
-        assert!(LighthouseAPIClientBaseURL::from("http://localhost/api/v1", Env::DEV).is_ok());
-        assert!(LighthouseAPIClientBaseURL::from(
-            "http://localhost:8080/api/v1/exercises",
-            Env::DEV
-        )
-        .is_ok());
+    fn test_request_timeout_from_env() {
+        assert_eq!(request_timeout(None), REQUEST_TIMEOUT);
+        assert_eq!(
+            request_timeout(Some("1500".to_string())),
+            Duration::from_millis(1500)
+        );
+        assert_eq!(request_timeout(Some("0".to_string())), REQUEST_TIMEOUT);
+        assert_eq!(request_timeout(Some("soon".to_string())), REQUEST_TIMEOUT);
     }
 
-    #[test]
-    fn test_lighthouse_api_base_url_release_subdomains() {
-        // Multiple subdomain levels
-        assert!(LighthouseAPIClientBaseURL::from(
-            "https://api.v2.projectlighthouse.io",
-            Env::RELEASE
-        )
-        .is_ok());
-        assert!(LighthouseAPIClientBaseURL::from(
-            "https://staging.api.projectlighthouse.io",
-            Env::RELEASE
-        )
-        .is_ok());
+    #[tokio::test]
+    async fn test_unreachable_host_fails_within_timeout() {
+        let client = build_client(Duration::from_millis(200));
+        let started = std::time::Instant::now();
+        // non-routable address: packets go nowhere, so only the timeout ends the wait
+        let result = client.get("http://10.255.255.1:81/").send().await;
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(2));
     }
 
     #[test]
-    fn test_lighthouse_api_base_url_error_messages() {
-        let dev_err =
-            LighthouseAPIClientBaseURL::from("https://example.com", Env::DEV).unwrap_err();
-        assert!(dev_err.contains("localhost"));
-        assert!(dev_err.contains("DEV"));
-
-        let release_err =
-            LighthouseAPIClientBaseURL::from("http://localhost", Env::RELEASE).unwrap_err();
-        assert!(release_err.contains("projectlighthouse.io"));
-        assert!(release_err.contains("RELEASE"));
+    fn test_env_display_dev() {
+        assert_eq!(format!("{}", Env::DEV), "dev");
     }
 
     #[test]
-    fn test_lighthouse_api_base_url_default_for_env_dev() {
-        let url = LighthouseAPIClientBaseURL::default_for_env(Env::DEV);
-        assert_eq!(url.0, "http://localhost:8000");
+    fn test_env_display_release() {
+        assert_eq!(format!("{}", Env::RELEASE), "release");
     }
 
     #[test]
-    fn test_lighthouse_api_base_url_default_for_env_release() {
-        let url = LighthouseAPIClientBaseURL::default_for_env(Env::RELEASE);
-        assert_eq!(url.0, "https://projectlighthouse.io");
+    fn test_env_clone() {
+        let env = Env::DEV;
+        let cloned = env;
+        assert_eq!(format!("{}", cloned), "dev");
     }
 
     #[test]
@@ -665,4 +1019,105 @@ mod tests {
         assert!(display.contains("https://projectlighthouse.io"));
         assert!(display.contains("release"));
     }
+
+    #[test]
+    fn test_redact_replaces_token() {
+        let token = "lux_live_abc123";
+        let error = format!(
+            "request failed: header Authorization: Bearer {} rejected, token={}",
+            token, token
+        );
+        assert_eq!(
+            redact(&error, token),
+            "request failed: header Authorization: Bearer *** rejected, token=***"
+        );
+    }
+
+    #[test]
+    fn test_redact_empty_secret_is_noop() {
+        assert_eq!(redact("nothing to hide", ""), "nothing to hide");
+    }
+
+    #[test]
+    fn test_client_redacts_its_token() {
+        let client = LighthouseAPIClient {
+            token: Some(SecretString::from("s3cr3t-token")),
+            ..Default::default()
+        };
+        assert_eq!(
+            client.redact("error sending request with s3cr3t-token"),
+            "error sending request with ***"
+        );
+
+        let anonymous = LighthouseAPIClient::default();
+        assert_eq!(anonymous.redact("s3cr3t-token"), "s3cr3t-token");
+    }
+
+    #[test]
+    fn test_resolve_env_var_overrides_file() {
+        assert!(matches!(
+            resolve_env(Some("release".to_string()), Some("dev")),
+            Env::RELEASE
+        ));
+        assert!(matches!(resolve_env(None, Some("release")), Env::RELEASE));
+        // unparseable env var falls through to the file
+        assert!(matches!(
+            resolve_env(Some("bogus".to_string()), Some("dev")),
+            Env::DEV
+        ));
+    }
+
+    #[test]
+    fn test_resolve_base_url_env_var_overrides_file() {
+        let url = resolve_base_url(
+            Some("http://localhost:7000".to_string()),
+            Some("http://localhost:9000"),
+            Env::DEV,
+        );
+        assert_eq!(url.0, "http://localhost:7000");
+
+        let url = resolve_base_url(None, Some("http://localhost:9000"), Env::DEV);
+        assert_eq!(url.0, "http://localhost:9000");
+    }
+
+    #[test]
+    fn test_resolve_base_url_invalid_file_url_falls_back() {
+        let url = resolve_base_url(None, Some("http://evil.example.com"), Env::RELEASE);
+        assert_eq!(url.0, "https://projectlighthouse.io");
+    }
+
+    #[test]
+    fn test_try_default_errors_on_invalid_base_url() {
+        with_env_vars(
+            &[
+                ("LUXCTL_ENV", Some("DEV")),
+                ("LUXCTL_API_BASE_URL", Some("https://invalid.com")),
+            ],
+            || {
+                let err = LighthouseAPIClient::try_default().err().unwrap();
+                assert!(err.to_string().starts_with("invalid LUXCTL_API_BASE_URL:"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_try_default_accepts_valid_base_url() {
+        with_env_vars(
+            &[
+                ("LUXCTL_ENV", Some("DEV")),
+                ("LUXCTL_API_BASE_URL", Some("http://127.0.0.1:9000")),
+            ],
+            || {
+                let api = LighthouseAPIClient::try_from_settings(&Settings::default()).unwrap();
+                assert_eq!(api.base_url, "http://127.0.0.1:9000");
+            },
+        );
+    }
+
+    #[test]
+    fn test_try_resolve_base_url_reports_file_source() {
+        let err =
+            try_resolve_base_url(None, Some("http://evil.example.com"), Env::RELEASE).unwrap_err();
+        assert!(err.starts_with("invalid api_base_url:"));
+    }
 }
@@ -1,9 +1,25 @@
+mod base_url;
 mod client;
 mod types;
 
-pub use client::{Env, LighthouseAPIClient};
+pub(crate) use base_url::LighthouseAPIClientBaseURL;
+pub(crate) use client::resolve_env;
+pub use client::{lab_fetch_error, redact, shared_client, user_agent, Env, LighthouseAPIClient};
 pub use types::{
-    ApiUser, AttemptData, Hint, Lab, LabStats, PaginatedResponse, PaginationLinks, PaginationMeta,
-    SubmitAnswerRequest, SubmitAnswerResponse, SubmitAttemptRequest, SubmitAttemptResponse, Task,
-    TaskInputType, TaskOutcome, TaskStatus,
+    ApiErrorCategory, ApiRequestError, ApiUser, AttemptData, Hint, Lab, LabStats,
+    PaginatedResponse, PaginationLinks, PaginationMeta, SubmitAnswerRequest, SubmitAnswerResponse,
+    SubmitAttemptRequest, SubmitAttemptResponse, Task, TaskInputType, TaskOutcome, TaskStatus,
+    MAX_CONTEXT_LEN,
 };
+
+#[cfg(test)]
+mod tests {
+    // the public api surface is re-exported from the directory module only
+    #[test]
+    fn test_public_types_resolve_from_api() {
+        fn assert_type<T>() {}
+        assert_type::<crate::api::ApiUser>();
+        assert_type::<crate::api::Env>();
+        assert_type::<crate::api::LighthouseAPIClient>();
+    }
+}
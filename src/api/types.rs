@@ -1,10 +1,76 @@
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
 pub struct ApiError {
     pub message: String,
 }
 
+/// what kind of failure an API response was, so callers can react (e.g. re-auth on 401)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiErrorCategory {
+    /// 401/403: missing, expired or rejected token
+    Auth,
+    NotFound,
+    /// 400/422: the request itself was rejected
+    Validation,
+    RateLimited,
+    /// 5xx
+    Server,
+    Other,
+}
+
+impl ApiErrorCategory {
+    pub fn from_status(status: u16) -> Self {
+        match status {
+            401 | 403 => ApiErrorCategory::Auth,
+            404 => ApiErrorCategory::NotFound,
+            400 | 422 => ApiErrorCategory::Validation,
+            429 => ApiErrorCategory::RateLimited,
+            500..=599 => ApiErrorCategory::Server,
+            _ => ApiErrorCategory::Other,
+        }
+    }
+}
+
+/// a non-success API response; displays as the API's message, like before
+#[derive(Debug, Clone)]
+pub struct ApiRequestError {
+    pub status: u16,
+    pub message: String,
+    pub category: ApiErrorCategory,
+}
+
+impl ApiRequestError {
+    /// parse `{"message": ...}` from the body, falling back to the raw text
+    pub fn from_body(status: u16, body: &str) -> Self {
+        let message = serde_json::from_str::<ApiError>(body)
+            .map(|e| e.message)
+            .unwrap_or_else(|_| body.to_string());
+        Self {
+            status,
+            message,
+            category: ApiErrorCategory::from_status(status),
+        }
+    }
+
+    pub fn is_auth(&self) -> bool {
+        self.category == ApiErrorCategory::Auth
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.category == ApiErrorCategory::NotFound
+    }
+}
+
+impl std::fmt::Display for ApiRequestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ApiRequestError {}
+
 #[derive(Debug, Deserialize)]
 pub struct HealthCheckResponse {
     pub status: String,
@@ -39,7 +105,7 @@ pub struct PaginationMeta {
     pub total: i32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct ApiUser {
     pub id: i32,
     pub name: String,
@@ -48,7 +114,7 @@ pub struct ApiUser {
     pub stats: Option<UserStats>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct UserStats {
     pub projects_attempted: i32,
     pub tasks_completed: i32,
@@ -145,6 +211,9 @@ pub struct Task {
     pub points_earned: i32,
     pub hints: Vec<Hint>,
     pub validators: Vec<String>,
+    /// port the task's server is expected to listen on
+    #[serde(default)]
+    pub port: Option<u16>,
     /// commands to run before validators (e.g., docker compose up)
     #[serde(default)]
     pub prologue: Vec<String>,
@@ -206,7 +275,7 @@ pub struct UnlockedHintData {
 }
 
 /// outcome values for task attempts
-#[derive(Debug, Clone, Copy, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TaskOutcome {
     Attempted,
@@ -224,8 +293,11 @@ impl std::fmt::Display for TaskOutcome {
     }
 }
 
+/// max length in bytes the API accepts for `task_outcome_context`
+pub const MAX_CONTEXT_LEN: usize = 5000;
+
 /// request body for submitting a task attempt
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubmitAttemptRequest {
     pub lab_slug: String,
     pub task_id: i32,
@@ -234,6 +306,9 @@ pub struct SubmitAttemptRequest {
     pub points_achieved: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub task_outcome_context: Option<String>,
+    /// generated once per run so the server can drop duplicate submissions on retry
+    #[serde(default = "Uuid::new_v4")]
+    pub idempotency_key: Uuid,
 }
 
 /// response from submitting a task attempt
@@ -294,6 +369,13 @@ pub struct SubmitAnswerResponse {
     pub already_completed: Option<bool>,
 }
 
+impl AttemptData {
+    /// a first pass: the attempt earned `points_achieved`, unlike a re-attempt or a failure
+    pub fn awards_points(&self) -> bool {
+        !self.is_reattempt && self.task_outcome == "passed"
+    }
+}
+
 impl ApiUser {
     pub fn id(&self) -> i32 {
         self.id
@@ -308,6 +390,39 @@ impl ApiUser {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_api_request_error_categories() {
+        let unauthorized = ApiRequestError::from_body(401, r#"{"message":"Unauthenticated."}"#);
+        assert_eq!(unauthorized.category, ApiErrorCategory::Auth);
+        assert!(unauthorized.is_auth());
+        assert_eq!(unauthorized.to_string(), "Unauthenticated.");
+
+        let invalid = ApiRequestError::from_body(422, r#"{"message":"task_id is required"}"#);
+        assert_eq!(invalid.category, ApiErrorCategory::Validation);
+        assert_eq!(invalid.status, 422);
+        assert_eq!(invalid.to_string(), "task_id is required");
+
+        let server = ApiRequestError::from_body(500, "<html>Server Error</html>");
+        assert_eq!(server.category, ApiErrorCategory::Server);
+        assert_eq!(server.to_string(), "<html>Server Error</html>");
+    }
+
+    #[test]
+    fn test_attempt_awards_points_on_first_pass() {
+        let attempt = |outcome: &str, is_reattempt| AttemptData {
+            id: 1,
+            task_id: 7,
+            lab_id: 2,
+            task_outcome: outcome.to_string(),
+            points_achieved: 50,
+            is_reattempt,
+            created_at: "2026-03-01T12:00:00Z".to_string(),
+        };
+        assert!(attempt("passed", false).awards_points());
+        assert!(!attempt("passed", true).awards_points());
+        assert!(!attempt("failed", false).awards_points());
+    }
+
     #[test]
     fn test_api_user_accessors() {
         let user = ApiUser {
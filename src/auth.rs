@@ -22,7 +22,7 @@ impl TokenAuthenticator {
 
         // Create a temporary config to build the client with token
         let cfg = Config::new(&self.token);
-        let client = LighthouseAPIClient::from_config(&cfg);
+        let client = LighthouseAPIClient::try_from_config(&cfg)?;
 
         let user = client.me().await?;
 
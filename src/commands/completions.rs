@@ -0,0 +1,140 @@
+use clap::Command;
+use clap_complete::{generate, Shell};
+use color_eyre::eyre::Result;
+use std::io::{self, Write};
+
+use crate::config::Config;
+use crate::state::{ActiveLab, LabState};
+
+/// bash: complete `run` and `task show` task arguments from `luxctl completions --tasks`,
+/// everything else through the generated `_luxctl`
+const BASH_TASKS: &str = r#"
+_luxctl_tasks() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}" first
+    case "${COMP_WORDS[1]} ${COMP_WORDS[2]}" in
+        "run "*) first=2 ;;
+        "task show") first=3 ;;
+        *) _luxctl "$@"; return ;;
+    esac
+    if [[ "$prev" == "-t" || "$prev" == "--task" ]] || [[ $COMP_CWORD -eq $first && "$cur" != -* ]]; then
+        COMPREPLY=($(compgen -W "$(luxctl completions --tasks 2>/dev/null)" -- "$cur"))
+        return 0
+    fi
+    _luxctl "$@"
+}
+
+if [[ "${BASH_VERSINFO[0]}" -eq 4 && "${BASH_VERSINFO[1]}" -ge 4 || "${BASH_VERSINFO[0]}" -gt 4 ]]; then
+    complete -F _luxctl_tasks -o nosort -o bashdefault -o default luxctl
+else
+    complete -F _luxctl_tasks -o bashdefault -o default luxctl
+fi
+"#;
+
+/// zsh: the completer swapped in for the task args of `run` and `task show`
+const ZSH_TASKS: &str = r#"
+_luxctl_tasks() {
+    local -a tasks
+    tasks=(${(f)"$(luxctl completions --tasks 2>/dev/null)"})
+    compadd -a tasks
+}
+"#;
+
+/// fish: task candidates for `run` and `task show`, positional or after `--task`
+const FISH_TASKS: &str = r#"
+complete -c luxctl -n "__fish_luxctl_using_subcommand run" -f -a "(luxctl completions --tasks)"
+complete -c luxctl -n "__fish_luxctl_using_subcommand run" -s t -l task -x -a "(luxctl completions --tasks)"
+complete -c luxctl -n "__fish_luxctl_using_subcommand task; and __fish_seen_subcommand_from show" -f -a "(luxctl completions --tasks)"
+complete -c luxctl -n "__fish_luxctl_using_subcommand task; and __fish_seen_subcommand_from show" -s t -l task -x -a "(luxctl completions --tasks)"
+"#;
+
+/// write the completion script for `shell` to `out`. bash, zsh and fish
+/// also complete task numbers and slugs for `run` and `task show`.
+pub fn write_script(shell: Shell, cmd: &mut Command, out: &mut dyn Write) -> io::Result<()> {
+    let name = cmd.get_name().to_string();
+    let mut generated = Vec::new();
+    generate(shell, cmd, name, &mut generated);
+    let script = String::from_utf8_lossy(&generated);
+
+    match shell {
+        Shell::Bash => write!(out, "{}{}", script, BASH_TASKS),
+        Shell::Zsh => {
+            // after the `#compdef` line, so the helper exists before `_luxctl` runs
+            let (compdef, rest) = script.split_once('\n').unwrap_or(("", &script));
+            let rest = rest
+                .replace(
+                    "'::task -- Task number or slug:_default'",
+                    "'::task -- Task number or slug:_luxctl_tasks'",
+                )
+                .replace(":TASK_FLAG:_default'", ":TASK_FLAG:_luxctl_tasks'");
+            write!(out, "{}\n{}{}", compdef, ZSH_TASKS, rest)
+        }
+        Shell::Fish => write!(out, "{}{}", script, FISH_TASKS),
+        _ => out.write_all(script.as_bytes()),
+    }
+}
+
+/// task numbers and slugs of the active lab, as accepted by `--task`
+pub fn task_candidates(lab: &ActiveLab) -> Vec<String> {
+    lab.tasks
+        .iter()
+        .enumerate()
+        .flat_map(|(i, task)| [(i + 1).to_string(), task.slug.clone()])
+        .collect()
+}
+
+/// handle `luxctl completions --tasks`: print task candidates from the cached
+/// active lab, one per line; the bash, zsh and fish scripts call it
+pub fn print_tasks() -> Result<()> {
+    let config = match Config::load() {
+        Ok(c) if c.has_auth_token() => c,
+        _ => return Ok(()),
+    };
+    let state = LabState::load(config.expose_token())?;
+    if let Some(lab) = state.get_active() {
+        for candidate in task_candidates(lab) {
+            println!("{}", candidate);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::TaskStatus;
+    use crate::state::CachedTask;
+
+    #[test]
+    fn test_task_candidates_lists_numbers_and_slugs() {
+        let task = |id: i32, slug: &str| CachedTask {
+            id,
+            slug: slug.to_string(),
+            title: slug.to_string(),
+            points: 10,
+            points_earned: 0,
+            status: TaskStatus::Challenged,
+            sort_order: id,
+            validators: vec![],
+        };
+        let lab = ActiveLab {
+            slug: "tcp-echo".to_string(),
+            name: "TCP Echo".to_string(),
+            fetched_at: chrono::Utc::now(),
+            tasks: vec![task(1, "bind-port"), task(2, "echo")],
+            workspace: ".".to_string(),
+            runtime: None,
+        };
+
+        assert_eq!(task_candidates(&lab), vec!["1", "bind-port", "2", "echo"]);
+    }
+
+    #[test]
+    fn test_write_script_for_bash() {
+        let mut cmd = Command::new("luxctl").subcommand(Command::new("run"));
+        let mut out = Vec::new();
+        write_script(Shell::Bash, &mut cmd, &mut out).unwrap();
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("luxctl"));
+        assert!(script.contains("run"));
+    }
+}
@@ -2,11 +2,13 @@
 
 use color_eyre::eyre::Result;
 use std::process::Command;
+use tokio::sync::OnceCell;
 
-use crate::api::LighthouseAPIClient;
+use crate::api::{ApiRequestError, LighthouseAPIClient};
 use crate::config::Config;
 use crate::state::LabState;
 use crate::ui::UI;
+use crate::validators::docker::is_docker_available;
 
 /// run all diagnostic checks
 pub async fn run() -> Result<()> {
@@ -26,7 +28,7 @@ pub async fn run() -> Result<()> {
 
     // development tools
     UI::section("Development Tools");
-    check_dev_tools();
+    check_dev_tools().await;
 
     // active lab
     UI::section("Lab State");
@@ -38,6 +40,11 @@ pub async fn run() -> Result<()> {
     Ok(())
 }
 
+/// the directory state is actually read from, so the report can't drift from it
+fn config_dir() -> Result<std::path::PathBuf> {
+    LabState::base_dir()
+}
+
 fn check_system_info() {
     let os = std::env::consts::OS;
     let arch = std::env::consts::ARCH;
@@ -45,9 +52,8 @@ fn check_system_info() {
     UI::ok("os", Some(os));
     UI::ok("arch", Some(arch));
 
-    match dirs::home_dir() {
-        Some(home) => {
-            let luxctl_dir = home.join(".luxctl");
+    match config_dir() {
+        Ok(luxctl_dir) => {
             if luxctl_dir.exists() {
                 UI::ok("config dir", Some(&luxctl_dir.to_string_lossy()));
             } else {
@@ -60,7 +66,7 @@ fn check_system_info() {
                 );
             }
         }
-        None => {
+        Err(_) => {
             UI::error("home dir", Some("could not determine home directory"));
         }
     }
@@ -99,7 +105,13 @@ fn check_auth() -> Option<Config> {
 }
 
 async fn check_network(config: &Option<Config>) {
-    let client = LighthouseAPIClient::default();
+    let client = match LighthouseAPIClient::try_default() {
+        Ok(c) => c,
+        Err(e) => {
+            UI::error("api url", Some(&format!("{}", e)));
+            return;
+        }
+    };
     match client.healthcheck().await {
         Ok(response) => {
             UI::ok("healthcheck", Some(&response.status));
@@ -123,29 +135,43 @@ async fn check_network(config: &Option<Config>) {
         return;
     };
 
-    let client = LighthouseAPIClient::from_config(config);
+    let client = match LighthouseAPIClient::try_from_config(config) {
+        Ok(c) => c,
+        Err(e) => {
+            UI::error("api url", Some(&format!("{}", e)));
+            return;
+        }
+    };
     match client.me().await {
         Ok(user) => {
             UI::ok("api", Some(&format!("connected as {}", user.email)));
         }
         Err(e) => {
-            UI::error("api", Some(&format!("{}", e)));
+            let auth_rejected = e
+                .downcast_ref::<ApiRequestError>()
+                .is_some_and(ApiRequestError::is_auth);
+            if auth_rejected {
+                UI::error("api", Some(&format!("token rejected: {}", e)));
+                UI::note("re-authenticate with `luxctl auth --token $token`");
+            } else {
+                UI::error("api", Some(&format!("{}", e)));
+            }
         }
     }
 }
 
-fn check_dev_tools() {
+async fn check_dev_tools() {
     let tools = vec![
         ToolCheck::new("git", &["--version"], true),
         ToolCheck::new("go", &["version"], false),
         ToolCheck::new("cargo", &["--version"], false),
         ToolCheck::new("rustc", &["--version"], false),
-        ToolCheck::new("docker", &["--version"], false),
     ];
 
     for tool in tools {
         tool.check();
     }
+    check_docker().await;
 
     UI::blank();
     UI::note("supported runtimes: go, rust");
@@ -190,6 +216,23 @@ impl ToolCheck {
     }
 }
 
+/// docker goes through the same availability check the docker validators use,
+/// so doctor can't report it usable while validators skip it
+async fn check_docker() {
+    if is_docker_available(&OnceCell::new()).await {
+        let version = Command::new("docker")
+            .arg("--version")
+            .output()
+            .ok()
+            .and_then(|output| extract_version(&output.stdout));
+        UI::ok("docker", version.as_deref());
+    } else {
+        UI::skip("docker", Some(DOCKER_UNAVAILABLE));
+    }
+}
+
+const DOCKER_UNAVAILABLE: &str = "not installed or daemon not running";
+
 /// extract version string from command output
 fn extract_version(output: &[u8]) -> Option<String> {
     let text = String::from_utf8_lossy(output);
@@ -244,6 +287,15 @@ fn check_lab_state(config: &Option<Config>) {
             lab.tasks.len()
         );
         UI::ok("progress", Some(&progress));
+
+        let points = format!(
+            "{}/{} ({:.0}%), {} remaining",
+            lab.earned_points(),
+            lab.total_points(),
+            lab.progress_percent(),
+            lab.remaining_points()
+        );
+        UI::ok("points", Some(&points));
     } else {
         UI::ok("lab", Some("none active"));
         UI::note("run `luxctl lab start --slug <SLUG>` to begin");
@@ -254,6 +306,12 @@ fn check_lab_state(config: &Option<Config>) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_config_dir_matches_state_location() {
+        let state_path = LabState::state_path().unwrap();
+        assert_eq!(config_dir().unwrap(), state_path.parent().unwrap());
+    }
+
     #[test]
     fn test_extract_version_git() {
         let output = b"git version 2.39.0";
@@ -0,0 +1,25 @@
+/// common workflows, printed by `luxctl examples`
+pub const WORKFLOWS: &str = "\
+get started:
+  luxctl auth --token $token
+  luxctl lab list
+  luxctl lab start --slug tcp-echo-server
+
+work on a task:
+  luxctl task list
+  luxctl task show 1
+  luxctl run 1
+  luxctl run 1 --watch
+
+check everything:
+  luxctl validate --all
+  luxctl lab status
+
+when something is off:
+  luxctl doctor
+  luxctl submit --flush";
+
+/// handle `luxctl examples`
+pub fn print() {
+    println!("{}", WORKFLOWS);
+}
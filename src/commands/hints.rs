@@ -16,7 +16,7 @@ pub async fn list(task_slug: &str) -> Result<()> {
         return Ok(());
     }
 
-    let client = LighthouseAPIClient::from_config(&config);
+    let client = LighthouseAPIClient::try_from_config(&config)?;
 
     let response = match client.hints(task_slug).await {
         Ok(r) => r,
@@ -65,7 +65,7 @@ pub async fn unlock(task_slug: &str, hint_uuid: &str) -> Result<()> {
         return Ok(());
     }
 
-    let client = LighthouseAPIClient::from_config(&config);
+    let client = LighthouseAPIClient::try_from_config(&config)?;
 
     let response = match client.unlock_hint(task_slug, hint_uuid).await {
         Ok(r) => r,
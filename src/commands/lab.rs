@@ -1,12 +1,12 @@
 use color_eyre::eyre::Result;
 
-use crate::api::LighthouseAPIClient;
+use crate::api::{lab_fetch_error, ApiRequestError, LighthouseAPIClient};
 use crate::config::Config;
 use crate::state::LabState;
 use crate::ui::UI;
 
 /// handle `luxctl lab start --slug <slug> --workspace <path> [--runtime <runtime>]`
-pub async fn start(slug: &str, workspace: &str, runtime: Option<&str>) -> Result<()> {
+pub async fn start(slug: &str, workspace: Option<&str>, runtime: Option<&str>) -> Result<()> {
     let config = Config::load()?;
     if !config.has_auth_token() {
         UI::error(
@@ -16,30 +16,30 @@ pub async fn start(slug: &str, workspace: &str, runtime: Option<&str>) -> Result
         return Ok(());
     }
 
-    let client = LighthouseAPIClient::from_config(&config);
+    let client = LighthouseAPIClient::try_from_config(&config)?;
 
     let lab = match client.lab_by_slug(slug).await {
         Ok(l) => l,
         Err(err) => {
-            UI::error(
-                &format!("lab '{}' not found", slug),
-                Some(&format!("{}", err)),
-            );
-            UI::note("run `luxctl lab list` to see available labs");
+            UI::error(&lab_fetch_error(slug, &err), None);
+            let not_found = err
+                .downcast_ref::<ApiRequestError>()
+                .is_some_and(ApiRequestError::is_not_found);
+            if not_found {
+                UI::note("run `luxctl lab list` to see available labs");
+            }
             return Ok(());
         }
     };
 
-    let workspace_path = std::path::Path::new(workspace);
-    let absolute_workspace = if workspace_path.is_absolute() {
-        workspace_path.to_path_buf()
-    } else {
-        std::env::current_dir()
-            .map_err(|e| color_eyre::eyre::eyre!("cannot get cwd: {}", e))?
-            .join(workspace_path)
-    };
+    // flags win over config.toml defaults
+    let settings = config.settings();
+    let workspace = workspace.or(settings.workspace.as_deref()).unwrap_or(".");
+    let runtime = runtime.or(settings.runtime.as_deref());
 
-    let workspace_str = absolute_workspace.to_string_lossy().to_string();
+    let cwd =
+        std::env::current_dir().map_err(|e| color_eyre::eyre::eyre!("cannot get cwd: {}", e))?;
+    let workspace_str = absolute_workspace(workspace, &cwd);
 
     let tasks = lab.tasks.as_deref().unwrap_or(&[]);
 
@@ -52,11 +52,21 @@ pub async fn start(slug: &str, workspace: &str, runtime: Option<&str>) -> Result
     if let Some(rt) = runtime {
         UI::kv("runtime", rt);
     }
-    UI::note("run `luxctl tasks` to see available tasks");
+    UI::note("run `luxctl task list` to see available tasks");
 
     Ok(())
 }
 
+/// resolve a workspace flag against `cwd`; absolute paths are kept
+fn absolute_workspace(workspace: &str, cwd: &std::path::Path) -> String {
+    let workspace_path = std::path::Path::new(workspace);
+    if workspace_path.is_absolute() {
+        workspace_path.to_string_lossy().to_string()
+    } else {
+        cwd.join(workspace_path).to_string_lossy().to_string()
+    }
+}
+
 /// handle `luxctl lab status`
 pub fn status() -> Result<()> {
     let config = Config::load()?;
@@ -197,3 +207,17 @@ pub fn set_workspace(workspace: &str) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_absolute_workspace() {
+        let cwd = Path::new("/home/me/code");
+        assert_eq!(absolute_workspace(".", cwd), "/home/me/code/.");
+        assert_eq!(absolute_workspace("echo", cwd), "/home/me/code/echo");
+        assert_eq!(absolute_workspace("/srv/echo", cwd), "/srv/echo");
+    }
+}
@@ -1,7 +1,12 @@
+pub mod completions;
 pub mod doctor;
+pub mod examples;
 pub mod hints;
 pub mod lab;
 pub mod run;
+pub mod stats;
+pub mod submit;
 pub mod task;
 pub mod tasks;
 pub mod validate;
+pub mod validators;
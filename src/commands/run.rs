@@ -1,26 +1,253 @@
 use color_eyre::eyre::Result;
+use notify::{EventKind, RecursiveMode, Watcher};
+use regex::Regex;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedReceiver;
+use uuid::Uuid;
 
-use crate::api::{LighthouseAPIClient, SubmitAttemptRequest, Task, TaskOutcome, TaskStatus};
+use crate::api::{
+    lab_fetch_error, LighthouseAPIClient, SubmitAttemptRequest, Task, TaskOutcome, TaskStatus,
+    MAX_CONTEXT_LEN,
+};
 use crate::config::Config;
+use crate::history::History;
+use crate::outbox::Outbox;
 use crate::shell;
-use crate::state::LabState;
-use crate::tasks::{TestCase, TestResults};
-use crate::ui::RunUI;
-use crate::validators::create_validator;
+use crate::state::{ActiveLab, LabState};
+use crate::tasks::{TestCase, TestResults, ValidationContext};
+use crate::ui::{confirm, suggest, RunUI};
+use crate::validators::{
+    create_validator, create_validator_with, parse_validator, RuntimeValidator,
+};
 use crate::{complain, oops, say};
 
-/// handle `luxctl run --task <slug|number> [--lab <slug>]`
+/// quiet period after the last file change before validators re-run
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// directories whose changes never trigger a re-run (vcs and build output)
+const WATCH_IGNORED_DIRS: &[&str] = &[".git", "target", "build", "dist", "node_modules"];
+
+/// outcome of a run, mapped to the process exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunOutcome {
+    /// every validator passed
+    Passed,
+    /// at least one validator failed
+    Failed,
+    /// validators couldn't run (auth, lookup, setup commands, missing docker)
+    SetupError,
+}
+
+impl RunOutcome {
+    pub fn from_results(results: &TestResults) -> Self {
+        if results.all_passed() {
+            RunOutcome::Passed
+        } else if results.failed() > 0 {
+            RunOutcome::Failed
+        } else {
+            // nothing failed, but skipped checks mean the task wasn't fully verified
+            RunOutcome::SetupError
+        }
+    }
+
+    pub fn exit_code(self) -> i32 {
+        match self {
+            RunOutcome::Passed => 0,
+            RunOutcome::Failed => 1,
+            RunOutcome::SetupError => 2,
+        }
+    }
+
+    /// combine outcomes across tasks, keeping the most severe
+    pub fn worst(self, other: RunOutcome) -> RunOutcome {
+        if other.exit_code() > self.exit_code() {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+/// whether a run reports its attempt to the API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitPolicy {
+    /// always report the attempt
+    Always,
+    /// ask before re-submitting a task that already passed
+    ConfirmReattempt,
+    /// verify locally only (`--no-submit`)
+    Never,
+}
+
+impl SubmitPolicy {
+    /// `confirm` is only asked for tasks that already passed
+    fn should_submit(self, already_passed: bool, confirm: impl FnOnce() -> bool) -> bool {
+        match self {
+            SubmitPolicy::Always => true,
+            SubmitPolicy::Never => false,
+            SubmitPolicy::ConfirmReattempt => !already_passed || confirm(),
+        }
+    }
+}
+
+/// how `run_task_validators` runs and reports a task
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    /// print request/response transcripts under each result
+    pub detailed: bool,
+    pub submit: SubmitPolicy,
+    /// deadline for the whole validator list; validators left are recorded as timed out
+    pub timeout: Option<Duration>,
+    /// stop at the first failing validator, skipping the rest
+    pub fail_fast: bool,
+    /// run only validators whose name matches one of these (substring or glob)
+    pub only: Vec<String>,
+    /// leave out validators whose name matches one of these
+    pub skip: Vec<String>,
+    /// run command validators instead of skipping them
+    pub allow_commands: bool,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            detailed: false,
+            submit: SubmitPolicy::Always,
+            timeout: None,
+            fail_fast: false,
+            only: Vec::new(),
+            skip: Vec::new(),
+            allow_commands: false,
+        }
+    }
+}
+
+impl RunOptions {
+    pub fn with_detailed(mut self, detailed: bool) -> Self {
+        self.detailed = detailed;
+        self
+    }
+
+    pub fn with_submit(mut self, submit: SubmitPolicy) -> Self {
+        self.submit = submit;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
+    pub fn with_only(mut self, patterns: Vec<String>) -> Self {
+        self.only = patterns;
+        self
+    }
+
+    pub fn with_skip(mut self, patterns: Vec<String>) -> Self {
+        self.skip = patterns;
+        self
+    }
+
+    pub fn with_allow_commands(mut self, allow: bool) -> Self {
+        self.allow_commands = allow;
+        self
+    }
+
+    /// whether `--only`/`--skip` keep this validator DSL
+    fn selects(&self, dsl: &str) -> bool {
+        // match on the validator name; unparseable DSLs are matched whole so they still report
+        let name = parse_validator(dsl)
+            .map(|p| p.name)
+            .unwrap_or_else(|_| dsl.to_string());
+        let matches = |pattern: &String| name_matches(pattern, &name);
+        (self.only.is_empty() || self.only.iter().any(matches)) && !self.skip.iter().any(matches)
+    }
+}
+
+/// `*`/`?` patterns must match the whole name; anything else is a substring match
+fn name_matches(pattern: &str, name: &str) -> bool {
+    if !pattern.contains(['*', '?']) {
+        return name.contains(pattern);
+    }
+    let regex = regex::escape(pattern)
+        .replace(r"\*", ".*")
+        .replace(r"\?", ".");
+    Regex::new(&format!("^{}$", regex)).is_ok_and(|re| re.is_match(name))
+}
+
+/// handle `luxctl run <slug|number> [--lab <slug>] [--watch]`
 /// task can be specified by slug or by number (1, 01, 2, 02, etc.)
-pub async fn run(task_id: &str, lab_slug: Option<&str>, detailed: bool) -> Result<()> {
+pub async fn run(
+    task_id: &str,
+    lab_slug: Option<&str>,
+    watch: bool,
+    options: &RunOptions,
+) -> Result<RunOutcome> {
+    let mut outcome = run_once(task_id, lab_slug, options).await?;
+    if !watch {
+        return Ok(outcome);
+    }
+
+    let root = watch_root();
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            let _ = tx.send(event);
+        }
+    })?;
+    watcher.watch(&root, RecursiveMode::Recursive)?;
+
+    // re-runs are a local feedback loop; only the first run reports an attempt
+    let rerun_options = options.clone().with_submit(SubmitPolicy::Never);
+
+    println!();
+    say!("watching {} for changes (ctrl+c to stop)", root.display());
+    say!("re-runs are not submitted; run without --watch to submit");
+
+    while let Some(changed) = next_change(&mut rx, &root).await {
+        let ui = RunUI::new(task_id, 0);
+        let changed = changed.strip_prefix(&root).unwrap_or(&changed);
+        ui.rerun_separator(&changed.display().to_string());
+
+        // a flaky network or state file shouldn't end the session
+        match run_once(task_id, lab_slug, &rerun_options).await {
+            Ok(rerun) => outcome = rerun,
+            Err(err) => {
+                log::error!("watch re-run failed: {}", err);
+                oops!("re-run failed: {}", err);
+            }
+        }
+
+        // hooks and builds touch files too; drop what they produced
+        settle(&mut rx, WATCH_DEBOUNCE).await;
+    }
+
+    Ok(outcome)
+}
+
+async fn run_once(
+    task_id: &str,
+    lab_slug: Option<&str>,
+    options: &RunOptions,
+) -> Result<RunOutcome> {
     let config = Config::load()?;
     if !config.has_auth_token() {
         oops!("not authenticated. Run: `luxctl auth --token $token`");
-        return Ok(());
+        return Ok(RunOutcome::SetupError);
     }
 
     let token = config.expose_token().to_string();
     let mut state = LabState::load(&token)?;
-    let client = LighthouseAPIClient::from_config(&config);
+    let client = LighthouseAPIClient::try_from_config(&config)?;
+    let allow_commands = options.allow_commands || config.settings().allow_commands;
+    let options = &options.clone().with_allow_commands(allow_commands);
 
     // determine lab slug (from arg or active lab)
     let lab_slug = match lab_slug {
@@ -31,7 +258,7 @@ pub async fn run(task_id: &str, lab_slug: Option<&str>, detailed: bool) -> Resul
             } else {
                 oops!("no lab specified and no active lab");
                 say!("use `--lab <SLUG>` or run `luxctl lab start --slug <SLUG>` first");
-                return Ok(());
+                return Ok(RunOutcome::SetupError);
             }
         }
     };
@@ -40,8 +267,8 @@ pub async fn run(task_id: &str, lab_slug: Option<&str>, detailed: bool) -> Resul
     let lab_data = match client.lab_by_slug(&lab_slug).await {
         Ok(l) => l,
         Err(err) => {
-            oops!("failed to fetch lab '{}': {}", lab_slug, err);
-            return Ok(());
+            oops!("{}", lab_fetch_error(&lab_slug, &err));
+            return Ok(RunOutcome::SetupError);
         }
     };
 
@@ -50,40 +277,28 @@ pub async fn run(task_id: &str, lab_slug: Option<&str>, detailed: bool) -> Resul
         t
     } else {
         oops!("lab '{}' has no tasks", lab_slug);
-        return Ok(());
+        return Ok(RunOutcome::SetupError);
     };
 
     // find task by number or slug
-    let task_data = if let Ok(task_num) = task_id.parse::<usize>() {
-        // task specified by number (1-based index)
-        if task_num == 0 || task_num > tasks.len() {
-            oops!(
-                "task #{} not found. valid range: 1-{}",
-                task_num,
-                tasks.len()
-            );
-            return Ok(());
-        }
-        &tasks[task_num - 1]
-    } else {
-        // task specified by slug
-        if let Some(t) = tasks.iter().find(|t| t.slug == task_id) {
-            t
-        } else {
-            oops!("task '{}' not found in lab '{}'", task_id, lab_slug);
-            say!("use task number (1, 2, 3...) or slug:");
-            for (i, t) in tasks.iter().enumerate() {
-                say!("  {:02}. {}", i + 1, t.slug);
-            }
-            return Ok(());
+    let lab = ActiveLab::from_api(&lab_data.slug, &lab_data.name, tasks);
+    let Some(task_data) = lab
+        .task_by_ref(task_id)
+        .and_then(|cached| tasks.iter().find(|t| t.id == cached.id))
+    else {
+        oops!("task '{}' not found in lab '{}'", task_id, lab_slug);
+        say!("use task number (1-{}) or slug:", tasks.len());
+        for (i, t) in tasks.iter().enumerate() {
+            say!("  {:02}. {}", i + 1, t.slug);
         }
+        return Ok(RunOutcome::SetupError);
     };
 
     run_task_validators(
         &client,
         &lab_data.slug,
         task_data,
-        detailed,
+        options,
         Some((&mut state, &token)),
     )
     .await
@@ -95,10 +310,19 @@ pub async fn run_task_validators(
     client: &LighthouseAPIClient,
     lab_slug: &str,
     task: &Task,
-    _detailed: bool,
+    options: &RunOptions,
     state_ctx: Option<(&mut LabState, &str)>,
-) -> Result<()> {
-    let ui = RunUI::new(&task.slug, task.validators.len());
+) -> Result<RunOutcome> {
+    let validators: Vec<String> = task
+        .validators
+        .iter()
+        .filter(|dsl| options.selects(dsl))
+        .cloned()
+        .collect();
+    let filtered = validators.len() < task.validators.len();
+    let ui = RunUI::new(&task.slug, validators.len());
+    // one key per run: client retries and outbox flushes reuse it
+    let idempotency_key = Uuid::new_v4();
 
     // check if task already completed
     let already_passed = task.status.is_completed();
@@ -106,82 +330,84 @@ pub async fn run_task_validators(
         complain!("you've already passed this task");
         say!("running validators anyway for verification...");
     }
+    // a filtered run can't pass the task, so it never reports
+    let submit = !filtered
+        && options
+            .submit
+            .should_submit(already_passed, || confirm("submit this re-attempt?"));
 
     ui.header();
     ui.blank_line();
 
+    let active_lab = state_ctx.as_ref().and_then(|(state, _)| state.get_active());
+    let workspace = active_lab.map(|lab| lab.workspace.clone());
+    let ctx = ValidationContext {
+        port: task.port,
+        project_path: workspace.as_ref().map(PathBuf::from),
+        runtime: active_lab.and_then(|lab| lab.runtime.clone()),
+        allow_commands: options.allow_commands,
+        // checked afresh each run, so docker started during --watch is noticed
+        docker_available: Arc::default(),
+    };
+    let hook_opts = hook_options(task, workspace.as_deref());
+
     // run prologue commands
     if !task.prologue.is_empty() {
         ui.step(&format!(
             "Running {} setup commands...",
             task.prologue.len()
         ));
-        if let Err((cmd, result)) = shell::run_commands(&task.prologue).await {
-            oops!("setup command failed: {}", cmd);
+        if let Err((cmd, result)) = shell::run_commands_with(&task.prologue, &hook_opts).await {
+            if result.timed_out {
+                oops!("setup command timed out: {}", cmd);
+            } else {
+                oops!("setup command failed: {}", cmd);
+            }
             if !result.stderr.is_empty() {
                 say!("stderr: {}", result.stderr.trim());
             }
             // run epilogue for cleanup even if prologue fails
-            run_epilogue(&ui, &task.epilogue).await;
-            return Ok(());
+            run_epilogue(&ui, &task.epilogue, &hook_opts).await;
+            return Ok(RunOutcome::SetupError);
         }
         ui.blank_line();
     }
 
     // run validators
-    if task.validators.is_empty() {
-        ui.step("no validators defined for this task");
-        run_epilogue(&ui, &task.epilogue).await;
-        return Ok(());
+    if validators.is_empty() {
+        if filtered {
+            ui.step("no validators match --only/--skip");
+        } else {
+            ui.step("no validators defined for this task");
+        }
+        run_epilogue(&ui, &task.epilogue, &hook_opts).await;
+        return Ok(RunOutcome::Passed);
     }
 
-    ui.step(&format!("Running {} validators...", task.validators.len()));
+    if filtered {
+        ui.step(&format!(
+            "Running {} of {} validators (filtered, results won't be submitted)...",
+            validators.len(),
+            task.validators.len()
+        ));
+    } else {
+        ui.step(&format!("Running {} validators...", validators.len()));
+    }
     ui.blank_line();
 
-    let mut results = TestResults::new();
-
-    for validator_str in task.validators.iter() {
-        log::debug!("parsing validator: {}", validator_str);
-
-        let validator = match create_validator(validator_str) {
-            Ok(v) => v,
-            Err(err) => {
-                oops!("invalid validator '{}': {}", validator_str, err);
-                continue;
-            }
-        };
-
-        match validator.validate().await {
-            Ok(test_case) => {
-                if test_case.passed() {
-                    ui.test_pass(&test_case.name);
-                } else {
-                    let detail = if test_case.message() != test_case.name {
-                        Some(test_case.message())
-                    } else {
-                        None
-                    };
-                    ui.test_fail(&test_case.name, detail);
-                }
-                results.add(test_case);
-            }
-            Err(err) => {
-                ui.test_fail(&err, None);
-                let failed_case = TestCase {
-                    name: err.clone(),
-                    result: Err(err),
-                };
-                results.add(failed_case);
-            }
-        }
-    }
+    let results = run_validators(&validators, &ctx, &ui, options).await;
 
     ui.blank_line();
     if results.all_passed() {
         ui.summary_pass(results.total());
+    } else if results.failed() > 0 {
+        ui.summary_fail(results.failed(), results.total());
     } else {
-        ui.summary_fail(results.passed(), results.total());
+        ui.summary_skipped(results.skipped(), results.total());
+    }
+    ui.timing(results.total_duration(), results.slowest());
 
+    if results.failed() > 0 {
         // show hints from task if available
         if !task.hints.is_empty() {
             for hint in &task.hints {
@@ -191,10 +417,13 @@ pub async fn run_task_validators(
     }
 
     // report results back to API
+    // skipped checks are neither a pass nor a wrong answer
     let outcome = if results.all_passed() {
         TaskOutcome::Passed
-    } else {
+    } else if results.failed() > 0 {
         TaskOutcome::Failed
+    } else {
+        TaskOutcome::Attempted
     };
 
     // build context string from test results
@@ -203,18 +432,25 @@ pub async fn run_task_validators(
         .iter()
         .enumerate()
         .map(|(i, t)| {
-            let status = if t.passed() { "PASS" } else { "FAIL" };
+            let status = if t.passed() {
+                "PASS"
+            } else if t.is_skipped() {
+                "SKIP"
+            } else {
+                "FAIL"
+            };
             format!("#{} [{}] {}: {}", i + 1, status, t.name, t.message())
         })
         .collect::<Vec<_>>()
         .join("\n");
 
-    // truncate context if too long (API limit is 5000 chars)
-    let context = if context.len() > 4900 {
-        format!("{}...[truncated]", &context[..4900])
-    } else {
-        context
-    };
+    let context = truncate_context(context, MAX_CONTEXT_LEN);
+
+    if !submit {
+        say!("results not submitted");
+        run_epilogue(&ui, &task.epilogue, &hook_opts).await;
+        return Ok(RunOutcome::from_results(&results));
+    }
 
     let attempt_request = SubmitAttemptRequest {
         lab_slug: lab_slug.to_string(),
@@ -222,6 +458,7 @@ pub async fn run_task_validators(
         task_outcome: outcome,
         points_achieved: None,
         task_outcome_context: Some(context),
+        idempotency_key,
     };
 
     match client.submit_attempt(&attempt_request).await {
@@ -231,6 +468,7 @@ pub async fn run_task_validators(
                 log::debug!("re-attempt recorded (no additional points)");
             } else if response.data.task_outcome == "passed" {
                 ui.points_earned(response.data.points_achieved);
+                History::record_pass(lab_slug, &task.slug, response.data.points_achieved);
             }
 
             // update cached task status if state context provided
@@ -249,17 +487,170 @@ pub async fn run_task_validators(
         Err(err) => {
             log::error!("failed to submit attempt: {}", err);
             oops!("failed to submit results: {}", err);
+            queue_submission(attempt_request, &task.slug);
         }
     }
 
     // run epilogue commands (cleanup)
-    run_epilogue(&ui, &task.epilogue).await;
+    run_epilogue(&ui, &task.epilogue, &hook_opts).await;
+
+    Ok(RunOutcome::from_results(&results))
+}
+
+/// run validators in order, printing each result. once `options.timeout` has
+/// passed, the running validator is cut off and the rest are not started; with
+/// `options.fail_fast` the first failure stops the run
+async fn run_validators(
+    validators: &[String],
+    ctx: &ValidationContext,
+    ui: &RunUI,
+    options: &RunOptions,
+) -> TestResults {
+    let deadline = options
+        .timeout
+        .map(|timeout| (tokio::time::Instant::now() + timeout, timeout));
+    let mut results = TestResults::new();
+
+    for (index, validator_str) in validators.iter().enumerate() {
+        if options.fail_fast && results.failed() > 0 {
+            let remaining = validators.len() - index;
+            ui.step(&format!(
+                "stopping at the first failure, {} validator(s) not run",
+                remaining
+            ));
+            break;
+        }
+
+        log::debug!("parsing validator: {}", validator_str);
+
+        // an invalid check counts as failed, so it can't be dropped from a passing run
+        let validator = match create_validator_with(validator_str, ctx) {
+            Ok(v) => v,
+            Err(err) => {
+                let invalid_case = TestCase::new(
+                    validator_str.clone(),
+                    Err(format!("invalid validator: {}", err)),
+                );
+                report_case(ui, &invalid_case, options.detailed);
+                results.add(invalid_case);
+                continue;
+            }
+        };
+
+        if let Some((at, timeout)) = deadline {
+            if tokio::time::Instant::now() >= at {
+                let test_case = timed_out_case(validator_str, timeout);
+                report_case(ui, &test_case, options.detailed);
+                results.add(test_case);
+                continue;
+            }
+        }
+
+        let started = Instant::now();
+        let work = async {
+            match deadline {
+                Some((at, timeout)) => tokio::time::timeout_at(at, validator.validate(ctx))
+                    .await
+                    .unwrap_or_else(|_| Ok(timed_out_case(validator_str, timeout))),
+                None => validator.validate(ctx).await,
+            }
+        };
+        let outcome = ui.spin_while(index, validator.name(), work).await;
+
+        match outcome {
+            Ok(mut test_case) => {
+                test_case.duration = started.elapsed();
+                report_case(ui, &test_case, options.detailed);
+                results.add(test_case);
+            }
+            Err(err) => {
+                ui.test_fail(validator.name(), Some(&err), started.elapsed());
+                let failed_case =
+                    TestCase::new(err.clone(), Err(err)).with_duration(started.elapsed());
+                if let Some(hint) = suggest(&failed_case) {
+                    ui.suggestion(hint);
+                }
+                results.add(failed_case);
+            }
+        }
+    }
+
+    results
+}
+
+/// print a validator result with its diff, suggestion and (detailed) transcript
+fn report_case(ui: &RunUI, test_case: &TestCase, detailed: bool) {
+    if test_case.passed() {
+        ui.test_pass(&test_case.name, test_case.duration);
+    } else if test_case.is_skipped() {
+        ui.test_skip(&test_case.name, test_case.message());
+    } else {
+        let detail = if test_case.message() != test_case.name {
+            Some(test_case.message())
+        } else {
+            None
+        };
+        ui.test_fail(&test_case.name, detail, test_case.duration);
+        if let Some(mismatch) = test_case.mismatch.as_ref().filter(|m| m.is_multiline()) {
+            ui.diff(mismatch);
+        }
+        if let Some(hint) = suggest(test_case) {
+            ui.suggestion(hint);
+        }
+    }
+    if let Some(transcript) = shown_details(test_case, detailed) {
+        ui.details(transcript);
+    }
+}
+
+/// a validator cut off (or never started) because the run deadline passed, named by its DSL
+fn timed_out_case(name: &str, timeout: Duration) -> TestCase {
+    TestCase::new(
+        name,
+        Err(format!(
+            "timed out: run deadline of {}s exceeded",
+            timeout.as_secs_f64()
+        )),
+    )
+}
+
+/// the transcript printed under a validator result, only in detailed mode
+fn shown_details(test_case: &TestCase, detailed: bool) -> Option<&str> {
+    test_case.details.as_deref().filter(|_| detailed)
+}
+
+/// appended to a context that was cut to fit the API limit
+const TRUNCATION_MARKER: &str = "...[truncated]";
+
+/// cut `context` to at most `max` bytes, marker included, without splitting a utf-8 character
+fn truncate_context(context: String, max: usize) -> String {
+    if context.len() <= max {
+        return context;
+    }
+    let budget = max.saturating_sub(TRUNCATION_MARKER.len());
+    let end = context
+        .char_indices()
+        .map(|(i, _)| i)
+        .take_while(|&i| i <= budget)
+        .last()
+        .unwrap_or(0);
+    format!("{}{}", &context[..end], TRUNCATION_MARKER)
+}
 
-    Ok(())
+/// keep an unsent attempt so `luxctl submit --flush` can retry it later
+fn queue_submission(request: SubmitAttemptRequest, task_slug: &str) {
+    let queued = Outbox::load().and_then(|mut outbox| {
+        outbox.enqueue(request, task_slug);
+        outbox.save()
+    });
+    match queued {
+        Ok(()) => say!("results saved. retry with `luxctl submit --flush`"),
+        Err(e) => log::warn!("failed to queue submission: {}", e),
+    }
 }
 
 /// run epilogue commands with best-effort (continues even on failure)
-async fn run_epilogue(ui: &RunUI, commands: &[String]) {
+async fn run_epilogue(ui: &RunUI, commands: &[String], opts: &shell::CommandOptions) {
     if commands.is_empty() {
         return;
     }
@@ -267,8 +658,12 @@ async fn run_epilogue(ui: &RunUI, commands: &[String]) {
     ui.blank_line();
     ui.step(&format!("Running {} cleanup commands...", commands.len()));
 
-    let failures = shell::run_commands_best_effort(commands).await;
+    let failures = shell::run_commands_best_effort_with(commands, opts).await;
     for (cmd, result) in failures {
+        if result.timed_out {
+            log::warn!("cleanup command timed out: {}", cmd);
+            continue;
+        }
         log::warn!(
             "cleanup command failed: {} (exit {})",
             cmd,
@@ -280,6 +675,112 @@ async fn run_epilogue(ui: &RunUI, commands: &[String]) {
     }
 }
 
+/// environment and working directory for prologue/epilogue hooks
+/// hooks run from the lab workspace when one is known
+fn hook_options(task: &Task, workspace: Option<&str>) -> shell::CommandOptions {
+    let mut opts = shell::CommandOptions::default().with_env("LUX_TASK_SLUG", &task.slug);
+    if let Some(workspace) = workspace {
+        opts = opts
+            .with_env("LUX_WORKSPACE", workspace)
+            .with_cwd(workspace);
+    }
+    if let Some(port) = task_port(task) {
+        opts = opts.with_env("LUX_PORT", &port.to_string());
+    }
+    opts
+}
+
+/// port the task expects the server on: its metadata, else its first tcp_listening validator
+fn task_port(task: &Task) -> Option<u16> {
+    task.port.or_else(|| {
+        task.validators
+            .iter()
+            .filter_map(|v| create_validator(v).ok())
+            .find_map(|v| match v {
+                RuntimeValidator::TcpListening(p) => Some(p.port()),
+                _ => None,
+            })
+    })
+}
+
+/// directory to watch: the active lab's workspace, or the current directory
+fn watch_root() -> PathBuf {
+    let workspace = Config::load()
+        .ok()
+        .filter(|c| c.has_auth_token())
+        .and_then(|c| LabState::load(c.expose_token()).ok())
+        .and_then(|state| state.get_active().map(|lab| lab.workspace.clone()))
+        .unwrap_or_else(|| ".".to_string());
+    let path = PathBuf::from(workspace);
+    path.canonicalize().unwrap_or(path)
+}
+
+/// true when a path sits inside vcs metadata or build output
+fn is_ignored(path: &Path, root: &Path) -> bool {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .any(|c| match c {
+            Component::Normal(name) => WATCH_IGNORED_DIRS.iter().any(|d| name == *d),
+            _ => false,
+        })
+}
+
+/// wait for a burst of relevant changes to go quiet; returns one changed path
+async fn next_change(rx: &mut UnboundedReceiver<notify::Event>, root: &Path) -> Option<PathBuf> {
+    let mut debouncer = Debouncer::new(WATCH_DEBOUNCE);
+    let mut changed = None;
+    loop {
+        let event = match debouncer.remaining(Instant::now()) {
+            None => rx.recv().await?,
+            Some(wait) => match tokio::time::timeout(wait, rx.recv()).await {
+                Ok(event) => event?,
+                Err(_) => return changed,
+            },
+        };
+        if matches!(event.kind, EventKind::Access(_)) {
+            continue;
+        }
+        if let Some(path) = event.paths.into_iter().find(|p| !is_ignored(p, root)) {
+            changed = Some(path);
+            debouncer.record(Instant::now());
+        }
+    }
+}
+
+/// discard events that arrive within `window` of a run finishing
+async fn settle(rx: &mut UnboundedReceiver<notify::Event>, window: Duration) {
+    let deadline = tokio::time::Instant::now() + window;
+    while let Ok(Some(_)) = tokio::time::timeout_at(deadline, rx.recv()).await {}
+}
+
+/// collapses a burst of file events into a single trigger
+struct Debouncer {
+    window: Duration,
+    last_event: Option<Instant>,
+}
+
+impl Debouncer {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            last_event: None,
+        }
+    }
+
+    fn record(&mut self, at: Instant) {
+        self.last_event = Some(at);
+    }
+
+    /// time left until the pending burst settles; None when nothing is pending
+    fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.last_event.map(|last| {
+            self.window
+                .saturating_sub(now.saturating_duration_since(last))
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -306,11 +807,227 @@ mod tests {
             points_earned: 0,
             hints: vec![],
             validators,
+            port: None,
             prologue,
             epilogue,
         }
     }
 
+    #[test]
+    fn test_submit_policy() {
+        assert!(SubmitPolicy::Always.should_submit(true, || false));
+        assert!(!SubmitPolicy::Never.should_submit(false, || true));
+        assert!(SubmitPolicy::ConfirmReattempt.should_submit(false, || false));
+        assert!(SubmitPolicy::ConfirmReattempt.should_submit(true, || true));
+        assert!(!SubmitPolicy::ConfirmReattempt.should_submit(true, || false));
+    }
+
+    #[tokio::test]
+    async fn test_no_submit_never_calls_the_api() {
+        use crate::api::{Env, LighthouseAPIClientBaseURL};
+        use crate::validators::test_support::serve_counting;
+        use std::sync::atomic::Ordering;
+
+        // count every connection the client makes
+        let (port, hits) = serve_counting(|_| None).await;
+
+        let base_url =
+            LighthouseAPIClientBaseURL::from(&format!("http://127.0.0.1:{}", port), Env::DEV)
+                .unwrap();
+        let client = LighthouseAPIClient::new(base_url, "v1", Env::DEV, None);
+        let task = make_task_with_hooks(vec![], vec![], vec!["tcp_not_listening:int(1)".into()]);
+
+        let outcome = run_task_validators(
+            &client,
+            "test-lab",
+            &task,
+            &RunOptions::default().with_submit(SubmitPolicy::Never),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(outcome, RunOutcome::Passed);
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_cuts_off_remaining_validators() {
+        use tokio::net::TcpListener;
+
+        // accepts connections but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let mut open = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                open.push(socket);
+            }
+        });
+
+        let validators = vec![
+            "http_get:string(/),int(200)".to_string(),
+            "http_get:string(/health),int(200)".to_string(),
+        ];
+        let ctx = ValidationContext {
+            port: Some(port),
+            ..Default::default()
+        };
+        let options = RunOptions::default().with_timeout(Some(Duration::from_millis(200)));
+        let ui = RunUI::new("hung-task", validators.len());
+
+        let started = Instant::now();
+        let results = run_validators(&validators, &ctx, &ui, &options).await;
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        assert_eq!(results.total(), 2);
+        assert_eq!(results.failed(), 2);
+        for test_case in &results.tests {
+            assert!(test_case
+                .message()
+                .contains("timed out: run deadline of 0.2s exceeded"));
+        }
+        assert_eq!(results.tests[1].name, "http_get:string(/health),int(200)");
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast_skips_rest_but_runs_epilogue() {
+        use crate::api::{Env, LighthouseAPIClientBaseURL};
+        use crate::validators::test_support::serve_counting;
+        use std::sync::atomic::Ordering;
+        use tokio::net::TcpListener;
+
+        // a port nothing listens on, so the first validator fails
+        let closed = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let closed_port = closed.local_addr().unwrap().port();
+        drop(closed);
+
+        // the second validator would connect here
+        let (port, hits) = serve_counting(|_| None).await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("cleaned-up");
+        let task = make_task_with_hooks(
+            vec![],
+            vec![format!("touch {}", marker.display())],
+            vec![
+                format!("tcp_listening:int({})", closed_port),
+                format!("tcp_listening:int({})", port),
+            ],
+        );
+
+        let base_url =
+            LighthouseAPIClientBaseURL::from(&format!("http://127.0.0.1:{}", port), Env::DEV)
+                .unwrap();
+        let client = LighthouseAPIClient::new(base_url, "v1", Env::DEV, None);
+        let options = RunOptions::default()
+            .with_submit(SubmitPolicy::Never)
+            .with_fail_fast(true);
+
+        let outcome = run_task_validators(&client, "test-lab", &task, &options, None)
+            .await
+            .unwrap();
+        assert_eq!(outcome, RunOutcome::Failed);
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
+        assert!(marker.exists());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_validator_counts_as_failed() {
+        use tokio::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let validators = vec![
+            format!("tcp_listening:int({})", port),
+            "http_get:string(/),,int(200)".to_string(),
+        ];
+        let ui = RunUI::new("invalid-task", validators.len());
+        let results = run_validators(
+            &validators,
+            &ValidationContext::default(),
+            &ui,
+            &RunOptions::default(),
+        )
+        .await;
+
+        assert_eq!(results.total(), 2);
+        assert_eq!(results.failed(), 1);
+        assert!(!results.all_passed());
+        assert_eq!(results.tests[1].name, "http_get:string(/),,int(200)");
+        assert!(results.tests[1]
+            .message()
+            .starts_with("invalid validator: "));
+        assert_eq!(RunOutcome::from_results(&results), RunOutcome::Failed);
+        drop(listener);
+    }
+
+    #[test]
+    fn test_only_and_skip_select_validators_by_name() {
+        let validators = [
+            "tcp_listening:int(4221)",
+            "http_get:string(/),int(200)",
+            "http_get_with_header:string(/echo),string(X-A),string(b),int(200)",
+            "http_header_present:string(Content-Type),bool(true)",
+        ];
+        let selected = |options: RunOptions| -> Vec<&str> {
+            validators
+                .iter()
+                .copied()
+                .filter(|dsl| options.selects(dsl))
+                .collect()
+        };
+
+        let only_get = RunOptions::default().with_only(vec!["http_get".to_string()]);
+        assert_eq!(selected(only_get), vec![validators[1], validators[2]]);
+
+        let exact_get = RunOptions::default()
+            .with_only(vec!["http_get".to_string()])
+            .with_skip(vec!["*header*".to_string()]);
+        assert_eq!(selected(exact_get), vec![validators[1]]);
+
+        let glob = RunOptions::default().with_only(vec!["http_*".to_string()]);
+        assert_eq!(selected(glob).len(), 3);
+
+        let skip_tcp = RunOptions::default().with_skip(vec!["tcp".to_string()]);
+        assert_eq!(selected(skip_tcp).len(), 3);
+
+        assert_eq!(selected(RunOptions::default()).len(), 4);
+    }
+
+    #[test]
+    fn test_name_matches() {
+        assert!(name_matches("get", "http_get"));
+        assert!(name_matches("http_*", "http_get"));
+        assert!(name_matches("tcp_?istening", "tcp_listening"));
+        assert!(!name_matches("http_*", "tcp_listening"));
+        assert!(!name_matches("get*", "http_get"));
+        assert!(!name_matches("a.b*", "axb"));
+    }
+
+    #[tokio::test]
+    async fn test_detailed_mode_shows_response_preview() {
+        use crate::validators::test_support::serve_once;
+        use crate::validators::HttpGetValidator;
+
+        let port = serve_once(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 13\r\nConnection: close\r\n\r\nHello, World!",
+        )
+        .await;
+
+        let mut validator = HttpGetValidator::new("/", 200, Some("Hello, World!".to_string()));
+        validator.port = port;
+        let test_case = validator.validate().await.unwrap();
+        assert!(test_case.passed());
+
+        let transcript = shown_details(&test_case, true).unwrap();
+        assert!(transcript.contains("> GET /"));
+        assert!(transcript.contains("< HTTP/1.1 200 OK"));
+        assert!(transcript.contains("< content-type: text/plain"));
+        assert!(transcript.contains("< Hello, World!"));
+        assert_eq!(shown_details(&test_case, false), None);
+    }
+
     #[test]
     fn test_task_with_empty_hooks() {
         let task = make_task_with_hooks(vec![], vec![], vec![]);
@@ -369,4 +1086,186 @@ mod tests {
         let result = shell::run_commands(&commands).await;
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_hook_options_env() {
+        let task = make_task_with_hooks(
+            vec![],
+            vec![],
+            vec![
+                "http_get:string(/),int(200)".to_string(),
+                "tcp_listening:int(8080)".to_string(),
+            ],
+        );
+
+        let opts = hook_options(&task, Some("/home/me/lab"));
+        assert_eq!(opts.env.get("LUX_TASK_SLUG").unwrap(), "test-task");
+        assert_eq!(opts.env.get("LUX_WORKSPACE").unwrap(), "/home/me/lab");
+        assert_eq!(opts.env.get("LUX_PORT").unwrap(), "8080");
+        assert_eq!(opts.cwd, Some(std::path::PathBuf::from("/home/me/lab")));
+    }
+
+    #[test]
+    fn test_hook_options_without_workspace_or_port() {
+        let task = make_task_with_hooks(vec![], vec![], vec![]);
+        let opts = hook_options(&task, None);
+        assert!(!opts.env.contains_key("LUX_WORKSPACE"));
+        assert!(!opts.env.contains_key("LUX_PORT"));
+        assert!(opts.cwd.is_none());
+    }
+
+    fn results_with(outcomes: &[bool]) -> TestResults {
+        let mut results = TestResults::new();
+        for (i, passed) in outcomes.iter().enumerate() {
+            results.add(
+                TestCase::new(
+                    format!("check {}", i),
+                    if *passed {
+                        Ok(String::new())
+                    } else {
+                        Err(String::new())
+                    },
+                )
+                .with_duration(std::time::Duration::ZERO),
+            );
+        }
+        results
+    }
+
+    #[test]
+    fn test_run_outcome_all_passed_exits_zero() {
+        let outcome = RunOutcome::from_results(&results_with(&[true, true]));
+        assert_eq!(outcome, RunOutcome::Passed);
+        assert_eq!(outcome.exit_code(), 0);
+    }
+
+    #[test]
+    fn test_run_outcome_some_failed_exits_one() {
+        let outcome = RunOutcome::from_results(&results_with(&[true, false, true]));
+        assert_eq!(outcome, RunOutcome::Failed);
+        assert_eq!(outcome.exit_code(), 1);
+    }
+
+    #[test]
+    fn test_run_outcome_skips_without_failures() {
+        let mut results = results_with(&[true]);
+        results.add(TestCase::skip("docker:Go1.22-race", "docker not available"));
+        assert_eq!(RunOutcome::from_results(&results), RunOutcome::SetupError);
+
+        let mut results_with_failure = results_with(&[true, false]);
+        results_with_failure.add(TestCase::skip("docker", "docker not available"));
+        assert_eq!(
+            RunOutcome::from_results(&results_with_failure),
+            RunOutcome::Failed
+        );
+    }
+
+    #[test]
+    fn test_run_outcome_setup_error_exits_two() {
+        assert_eq!(RunOutcome::SetupError.exit_code(), 2);
+    }
+
+    #[test]
+    fn test_run_outcome_worst() {
+        assert_eq!(
+            RunOutcome::Passed.worst(RunOutcome::Failed),
+            RunOutcome::Failed
+        );
+        assert_eq!(
+            RunOutcome::SetupError.worst(RunOutcome::Failed),
+            RunOutcome::SetupError
+        );
+        assert_eq!(
+            RunOutcome::Passed.worst(RunOutcome::Passed),
+            RunOutcome::Passed
+        );
+    }
+
+    #[test]
+    fn test_debouncer_idle_until_first_event() {
+        let debouncer = Debouncer::new(Duration::from_millis(500));
+        assert_eq!(debouncer.remaining(Instant::now()), None);
+    }
+
+    #[test]
+    fn test_debouncer_waits_for_quiet_period() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(500));
+        let start = Instant::now();
+        debouncer.record(start);
+        assert_eq!(
+            debouncer.remaining(start + Duration::from_millis(200)),
+            Some(Duration::from_millis(300))
+        );
+        assert_eq!(
+            debouncer.remaining(start + Duration::from_millis(600)),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_debouncer_new_event_restarts_window() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(500));
+        let start = Instant::now();
+        debouncer.record(start);
+        debouncer.record(start + Duration::from_millis(400));
+        assert_eq!(
+            debouncer.remaining(start + Duration::from_millis(600)),
+            Some(Duration::from_millis(300))
+        );
+    }
+
+    #[test]
+    fn test_is_ignored_skips_vcs_and_build_output() {
+        let root = Path::new("/work/lab");
+        assert!(is_ignored(Path::new("/work/lab/.git/index"), root));
+        assert!(is_ignored(Path::new("/work/lab/target/debug/app"), root));
+        assert!(!is_ignored(Path::new("/work/lab/src/main.rs"), root));
+        // only components below the root count
+        assert!(!is_ignored(
+            Path::new("/build/lab/main.go"),
+            Path::new("/build/lab")
+        ));
+    }
+
+    #[test]
+    fn test_truncate_context_short_is_unchanged() {
+        assert_eq!(
+            truncate_context("#1 [PASS] ok".to_string(), MAX_CONTEXT_LEN),
+            "#1 [PASS] ok"
+        );
+    }
+
+    #[test]
+    fn test_truncate_context_respects_char_boundaries() {
+        // "é" is 2 bytes, so the cut point lands inside a character
+        let budget = MAX_CONTEXT_LEN - TRUNCATION_MARKER.len();
+        let context = format!("{}{}", "a".repeat(budget - 1), "é".repeat(100));
+        assert!(!context.is_char_boundary(budget));
+
+        let truncated = truncate_context(context, MAX_CONTEXT_LEN);
+        assert!(truncated.ends_with(TRUNCATION_MARKER));
+        assert_eq!(
+            truncated.trim_end_matches(TRUNCATION_MARKER),
+            "a".repeat(budget - 1)
+        );
+    }
+
+    #[test]
+    fn test_truncate_context_multibyte_text() {
+        let context = "日本語".repeat(2000);
+        let truncated = truncate_context(context, MAX_CONTEXT_LEN);
+        let kept = truncated.trim_end_matches(TRUNCATION_MARKER);
+        assert!(kept.chars().all(|c| "日本語".contains(c)));
+    }
+
+    #[test]
+    fn test_truncate_context_never_exceeds_api_limit() {
+        for len in [MAX_CONTEXT_LEN, MAX_CONTEXT_LEN + 1, MAX_CONTEXT_LEN * 10] {
+            let truncated = truncate_context("x".repeat(len), MAX_CONTEXT_LEN);
+            assert!(truncated.len() <= MAX_CONTEXT_LEN);
+        }
+        let truncated = truncate_context("é".repeat(MAX_CONTEXT_LEN), MAX_CONTEXT_LEN);
+        assert!(truncated.len() <= MAX_CONTEXT_LEN);
+        assert!(truncated.ends_with(TRUNCATION_MARKER));
+    }
 }
@@ -0,0 +1,39 @@
+use chrono::Local;
+use color_eyre::eyre::Result;
+
+use crate::history::History;
+use crate::ui::UI;
+
+/// entries listed under "recent"
+const RECENT_ENTRIES: usize = 5;
+
+/// handle `luxctl stats`
+pub fn show() -> Result<()> {
+    let entries = History::load()?;
+    if entries.is_empty() {
+        UI::info("no points recorded yet");
+        UI::note("pass a task with `luxctl run <task>` to start your history");
+        return Ok(());
+    }
+
+    let summary = History::summarize(&entries, Local::now().date_naive(), RECENT_ENTRIES);
+    UI::kv("points earned", &summary.total_points.to_string());
+    UI::kv("tasks passed", &summary.tasks_passed.to_string());
+    UI::kv("active days", &summary.active_days.to_string());
+    UI::kv("streak", &format!("{} day(s)", summary.streak));
+
+    UI::blank();
+    UI::info("recent");
+    for entry in &summary.recent {
+        UI::kv(
+            &entry
+                .at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+            &format!("{}/{} +{}", entry.lab_slug, entry.task_slug, entry.points),
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use color_eyre::eyre::Result;
+
+use crate::api::LighthouseAPIClient;
+use crate::config::Config;
+use crate::history::History;
+use crate::outbox::Outbox;
+use crate::ui::UI;
+
+/// handle `luxctl submit [--flush]`
+pub async fn submit(flush: bool) -> Result<()> {
+    let mut outbox = Outbox::load()?;
+    if outbox.is_empty() {
+        UI::info("no pending submissions");
+        return Ok(());
+    }
+
+    if !flush {
+        UI::info(&format!("{} pending submission(s)", outbox.len()));
+        for entry in &outbox.pending {
+            UI::kv(
+                &format!("{} task {}", entry.request.lab_slug, entry.request.task_id),
+                &format!(
+                    "{} (queued {})",
+                    entry.request.task_outcome,
+                    entry.queued_at.format("%Y-%m-%d %H:%M")
+                ),
+            );
+        }
+        UI::note("run `luxctl submit --flush` to send them");
+        return Ok(());
+    }
+
+    let config = Config::load()?;
+    if !config.has_auth_token() {
+        UI::error(
+            "not authenticated",
+            Some("run `luxctl auth --token $token`"),
+        );
+        return Ok(());
+    }
+
+    let client = LighthouseAPIClient::try_from_config(&config)?;
+    let report = outbox
+        .flush(|entry| {
+            let client = &client;
+            async move {
+                let response = client.submit_attempt(&entry.request).await?;
+                // a pass queued while offline still counts toward `luxctl stats`
+                if response.data.awards_points() {
+                    History::record_pass(
+                        &entry.request.lab_slug,
+                        &entry.task_label(),
+                        response.data.points_achieved,
+                    );
+                }
+                Ok(())
+            }
+        })
+        .await;
+    outbox.save()?;
+
+    if report.submitted > 0 {
+        UI::success(&format!("submitted {} result(s)", report.submitted));
+    }
+    for err in &report.errors {
+        UI::error("failed to submit", Some(err));
+    }
+    if !outbox.is_empty() {
+        UI::note(&format!("{} submission(s) still pending", outbox.len()));
+    }
+
+    Ok(())
+}
@@ -1,9 +1,9 @@
 use color_eyre::eyre::Result;
 
-use crate::api::LighthouseAPIClient;
+use crate::api::{lab_fetch_error, LighthouseAPIClient};
 use crate::config::Config;
 use crate::message::Message;
-use crate::state::LabState;
+use crate::state::{ActiveLab, LabState};
 use crate::ui::UI;
 
 /// handle `luxctl task --task <slug|number> [--detailed]`
@@ -18,7 +18,7 @@ pub async fn show(task_id: &str, detailed: bool) -> Result<()> {
     }
 
     let state = LabState::load(config.expose_token())?;
-    let client = LighthouseAPIClient::from_config(&config);
+    let client = LighthouseAPIClient::try_from_config(&config)?;
 
     let lab_slug = if let Some(l) = state.get_active() {
         l.slug.clone()
@@ -31,10 +31,7 @@ pub async fn show(task_id: &str, detailed: bool) -> Result<()> {
     let lab_data = match client.lab_by_slug(&lab_slug).await {
         Ok(l) => l,
         Err(err) => {
-            UI::error(
-                &format!("failed to fetch lab '{}'", lab_slug),
-                Some(&format!("{}", err)),
-            );
+            UI::error(&lab_fetch_error(&lab_slug, &err), None);
             return Ok(());
         }
     };
@@ -46,21 +43,14 @@ pub async fn show(task_id: &str, detailed: bool) -> Result<()> {
         return Ok(());
     };
 
-    let task_data = if let Ok(task_num) = task_id.parse::<usize>() {
-        if task_num == 0 || task_num > tasks.len() {
-            UI::error(
-                &format!("task #{} not found", task_num),
-                Some(&format!("valid range: 1-{}", tasks.len())),
-            );
-            return Ok(());
-        }
-        &tasks[task_num - 1]
-    } else if let Some(t) = tasks.iter().find(|t| t.slug == task_id) {
-        t
-    } else {
+    let lab = ActiveLab::from_api(&lab_data.slug, &lab_data.name, tasks);
+    let Some(task_data) = lab
+        .task_by_ref(task_id)
+        .and_then(|cached| tasks.iter().find(|t| t.id == cached.id))
+    else {
         UI::error(
             &format!("task '{}' not found in lab '{}'", task_id, lab_slug),
-            None,
+            Some(&format!("use a task number (1-{}) or slug", tasks.len())),
         );
         return Ok(());
     };
@@ -1,6 +1,6 @@
 use color_eyre::eyre::Result;
 
-use crate::api::LighthouseAPIClient;
+use crate::api::{lab_fetch_error, LighthouseAPIClient};
 use crate::config::Config;
 use crate::message::Message;
 use crate::state::LabState;
@@ -28,24 +28,8 @@ pub async fn list(refresh: bool) -> Result<()> {
     };
 
     // refresh from API if requested or no cached tasks
-    if refresh || lab.tasks.is_empty() {
-        let client = LighthouseAPIClient::from_config(&config);
-
-        let fresh_lab = match client.lab_by_slug(&lab.slug).await {
-            Ok(l) => l,
-            Err(err) => {
-                UI::error(
-                    &format!("failed to fetch lab '{}'", lab.slug),
-                    Some(&format!("{}", err)),
-                );
-                return Ok(());
-            }
-        };
-
-        if let Some(tasks) = &fresh_lab.tasks {
-            state.refresh_tasks(tasks);
-            state.save(config.expose_token())?;
-        }
+    if (refresh || lab.tasks.is_empty()) && !pull_tasks(&config, &mut state, &lab.slug).await? {
+        return Ok(());
     }
 
     // print task list
@@ -55,3 +39,62 @@ pub async fn list(refresh: bool) -> Result<()> {
 
     Ok(())
 }
+
+/// handle `luxctl refresh`: re-fetch the active lab's tasks, keeping workspace and runtime
+pub async fn refresh() -> Result<()> {
+    let config = Config::load()?;
+    if !config.has_auth_token() {
+        UI::error(
+            "not authenticated",
+            Some("run `luxctl auth --token $token`"),
+        );
+        return Ok(());
+    }
+
+    let mut state = LabState::load(config.expose_token())?;
+
+    let slug = if let Some(l) = state.get_active() {
+        l.slug.clone()
+    } else {
+        UI::error("no active lab", None);
+        UI::note("run `luxctl lab start --slug <SLUG>` first");
+        return Ok(());
+    };
+
+    if !pull_tasks(&config, &mut state, &slug).await? {
+        return Ok(());
+    }
+
+    if let Some(active) = state.get_active() {
+        UI::success(&format!(
+            "refreshed {} task(s) for {}",
+            active.tasks.len(),
+            active.name
+        ));
+        UI::kv(
+            "points",
+            &format!("{}/{}", active.earned_points(), active.total_points()),
+        );
+    }
+
+    Ok(())
+}
+
+/// fetch the lab's tasks into the cached state and save it; false if the fetch failed
+async fn pull_tasks(config: &Config, state: &mut LabState, slug: &str) -> Result<bool> {
+    let client = LighthouseAPIClient::try_from_config(config)?;
+
+    let fresh_lab = match client.lab_by_slug(slug).await {
+        Ok(l) => l,
+        Err(err) => {
+            UI::error(&lab_fetch_error(slug, &err), None);
+            return Ok(false);
+        }
+    };
+
+    if let Some(tasks) = &fresh_lab.tasks {
+        state.refresh_tasks(tasks);
+        state.save(config.expose_token())?;
+    }
+    Ok(true)
+}
@@ -1,8 +1,8 @@
 use color_eyre::eyre::Result;
 
-use crate::api::LighthouseAPIClient;
 use crate::api::Task;
-use crate::commands::run::run_task_validators;
+use crate::api::{lab_fetch_error, LighthouseAPIClient};
+use crate::commands::run::{run_task_validators, RunOptions, RunOutcome};
 use crate::config::Config;
 use crate::state::LabState;
 use crate::ui::RunUI;
@@ -52,11 +52,11 @@ pub fn filter_tasks_for_validation<'a>(
 }
 
 /// handle `luxctl validate [--all] [--detailed]`
-pub async fn validate_all(include_passed: bool, detailed: bool) -> Result<()> {
+pub async fn validate_all(include_passed: bool, detailed: bool) -> Result<RunOutcome> {
     let config = Config::load()?;
     if !config.has_auth_token() {
         oops!("not authenticated. Run: `luxctl auth --token $token`");
-        return Ok(());
+        return Ok(RunOutcome::SetupError);
     }
 
     let token = config.expose_token().to_string();
@@ -67,17 +67,17 @@ pub async fn validate_all(include_passed: bool, detailed: bool) -> Result<()> {
     } else {
         oops!("no active lab");
         say!("run `luxctl lab start --slug <SLUG>` first");
-        return Ok(());
+        return Ok(RunOutcome::SetupError);
     };
 
-    let client = LighthouseAPIClient::from_config(&config);
+    let client = LighthouseAPIClient::try_from_config(&config)?;
 
     // fetch fresh lab data
     let lab = match client.lab_by_slug(&active.slug).await {
         Ok(l) => l,
         Err(err) => {
-            oops!("failed to fetch lab: {}", err);
-            return Ok(());
+            oops!("{}", lab_fetch_error(&active.slug, &err));
+            return Ok(RunOutcome::SetupError);
         }
     };
 
@@ -85,7 +85,7 @@ pub async fn validate_all(include_passed: bool, detailed: bool) -> Result<()> {
         t
     } else {
         oops!("lab has no tasks");
-        return Ok(());
+        return Ok(RunOutcome::SetupError);
     };
 
     // update cache with fresh data
@@ -106,26 +106,31 @@ pub async fn validate_all(include_passed: bool, detailed: bool) -> Result<()> {
 
     if filtered.to_run.is_empty() {
         say!("no tasks to validate");
-        return Ok(());
+        return Ok(RunOutcome::Passed);
     }
 
     let total_tasks = filtered.to_run.len();
+    let options = RunOptions::default()
+        .with_detailed(detailed)
+        .with_allow_commands(config.settings().allow_commands);
 
     // run each task
+    let mut outcome = RunOutcome::Passed;
     for (i, task) in filtered.to_run.iter().enumerate() {
         let ui = RunUI::new(&task.slug, task.validators.len());
         println!();
         ui.task_separator(i + 1, total_tasks, &task.slug);
 
         // run validators and submit results (pass state for auto-refresh)
-        run_task_validators(
+        let task_outcome = run_task_validators(
             &client,
             &lab.slug,
             task,
-            detailed,
+            &options,
             Some((&mut state, &token)),
         )
         .await?;
+        outcome = outcome.worst(task_outcome);
     }
 
     // print summary
@@ -140,7 +145,7 @@ pub async fn validate_all(include_passed: bool, detailed: bool) -> Result<()> {
         say!("    skipped: {} (locked)", filtered.skipped_locked);
     }
 
-    Ok(())
+    Ok(outcome)
 }
 
 #[cfg(test)]
@@ -165,6 +170,7 @@ mod tests {
             points_earned: 0,
             hints: vec![],
             validators: vec![],
+            port: None,
             prologue: vec![],
             epilogue: vec![],
         }
@@ -0,0 +1,8 @@
+use crate::validators::known_validators;
+
+/// handle `luxctl validators`: print every validator name, one per line
+pub fn list() {
+    for name in known_validators() {
+        println!("{}", name);
+    }
+}
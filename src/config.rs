@@ -1,23 +1,111 @@
 use color_eyre::eyre::{self, Ok};
 use secrecy::{ExposeSecret, SecretString};
-use std::{collections::HashMap, fs, path::Path, path::PathBuf};
+use serde::Deserialize;
+use std::{collections::HashMap, env, fs, path::Path, path::PathBuf};
+
+use crate::api::{resolve_env, LighthouseAPIClientBaseURL};
 
 // we'll always use this path.
 static CFG_DIR: &str = ".luxctl";
 static CFG_FILE: &str = "cfg";
+static SETTINGS_FILE: &str = "config.toml";
+static LUX_HOME_ENV: &str = "LUX_HOME";
+
+/// base directory for config, state and caches: $LUX_HOME, else ~/.luxctl
+pub fn lux_home() -> Result<PathBuf, eyre::Error> {
+    resolve_lux_home(env::var(LUX_HOME_ENV).ok(), dirs::home_dir())
+        .ok_or_else(|| eyre::eyre!("could not determine home dir"))
+}
+
+pub(crate) fn resolve_lux_home(
+    env_value: Option<String>,
+    home: Option<PathBuf>,
+) -> Option<PathBuf> {
+    match env_value.filter(|dir| !dir.trim().is_empty()) {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => home.map(|home| home.join(CFG_DIR)),
+    }
+}
+
+/// optional user settings from ~/.luxctl/config.toml
+/// env vars (LUXCTL_ENV, LUXCTL_API_BASE_URL) take precedence over these
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct Settings {
+    pub env: Option<String>,
+    pub api_base_url: Option<String>,
+    pub runtime: Option<String>,
+    pub workspace: Option<String>,
+    /// let task validators run shell commands (command_exit, command_output)
+    #[serde(default)]
+    pub allow_commands: bool,
+}
+
+impl Settings {
+    fn settings_path() -> Result<PathBuf, eyre::Error> {
+        Ok(lux_home()?.join(SETTINGS_FILE))
+    }
+
+    /// load settings, returning defaults when the file doesn't exist
+    pub fn load() -> Result<Settings, eyre::Error> {
+        let path = Self::settings_path()?;
+        Self::load_from_path(&path)
+    }
+
+    fn load_from_path(path: &Path) -> Result<Settings, eyre::Error> {
+        if !path.exists() {
+            return Ok(Settings::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .map_err(|e| eyre::eyre!("failed to read {}: {}", SETTINGS_FILE, e))?;
+        let settings: Settings = toml::from_str(&content)
+            .map_err(|e| eyre::eyre!("invalid {}: {}", SETTINGS_FILE, e))?;
+
+        settings.validate(env::var("LUXCTL_ENV").ok())?;
+        Ok(settings)
+    }
+
+    /// check the file values against the same rules the api client enforces
+    fn validate(&self, env_override: Option<String>) -> Result<(), eyre::Error> {
+        if let Some(ref e) = self.env {
+            if !matches!(e.to_uppercase().as_str(), "DEV" | "RELEASE") {
+                return Err(eyre::eyre!(
+                    "invalid env '{}' in {}: expected dev or release",
+                    e,
+                    SETTINGS_FILE
+                ));
+            }
+        }
+
+        if let Some(ref url) = self.api_base_url {
+            let environment = resolve_env(env_override, self.env.as_deref());
+            LighthouseAPIClientBaseURL::from(url, environment).map_err(|e| {
+                eyre::eyre!("invalid api_base_url '{}' in {}: {}", url, SETTINGS_FILE, e)
+            })?;
+        }
+
+        Ok(())
+    }
+}
 
 #[derive(Debug)]
 pub struct Config {
     token: SecretString,
+    settings: Settings,
 }
 
 impl Config {
     pub fn new(token: &str) -> Config {
         Config {
             token: SecretString::from(token),
+            settings: Settings::default(),
         }
     }
 
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
     pub fn token(&self) -> &SecretString {
         &self.token
     }
@@ -34,9 +122,7 @@ impl Config {
 
 impl Config {
     fn config_path() -> Result<PathBuf, eyre::Error> {
-        let home = dirs::home_dir().ok_or_else(|| eyre::eyre!("could not determine home dir"))?;
-
-        Ok(home.join(CFG_DIR).join(CFG_FILE))
+        Ok(lux_home()?.join(CFG_FILE))
     }
 
     pub fn load() -> Result<Config, eyre::Error> {
@@ -62,7 +148,12 @@ impl Config {
             .copied()
             .ok_or_else(|| eyre::eyre!("token not found in config"))?;
 
-        Ok(Config::new(token))
+        let settings = Settings::load_from_path(&path.with_file_name(SETTINGS_FILE))?;
+
+        Ok(Config {
+            token: SecretString::from(token),
+            settings,
+        })
     }
 
     pub fn exists() -> Result<bool, eyre::Error> {
@@ -221,6 +312,24 @@ mod tests {
         assert!(path.exists());
     }
 
+    #[test]
+    fn test_resolve_lux_home() {
+        let home = Some(PathBuf::from("/home/ada"));
+        assert_eq!(
+            resolve_lux_home(None, home.clone()),
+            Some(PathBuf::from("/home/ada/.luxctl"))
+        );
+        assert_eq!(
+            resolve_lux_home(Some("/tmp/lux".to_string()), home.clone()),
+            Some(PathBuf::from("/tmp/lux"))
+        );
+        assert_eq!(
+            resolve_lux_home(Some("  ".to_string()), home),
+            Some(PathBuf::from("/home/ada/.luxctl"))
+        );
+        assert_eq!(resolve_lux_home(None, None), None);
+    }
+
     #[test]
     fn test_config_path_returns_expected_path() {
         let path = Config::config_path();
@@ -229,4 +338,85 @@ mod tests {
         let path = path.unwrap();
         assert!(path.ends_with(".luxctl/cfg"));
     }
+
+    #[test]
+    fn test_load_reads_settings_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_config_path(&temp_dir);
+
+        fs::write(&path, "token=abc\n").unwrap();
+        fs::write(
+            temp_dir.path().join("config.toml"),
+            "env = \"dev\"\napi_base_url = \"http://localhost:9000\"\nruntime = \"go\"\nworkspace = \"~/labs\"\nallow_commands = true\n",
+        )
+        .unwrap();
+
+        let loaded = Config::load_from_path(&path).unwrap();
+        let settings = loaded.settings();
+        assert_eq!(settings.env.as_deref(), Some("dev"));
+        assert_eq!(
+            settings.api_base_url.as_deref(),
+            Some("http://localhost:9000")
+        );
+        assert_eq!(settings.runtime.as_deref(), Some("go"));
+        assert_eq!(settings.workspace.as_deref(), Some("~/labs"));
+        assert!(settings.allow_commands);
+    }
+
+    #[test]
+    fn test_load_without_settings_file_uses_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_config_path(&temp_dir);
+        fs::write(&path, "token=abc\n").unwrap();
+
+        let loaded = Config::load_from_path(&path).unwrap();
+        assert!(loaded.settings().api_base_url.is_none());
+        assert!(loaded.settings().runtime.is_none());
+        assert!(!loaded.settings().allow_commands);
+    }
+
+    #[test]
+    fn test_settings_invalid_api_base_url() {
+        let settings = Settings {
+            env: Some("release".to_string()),
+            api_base_url: Some("http://evil.example.com".to_string()),
+            ..Default::default()
+        };
+        let err = settings.validate(None).unwrap_err();
+        assert!(err.to_string().contains("invalid api_base_url"));
+    }
+
+    #[test]
+    fn test_settings_url_validated_against_env_override() {
+        // file says dev, but LUXCTL_ENV=release wins, so a localhost url is rejected
+        let settings = Settings {
+            env: Some("dev".to_string()),
+            api_base_url: Some("http://localhost:8000".to_string()),
+            ..Default::default()
+        };
+        assert!(settings.validate(None).is_ok());
+        assert!(settings.validate(Some("release".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_settings_invalid_env() {
+        let settings = Settings {
+            env: Some("staging".to_string()),
+            ..Default::default()
+        };
+        assert!(settings.validate(None).is_err());
+    }
+
+    #[test]
+    fn test_settings_malformed_toml() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        fs::write(&path, "env = [unterminated").unwrap();
+
+        let loaded = Settings::load_from_path(&path);
+        assert!(loaded
+            .unwrap_err()
+            .to_string()
+            .contains("invalid config.toml"));
+    }
 }
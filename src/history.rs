@@ -0,0 +1,238 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
+use color_eyre::eyre;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::config::lux_home;
+
+static HISTORY_FILE: &str = "history.jsonl";
+
+/// points earned for passing a task, one line of the history log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub at: DateTime<Utc>,
+    pub lab_slug: String,
+    pub task_slug: String,
+    pub points: i32,
+}
+
+/// totals over the history log, for `luxctl stats`
+#[derive(Debug, Default, PartialEq)]
+pub struct Summary {
+    pub total_points: i32,
+    pub tasks_passed: usize,
+    pub active_days: usize,
+    /// consecutive days with a pass, ending today or yesterday
+    pub streak: usize,
+    /// newest first
+    pub recent: Vec<HistoryEntry>,
+}
+
+/// append-only JSONL log of earned points in `~/.luxctl/history.jsonl`
+pub struct History;
+
+impl History {
+    pub fn append(entry: &HistoryEntry) -> eyre::Result<()> {
+        Self::append_to_path(&Self::history_path()?, entry)
+    }
+
+    /// log a first pass; a failed write only warns, the points are already on the server
+    pub fn record_pass(lab_slug: &str, task_slug: &str, points: i32) {
+        let entry = HistoryEntry {
+            at: Utc::now(),
+            lab_slug: lab_slug.to_string(),
+            task_slug: task_slug.to_string(),
+            points,
+        };
+        if let Err(e) = Self::append(&entry) {
+            log::warn!("failed to record history: {}", e);
+        }
+    }
+
+    pub fn load() -> eyre::Result<Vec<HistoryEntry>> {
+        Self::load_from_path(&Self::history_path()?)
+    }
+
+    fn append_to_path(path: &Path, entry: &HistoryEntry) -> eyre::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let line = serde_json::to_string(entry)
+            .map_err(|e| eyre::eyre!("failed to serialize history entry: {}", e))?;
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+
+    /// read every entry; lines that don't parse (e.g. a torn write) are skipped
+    fn load_from_path(path: &Path) -> eyre::Result<Vec<HistoryEntry>> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content =
+            fs::read_to_string(path).map_err(|e| eyre::eyre!("failed to read history: {}", e))?;
+        Ok(content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str(line) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    log::warn!("skipping malformed history line: {}", e);
+                    None
+                }
+            })
+            .collect())
+    }
+
+    /// summarize `entries` as of `today`, keeping the `recent` newest entries;
+    /// days (and so streaks) turn over at local midnight
+    pub fn summarize(entries: &[HistoryEntry], today: NaiveDate, recent: usize) -> Summary {
+        Self::summarize_in(&Local, entries, today, recent)
+    }
+
+    fn summarize_in<Tz: TimeZone>(
+        tz: &Tz,
+        entries: &[HistoryEntry],
+        today: NaiveDate,
+        recent: usize,
+    ) -> Summary {
+        let mut days: Vec<NaiveDate> = entries
+            .iter()
+            .map(|e| e.at.with_timezone(tz).date_naive())
+            .collect();
+        days.sort_unstable();
+        days.dedup();
+
+        let mut newest: Vec<HistoryEntry> = entries.to_vec();
+        newest.sort_by_key(|e| std::cmp::Reverse(e.at));
+        newest.truncate(recent);
+
+        Summary {
+            total_points: entries.iter().map(|e| e.points).sum(),
+            tasks_passed: entries.len(),
+            active_days: days.len(),
+            streak: streak(&days, today),
+            recent: newest,
+        }
+    }
+
+    fn history_path() -> eyre::Result<PathBuf> {
+        Ok(lux_home()?.join(HISTORY_FILE))
+    }
+}
+
+/// length of the run of consecutive `days` (sorted, unique) ending today or yesterday
+fn streak(days: &[NaiveDate], today: NaiveDate) -> usize {
+    let Some(&last) = days.last() else {
+        return 0;
+    };
+    if last != today && last != today - Duration::days(1) {
+        return 0;
+    }
+
+    let mut count = 1;
+    for pair in days.windows(2).rev() {
+        if pair[1] - pair[0] != Duration::days(1) {
+            break;
+        }
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry(day: u32, task: &str, points: i32) -> HistoryEntry {
+        HistoryEntry {
+            at: Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap(),
+            lab_slug: "tcp-echo".to_string(),
+            task_slug: task.to_string(),
+            points,
+        }
+    }
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    #[test]
+    fn test_append_roundtrips_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+
+        History::append_to_path(&path, &entry(1, "bind-port", 50)).unwrap();
+        History::append_to_path(&path, &entry(2, "echo", 30)).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count(), 2);
+
+        let loaded = History::load_from_path(&path).unwrap();
+        assert_eq!(
+            loaded,
+            vec![entry(1, "bind-port", 50), entry(2, "echo", 30)]
+        );
+    }
+
+    #[test]
+    fn test_load_skips_malformed_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        History::append_to_path(&path, &entry(1, "bind-port", 50)).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "{{\"at\": ").unwrap();
+
+        assert_eq!(History::load_from_path(&path).unwrap().len(), 1);
+        assert!(History::load_from_path(&dir.path().join("missing.jsonl"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_summarize_small_log() {
+        let entries = vec![
+            entry(1, "bind-port", 50),
+            entry(3, "echo", 30),
+            entry(4, "concurrency", 20),
+            entry(4, "graceful-shutdown", 10),
+        ];
+
+        let summary = History::summarize(&entries, day(5), 2);
+        assert_eq!(summary.total_points, 110);
+        assert_eq!(summary.tasks_passed, 4);
+        assert_eq!(summary.active_days, 3);
+        assert_eq!(summary.streak, 2);
+        assert_eq!(summary.recent.len(), 2);
+        assert_eq!(summary.recent[0].at.date_naive(), day(4));
+
+        // a missed day breaks the streak
+        assert_eq!(History::summarize(&entries, day(6), 2).streak, 0);
+        assert_eq!(History::summarize(&[], day(6), 2), Summary::default());
+    }
+
+    #[test]
+    fn test_summarize_buckets_days_in_local_time() {
+        let at = |d, h| HistoryEntry {
+            at: Utc.with_ymd_and_hms(2026, 3, d, h, 0, 0).unwrap(),
+            ..entry(d, "echo", 10)
+        };
+        // 23:00 UTC on the 1st and 01:00 UTC on the 3rd are the 2nd and 3rd at UTC+2
+        let entries = vec![at(1, 23), at(3, 1)];
+        let utc_plus_2 = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+
+        let summary = History::summarize_in(&utc_plus_2, &entries, day(3), 5);
+        assert_eq!(summary.active_days, 2);
+        assert_eq!(summary.streak, 2);
+
+        // in UTC the same passes are two days apart
+        assert_eq!(History::summarize_in(&Utc, &entries, day(3), 5).streak, 1);
+    }
+}
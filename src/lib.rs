@@ -2,7 +2,9 @@ pub mod api;
 pub mod auth;
 pub mod commands;
 pub mod config;
+pub mod history;
 pub mod message;
+pub mod outbox;
 pub mod runtime;
 pub mod shell;
 pub mod state;
@@ -10,4 +12,6 @@ pub mod tasks;
 pub mod ui;
 pub mod validators;
 
+pub use validators::{run_all, run_validator};
+
 pub const VERSION: &str = "0.6.3";
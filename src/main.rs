@@ -1,15 +1,59 @@
-use clap::{Parser, Subcommand};
+use clap::{ArgAction, CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
 use color_eyre::eyre::Result;
+use std::time::Duration;
 
+use luxctl::commands::run::{RunOptions, RunOutcome, SubmitPolicy};
 use luxctl::{
-    api::LighthouseAPIClient, auth::TokenAuthenticator, commands, config::Config, greet,
-    message::Message, oops, VERSION,
+    api::{lab_fetch_error, LighthouseAPIClient},
+    auth::TokenAuthenticator,
+    commands,
+    config::Config,
+    greet,
+    message::Message,
+    oops,
+    state::LabState,
+    VERSION,
 };
 
+const RUN_EXAMPLES: &str = "\
+Examples:
+  luxctl run 1
+  luxctl run bind-port --detailed
+  luxctl run 2 --lab tcp-echo-server --watch";
+
+const LAB_LIST_EXAMPLES: &str = "\
+Examples:
+  luxctl lab list
+  luxctl lab list --wide
+  luxctl lab list --quiet";
+
+const TASK_SHOW_EXAMPLES: &str = "\
+Examples:
+  luxctl task show 1
+  luxctl task show bind-port --detailed";
+
 #[derive(Parser)]
 #[command(name = "luxctl")]
 #[command(version = VERSION)]
+#[command(after_help = "Run `luxctl examples` to see common workflows.")]
 struct Cli {
+    /// Disable colored output (also honors NO_COLOR)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Print only results and errors, for scripts and CI
+    #[arg(long, global = true)]
+    quiet: bool,
+
+    /// Answer yes to every confirmation prompt
+    #[arg(short = 'y', long, global = true)]
+    yes: bool,
+
+    /// Log more: -v info, -vv debug, -vvv trace (RUST_LOG still wins)
+    #[arg(short = 'v', long = "verbose", action = ArgAction::Count, global = true)]
+    verbose: u8,
+
     #[command(subcommand)]
     commands: Commands,
 }
@@ -26,30 +70,69 @@ enum Commands {
     Whoami,
 
     /// Labs are a series of challenges that build on each other, preparing you for real-world problems
+    #[command(alias = "project")]
     Lab {
         #[command(subcommand)]
         action: LabAction,
     },
 
-    /// Tasks are individual challenges within a project - tackle them in any order
+    /// Tasks are individual challenges within a lab - tackle them in any order
     Task {
         #[command(subcommand)]
         action: TaskAction,
     },
 
     /// Test your solution to see if it passes
+    #[command(after_help = RUN_EXAMPLES)]
     Run {
+        /// Task number or slug
+        #[arg(required_unless_present = "task_flag")]
+        task: Option<String>,
+
+        /// Task number or slug (same as the positional form)
+        #[arg(short = 't', long = "task", conflicts_with = "task")]
+        task_flag: Option<String>,
+
+        /// Lab slug (defaults to the active lab)
         #[arg(short = 'l', long)]
         lab: Option<String>,
 
-        #[arg(short = 't', long)]
-        task: String,
-
         #[arg(short = 'd', long)]
         detailed: bool,
+
+        /// Re-run validators whenever workspace files change
+        #[arg(short = 'w', long)]
+        watch: bool,
+
+        /// Run validators without reporting the attempt
+        #[arg(long)]
+        no_submit: bool,
+
+        /// Give up on validators still running after this many seconds
+        #[arg(long, value_name = "SECS")]
+        timeout: Option<u64>,
+
+        /// Stop at the first failing validator
+        #[arg(long)]
+        fail_fast: bool,
+
+        /// Run only validators whose name matches (substring, or glob with * and ?)
+        #[arg(long, value_name = "PATTERN")]
+        only: Vec<String>,
+
+        /// Leave out validators whose name matches (substring, or glob with * and ?)
+        #[arg(long, value_name = "PATTERN")]
+        skip: Vec<String>,
+
+        /// Let validators run shell commands in your workspace (skipped otherwise)
+        #[arg(long)]
+        allow_commands: bool,
     },
 
-    /// Run all the tasks of a project at once
+    /// Re-fetch the active lab's tasks, keeping your workspace and runtime
+    Refresh,
+
+    /// Run all the tasks of a lab at once
     Validate {
         #[arg(short = 'd', long)]
         detailed: bool,
@@ -66,12 +149,47 @@ enum Commands {
 
     /// Check your environment and diagnose issues
     Doctor,
+
+    /// See points earned over time and your daily streak
+    Stats,
+
+    /// Show or retry results that failed to submit
+    Submit {
+        /// Retry every pending submission
+        #[arg(long)]
+        flush: bool,
+    },
+
+    /// List every validator name usable in task definitions
+    Validators,
+
+    /// Show common luxctl workflows
+    Examples,
+
+    /// Print a shell completion script, e.g. `luxctl completions bash > ~/.bash_completion.d/luxctl`
+    Completions {
+        #[arg(required_unless_present = "tasks")]
+        shell: Option<Shell>,
+
+        /// Print the active lab's task numbers and slugs, for the generated scripts to complete tasks
+        #[arg(long, hide = true)]
+        tasks: bool,
+    },
 }
 
 #[derive(Subcommand)]
 enum LabAction {
     /// See all available labs you can work on
-    List,
+    #[command(after_help = LAB_LIST_EXAMPLES)]
+    List {
+        /// Include lab stats and urls
+        #[arg(long)]
+        wide: bool,
+
+        /// Print only lab slugs, one per line (also implied by the global --quiet)
+        #[arg(short = 'q', conflicts_with = "wide")]
+        slugs: bool,
+    },
     /// Get details about a lab before starting
     Show {
         #[arg(short = 's', long)]
@@ -82,9 +200,9 @@ enum LabAction {
         #[arg(short = 's', long)]
         slug: String,
 
-        /// Workspace directory (defaults to current directory)
-        #[arg(short = 'w', long, default_value = ".")]
-        workspace: String,
+        /// Workspace directory (defaults to config.toml workspace, then current directory)
+        #[arg(short = 'w', long)]
+        workspace: Option<String>,
 
         /// Runtime environment (go, rust, c)
         #[arg(short = 'r', long)]
@@ -108,16 +226,21 @@ enum LabAction {
 
 #[derive(Subcommand)]
 enum TaskAction {
-    /// See all tasks in your current project
+    /// See all tasks in your current lab
     List {
         #[arg(short = 'r', long)]
         refresh: bool,
     },
     /// Read the task description and requirements
+    #[command(after_help = TASK_SHOW_EXAMPLES)]
     Show {
         /// Task number or slug
-        #[arg(short = 't', long)]
-        task: String,
+        #[arg(required_unless_present = "task_flag")]
+        task: Option<String>,
+
+        /// Task number or slug (same as the positional form)
+        #[arg(short = 't', long = "task", conflicts_with = "task")]
+        task_flag: Option<String>,
 
         /// Show full description
         #[arg(short = 'd', long)]
@@ -149,9 +272,14 @@ impl Commands {
 #[tokio::main]
 async fn main() -> Result<()> {
     color_eyre::install()?;
-    env_logger::init();
-
     let cli = Cli::parse();
+    env_logger::Builder::new()
+        .filter_level(log_level(cli.verbose))
+        .parse_default_env()
+        .init();
+    luxctl::ui::init_color(cli.no_color);
+    luxctl::ui::set_quiet(cli.quiet);
+    luxctl::ui::set_assume_yes(cli.yes);
 
     match cli.commands {
         Commands::Auth { token } => {
@@ -178,14 +306,14 @@ async fn main() -> Result<()> {
                 }
             };
 
-            let client = LighthouseAPIClient::from_config(&config);
+            let client = LighthouseAPIClient::try_from_config(&config)?;
             match client.me().await {
                 Ok(user) => {
                     println!("{}", user.name);
                     println!("{}", user.email);
                     if let Some(stats) = user.stats {
                         println!();
-                        println!("projects: {}", stats.projects_attempted);
+                        println!("labs: {}", stats.projects_attempted);
                         println!("tasks completed: {}", stats.tasks_completed);
                         println!("total xp: {}", stats.total_xp);
                     }
@@ -197,17 +325,24 @@ async fn main() -> Result<()> {
         }
 
         Commands::Lab { action } => match action {
-            LabAction::List => {
+            LabAction::List { wide, slugs } => {
+                let quiet = slugs || cli.quiet;
                 let config = Config::load()?;
                 if !config.has_auth_token() {
                     oops!("not authenticated. Run: `{}`", Commands::AUTH_USAGE);
                     return Ok(());
                 }
 
-                let client = LighthouseAPIClient::from_config(&config);
+                let client = LighthouseAPIClient::try_from_config(&config)?;
                 match client.labs(None, None).await {
+                    Ok(response) if quiet => {
+                        Message::print_labs_quiet(&response);
+                    }
                     Ok(response) => {
-                        Message::print_labs(&response);
+                        // progress comes from the cached active lab, if any
+                        let state = LabState::load(config.expose_token()).ok();
+                        let active = state.as_ref().and_then(|s| s.get_active());
+                        Message::print_labs(&response, active, wide);
                     }
                     Err(err) => {
                         oops!("failed to fetch labs: {}", err);
@@ -221,13 +356,13 @@ async fn main() -> Result<()> {
                     return Ok(());
                 }
 
-                let client = LighthouseAPIClient::from_config(&config);
+                let client = LighthouseAPIClient::try_from_config(&config)?;
                 match client.lab_by_slug(&slug).await {
                     Ok(lab) => {
                         Message::print_lab_detail(&lab);
                     }
                     Err(err) => {
-                        oops!("failed to fetch lab: {}", err);
+                        oops!("{}", lab_fetch_error(&slug, &err));
                     }
                 }
             }
@@ -236,7 +371,7 @@ async fn main() -> Result<()> {
                 workspace,
                 runtime,
             } => {
-                commands::lab::start(&slug, &workspace, runtime.as_deref()).await?;
+                commands::lab::start(&slug, workspace.as_deref(), runtime.as_deref()).await?;
             }
             LabAction::Status => {
                 commands::lab::status()?;
@@ -261,21 +396,54 @@ async fn main() -> Result<()> {
             TaskAction::List { refresh } => {
                 commands::tasks::list(refresh).await?;
             }
-            TaskAction::Show { task, detailed } => {
+            TaskAction::Show {
+                task,
+                task_flag,
+                detailed,
+            } => {
+                let task = task.or(task_flag).unwrap_or_default();
                 commands::task::show(&task, detailed).await?;
             }
         },
 
         Commands::Run {
-            lab,
             task,
+            task_flag,
+            lab,
             detailed,
+            watch,
+            no_submit,
+            timeout,
+            fail_fast,
+            only,
+            skip,
+            allow_commands,
         } => {
-            commands::run::run(&task, lab.as_deref(), detailed).await?;
+            let task = task.or(task_flag).unwrap_or_default();
+            let submit = if no_submit {
+                SubmitPolicy::Never
+            } else {
+                SubmitPolicy::ConfirmReattempt
+            };
+            let options = RunOptions::default()
+                .with_detailed(detailed)
+                .with_submit(submit)
+                .with_timeout(timeout.map(Duration::from_secs))
+                .with_fail_fast(fail_fast)
+                .with_only(only)
+                .with_skip(skip)
+                .with_allow_commands(allow_commands);
+            let outcome = commands::run::run(&task, lab.as_deref(), watch, &options).await;
+            exit_with(outcome);
+        }
+
+        Commands::Refresh => {
+            commands::tasks::refresh().await?;
         }
 
         Commands::Validate { detailed, all } => {
-            commands::validate::validate_all(all, detailed).await?;
+            let outcome = commands::validate::validate_all(all, detailed).await;
+            exit_with(outcome);
         }
 
         Commands::Hint { action } => match action {
@@ -290,7 +458,255 @@ async fn main() -> Result<()> {
         Commands::Doctor => {
             commands::doctor::run().await?;
         }
+
+        Commands::Stats => {
+            commands::stats::show()?;
+        }
+
+        Commands::Submit { flush } => {
+            commands::submit::submit(flush).await?;
+        }
+
+        Commands::Validators => {
+            commands::validators::list();
+        }
+
+        Commands::Examples => {
+            commands::examples::print();
+        }
+
+        Commands::Completions { shell, tasks } => {
+            if tasks {
+                commands::completions::print_tasks()?;
+            } else if let Some(shell) = shell {
+                commands::completions::write_script(
+                    shell,
+                    &mut Cli::command(),
+                    &mut std::io::stdout(),
+                )?;
+            }
+        }
     }
 
     Ok(())
 }
+
+/// log filter for `-v` repeated `verbosity` times; warnings show by default
+fn log_level(verbosity: u8) -> log::LevelFilter {
+    match verbosity {
+        0 => log::LevelFilter::Warn,
+        1 => log::LevelFilter::Info,
+        2 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    }
+}
+
+/// exit 0 when everything passed, 1 when validators failed, 2 on setup errors
+fn exit_with(outcome: Result<RunOutcome>) {
+    let outcome = outcome.unwrap_or_else(|err| {
+        oops!("{}", err);
+        RunOutcome::SetupError
+    });
+    if outcome != RunOutcome::Passed {
+        std::process::exit(outcome.exit_code());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bash_completions_are_generated() {
+        let mut out = Vec::new();
+        commands::completions::write_script(Shell::Bash, &mut Cli::command(), &mut out).unwrap();
+        let script = String::from_utf8(out).unwrap();
+        assert!(!script.is_empty());
+        assert!(script.contains("completions"));
+        assert!(script.contains("validate"));
+    }
+
+    /// the bash script's candidates for `words`, completing the last one,
+    /// with a stub `luxctl` printing the tasks
+    fn bash_candidates(words: &[&str]) -> String {
+        let mut out = Vec::new();
+        commands::completions::write_script(Shell::Bash, &mut Cli::command(), &mut out).unwrap();
+        let script = String::from_utf8(out).unwrap();
+        let words: Vec<String> = words.iter().map(|w| format!("'{}'", w)).collect();
+        let driver = format!(
+            "{}\nluxctl() {{ printf '1\\nbind-port\\n2\\necho\\n'; }}\n\
+             COMP_WORDS=({}); COMP_CWORD={}; COMP_LINE=\"${{COMP_WORDS[*]}}\"; COMP_POINT=${{#COMP_LINE}}\n\
+             _luxctl_tasks luxctl \"${{COMP_WORDS[COMP_CWORD]}}\" \"${{COMP_WORDS[COMP_CWORD-1]}}\"\n\
+             echo \"${{COMPREPLY[*]}}\"",
+            script,
+            words.join(" "),
+            words.len() - 1
+        );
+        let output = std::process::Command::new("bash")
+            .arg("-c")
+            .arg(driver)
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    }
+
+    #[test]
+    fn test_bash_completes_task_arguments() {
+        assert_eq!(
+            bash_candidates(&["luxctl", "run", ""]),
+            "1 bind-port 2 echo"
+        );
+        assert_eq!(bash_candidates(&["luxctl", "run", "b"]), "bind-port");
+        assert_eq!(bash_candidates(&["luxctl", "task", "show", "e"]), "echo");
+        assert_eq!(bash_candidates(&["luxctl", "run", "--task", "2"]), "2");
+        // other arguments still go through the generated completer
+        assert!(!bash_candidates(&["luxctl", "run", "--"]).contains("bind-port"));
+    }
+
+    #[test]
+    fn test_zsh_and_fish_complete_task_arguments() {
+        let script = |shell| {
+            let mut out = Vec::new();
+            commands::completions::write_script(shell, &mut Cli::command(), &mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let zsh = script(Shell::Zsh);
+        assert!(zsh.starts_with("#compdef luxctl\n"));
+        assert!(zsh.contains("luxctl completions --tasks"));
+        // the positional, -t and --task args of run and task show
+        assert_eq!(zsh.matches(":_luxctl_tasks'").count(), 6);
+
+        let fish = script(Shell::Fish);
+        assert!(fish.contains(
+            "__fish_luxctl_using_subcommand run\" -f -a \"(luxctl completions --tasks)\""
+        ));
+    }
+
+    fn help_for(path: &[&str]) -> String {
+        let mut cmd = Cli::command();
+        for name in path {
+            cmd = cmd.find_subcommand(name).unwrap().clone();
+        }
+        cmd.render_long_help().to_string()
+    }
+
+    #[test]
+    fn test_help_contains_examples() {
+        assert!(help_for(&[]).contains("luxctl examples"));
+        assert!(help_for(&["run"]).contains("luxctl run bind-port --detailed"));
+        assert!(help_for(&["lab", "list"]).contains("luxctl lab list --wide"));
+        assert!(help_for(&["task", "show"]).contains("luxctl task show bind-port --detailed"));
+    }
+
+    #[test]
+    fn test_task_show_accepts_positional_and_flag() {
+        for args in [
+            &["luxctl", "task", "show", "2"][..],
+            &["luxctl", "task", "show", "--task", "2"][..],
+        ] {
+            let cli = Cli::try_parse_from(args).unwrap();
+            assert!(matches!(
+                cli.commands,
+                Commands::Task {
+                    action: TaskAction::Show { task, task_flag, .. }
+                } if task.as_deref().or(task_flag.as_deref()) == Some("2")
+            ));
+        }
+
+        assert!(Cli::try_parse_from(["luxctl", "task", "show"]).is_err());
+        assert!(Cli::try_parse_from(["luxctl", "task", "show", "1", "--task", "2"]).is_err());
+    }
+
+    #[test]
+    fn test_run_accepts_positional_task() {
+        let cli = Cli::try_parse_from(["luxctl", "run", "3", "--lab", "tcp-echo", "-d"]).unwrap();
+        assert!(matches!(
+            cli.commands,
+            Commands::Run { task: Some(ref t), task_flag: None, lab: Some(ref l), detailed: true, watch: false, no_submit: false, timeout: None, fail_fast: false, .. }
+                if t == "3" && l == "tcp-echo"
+        ));
+
+        let cli = Cli::try_parse_from(["luxctl", "run", "bind-port"]).unwrap();
+        assert!(matches!(
+            cli.commands,
+            Commands::Run { task: Some(ref t), lab: None, .. } if t == "bind-port"
+        ));
+
+        let cli = Cli::try_parse_from(["luxctl", "run", "-t", "3"]).unwrap();
+        assert!(matches!(
+            cli.commands,
+            Commands::Run { task: None, task_flag: Some(ref t), .. } if t == "3"
+        ));
+
+        assert!(Cli::try_parse_from(["luxctl", "run"]).is_err());
+        assert!(Cli::try_parse_from(["luxctl", "run", "1", "--task", "2"]).is_err());
+    }
+
+    fn all_about(cmd: &clap::Command) -> Vec<String> {
+        let mut texts: Vec<String> = cmd.get_about().map(|a| a.to_string()).into_iter().collect();
+        for sub in cmd.get_subcommands() {
+            texts.extend(all_about(sub));
+        }
+        texts
+    }
+
+    #[test]
+    fn test_help_speaks_of_labs_not_projects() {
+        let texts = all_about(&Cli::command());
+        assert!(!texts.is_empty());
+        for text in texts {
+            let lower = text.to_lowercase();
+            let mut words = lower.split(|c: char| !c.is_alphanumeric());
+            assert!(
+                !words.any(|w| w == "project" || w == "projects"),
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_project_is_an_alias_for_lab() {
+        let cli =
+            Cli::try_parse_from(["luxctl", "project", "start", "--slug", "tcp-echo"]).unwrap();
+        assert!(matches!(
+            cli.commands,
+            Commands::Lab { action: LabAction::Start { ref slug, .. } } if slug == "tcp-echo"
+        ));
+    }
+
+    #[test]
+    fn test_quiet_is_global_and_lab_list_keeps_its_short_flag() {
+        Cli::command().debug_assert();
+
+        let cli = Cli::try_parse_from(["luxctl", "run", "1", "--quiet"]).unwrap();
+        assert!(cli.quiet);
+
+        let cli = Cli::try_parse_from(["luxctl", "lab", "list", "-q"]).unwrap();
+        assert!(!cli.quiet);
+        assert!(matches!(
+            cli.commands,
+            Commands::Lab {
+                action: LabAction::List { slugs: true, .. }
+            }
+        ));
+
+        let cli = Cli::try_parse_from(["luxctl", "lab", "list", "--quiet"]).unwrap();
+        assert!(cli.quiet);
+    }
+
+    #[test]
+    fn test_verbosity_maps_to_log_level() {
+        assert_eq!(log_level(0), log::LevelFilter::Warn);
+        assert_eq!(log_level(1), log::LevelFilter::Info);
+        assert_eq!(log_level(2), log::LevelFilter::Debug);
+        assert_eq!(log_level(3), log::LevelFilter::Trace);
+        assert_eq!(log_level(9), log::LevelFilter::Trace);
+
+        let cli = Cli::try_parse_from(["luxctl", "-vv", "doctor"]).unwrap();
+        assert_eq!(cli.verbose, 2);
+        let cli = Cli::try_parse_from(["luxctl", "doctor", "--verbose"]).unwrap();
+        assert_eq!(cli.verbose, 1);
+    }
+}
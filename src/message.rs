@@ -1,14 +1,16 @@
 use colored::Colorize;
 use termimad::MadSkin;
 
-use crate::api::{Lab, PaginatedResponse, Task, TaskStatus};
+use crate::api::{Lab, LabStats, PaginatedResponse, Task, TaskStatus};
 use crate::state::ActiveLab;
 use crate::tasks::{TestCase, TestResults};
+use crate::ui::is_quiet;
 
 // status symbols for consistent output (matching ui.rs)
 const SYM_PASS: &str = "✓";
 const SYM_FAIL: &str = "✗";
 const SYM_PENDING: &str = "○";
+const SYM_SKIP: &str = "○";
 
 pub struct Message;
 
@@ -23,46 +25,116 @@ impl Message {
     }
 
     pub fn say(msg: &str) {
-        println!("{}", msg);
+        if !is_quiet() {
+            println!("{}", msg);
+        }
     }
 
     pub fn cheer(msg: &str) {
-        println!("{}", msg.green());
+        if !is_quiet() {
+            println!("{}", msg.green());
+        }
     }
 
     pub fn complain(msg: &str) {
-        eprintln!("{}", msg.yellow());
+        if !is_quiet() {
+            eprintln!("{}", msg.yellow());
+        }
     }
 
     pub fn oops(msg: &str) {
         eprintln!("{}", msg.red());
     }
 
-    pub fn print_labs(response: &PaginatedResponse<Lab>) {
+    /// print labs as an aligned table; `active` merges progress from cached state
+    pub fn print_labs(response: &PaginatedResponse<Lab>, active: Option<&ActiveLab>, wide: bool) {
         Self::say(&format!(
             "available labs ({} total):\n",
             response.meta.total
         ));
 
-        for lab in &response.data {
-            Self::print_lab(lab);
+        let rows = Self::format_labs_table(&response.data, active, wide);
+        for (i, row) in rows.iter().enumerate() {
+            if i == 0 {
+                println!("  {}", row.dimmed());
+            } else {
+                println!("  {}", row);
+            }
         }
     }
 
-    fn print_lab(lab: &Lab) {
-        println!("  {} {}", "#".dimmed(), lab.name.bold());
-        if let Some(desc) = &lab.short_description {
-            println!("    {}", desc);
+    /// slugs only, one per line (for scripting)
+    pub fn print_labs_quiet(response: &PaginatedResponse<Lab>) {
+        let out = Self::format_labs_quiet(&response.data);
+        if !out.is_empty() {
+            println!("{}", out);
         }
-        let tasks_count = lab.tasks_count.unwrap_or(0);
-        println!(
-            "    {} {}  {} {}",
-            "slug:".dimmed(),
-            lab.slug.dimmed(),
-            "tasks:".dimmed(),
-            tasks_count.to_string().dimmed()
-        );
-        println!("    {} {}\n", "url:".dimmed(), lab.url().dimmed());
+    }
+
+    pub fn format_labs_quiet(labs: &[Lab]) -> String {
+        labs.iter()
+            .map(|l| l.slug.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// header row followed by one row per lab, columns padded to the widest cell
+    pub fn format_labs_table(labs: &[Lab], active: Option<&ActiveLab>, wide: bool) -> Vec<String> {
+        let mut header = vec!["SLUG", "NAME", "TASKS", "PROGRESS"];
+        if wide {
+            header.extend(["ATTEMPTED", "SUCCEEDED", "FAILED", "URL"]);
+        }
+
+        let mut rows: Vec<Vec<String>> = vec![header.iter().map(|h| h.to_string()).collect()];
+        for lab in labs {
+            let progress = match active {
+                Some(a) if a.slug == lab.slug => {
+                    format!("{}/{}", a.completed_count(), a.tasks.len())
+                }
+                _ => "-".to_string(),
+            };
+
+            let mut row = vec![
+                lab.slug.clone(),
+                lab.name.clone(),
+                lab.tasks_count.unwrap_or(0).to_string(),
+                progress,
+            ];
+            if wide {
+                let stat = |f: fn(&LabStats) -> i32| {
+                    lab.stats
+                        .as_ref()
+                        .map(|s| f(s).to_string())
+                        .unwrap_or_else(|| "-".to_string())
+                };
+                row.push(stat(|s| s.attempted_count));
+                row.push(stat(|s| s.succeed_count));
+                row.push(stat(|s| s.failed_count));
+                row.push(lab.url());
+            }
+            rows.push(row);
+        }
+
+        let widths: Vec<usize> = (0..header.len())
+            .map(|col| {
+                rows.iter()
+                    .map(|r| r[col].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        rows.iter()
+            .map(|r| {
+                r.iter()
+                    .zip(&widths)
+                    .map(|(cell, w)| format!("{:<width$}", cell, width = w))
+                    .collect::<Vec<_>>()
+                    .join("  ")
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
     }
 
     pub fn print_lab_detail(lab: &Lab) {
@@ -152,6 +224,14 @@ impl Message {
     pub fn print_test_case(test: &TestCase, index: usize) {
         if test.passed() {
             println!("{} #{} {}", SYM_PASS.green(), index + 1, test.name);
+        } else if test.is_skipped() {
+            println!(
+                "{} #{} {}  {}",
+                SYM_SKIP.yellow(),
+                index + 1,
+                test.name.yellow(),
+                test.message().dimmed()
+            );
         } else {
             println!("{} #{} {}", SYM_FAIL.red(), index + 1, test.name.red());
 
@@ -294,3 +374,64 @@ macro_rules! oops {
         $crate::message::Message::oops(&format!($fmt, $($arg)*))
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_lab(slug: &str, name: &str, tasks_count: i32) -> Lab {
+        Lab {
+            id: 1,
+            uuid: String::new(),
+            slug: slug.to_string(),
+            name: name.to_string(),
+            short_description: None,
+            is_published: None,
+            is_featured: None,
+            show_tasks: None,
+            stats: None,
+            published_at: None,
+            tasks_count: Some(tasks_count),
+            runner_image: None,
+            tasks: None,
+        }
+    }
+
+    #[test]
+    fn test_format_labs_quiet_prints_slugs() {
+        let labs = vec![
+            make_lab("build-your-own-http-server", "HTTP Server", 8),
+            make_lab("job-queue", "Job Queue", 5),
+        ];
+        assert_eq!(
+            Message::format_labs_quiet(&labs),
+            "build-your-own-http-server\njob-queue"
+        );
+    }
+
+    #[test]
+    fn test_format_labs_table_aligns_columns() {
+        let labs = vec![
+            make_lab("http-server", "HTTP Server", 8),
+            make_lab("queue", "Job Queue", 5),
+        ];
+        let rows = Message::format_labs_table(&labs, None, false);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], "SLUG         NAME         TASKS  PROGRESS");
+        assert_eq!(rows[1], "http-server  HTTP Server  8      -");
+        assert_eq!(rows[2], "queue        Job Queue    5      -");
+    }
+
+    #[test]
+    fn test_format_labs_table_wide_includes_stats() {
+        let mut lab = make_lab("queue", "Job Queue", 5);
+        lab.stats = Some(LabStats {
+            attempted_count: 12,
+            succeed_count: 7,
+            failed_count: 5,
+        });
+        let rows = Message::format_labs_table(&[lab], None, true);
+        assert!(rows[0].contains("ATTEMPTED  SUCCEEDED  FAILED"));
+        assert!(rows[1].contains("12         7          5"));
+    }
+}
@@ -0,0 +1,239 @@
+use chrono::{DateTime, Utc};
+use color_eyre::eyre;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::api::SubmitAttemptRequest;
+use crate::config::lux_home;
+
+static OUTBOX_FILE: &str = "outbox.json";
+
+/// an attempt that could not be reported yet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingSubmission {
+    pub request: SubmitAttemptRequest,
+    pub queued_at: DateTime<Utc>,
+    /// for the history log once sent; empty for entries queued by older versions
+    #[serde(default)]
+    pub task_slug: String,
+}
+
+impl PendingSubmission {
+    /// the task's slug, or its id when queued without one
+    pub fn task_label(&self) -> String {
+        if self.task_slug.is_empty() {
+            self.request.task_id.to_string()
+        } else {
+            self.task_slug.clone()
+        }
+    }
+}
+
+/// result of retrying queued submissions
+#[derive(Debug, Default)]
+pub struct FlushReport {
+    pub submitted: usize,
+    pub errors: Vec<String>,
+}
+
+/// submissions that failed to reach the API, kept on disk until flushed
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Outbox {
+    pub pending: Vec<PendingSubmission>,
+}
+
+impl Outbox {
+    pub fn load() -> eyre::Result<Self> {
+        Self::load_from_path(&Self::outbox_path()?)
+    }
+
+    pub fn save(&self) -> eyre::Result<()> {
+        self.save_to_path(&Self::outbox_path()?)
+    }
+
+    fn load_from_path(path: &Path) -> eyre::Result<Self> {
+        if !path.exists() {
+            return Ok(Outbox::default());
+        }
+
+        let content =
+            fs::read_to_string(path).map_err(|e| eyre::eyre!("failed to read outbox: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| eyre::eyre!("failed to parse outbox: {}", e))
+    }
+
+    fn save_to_path(&self, path: &Path) -> eyre::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| eyre::eyre!("failed to serialize outbox: {}", e))?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// queue a request for a later `luxctl submit --flush`
+    pub fn enqueue(&mut self, request: SubmitAttemptRequest, task_slug: &str) {
+        self.pending.push(PendingSubmission {
+            request,
+            queued_at: Utc::now(),
+            task_slug: task_slug.to_string(),
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// retry every pending submission in order; failed ones stay queued
+    pub async fn flush<F, Fut>(&mut self, mut submit: F) -> FlushReport
+    where
+        F: FnMut(PendingSubmission) -> Fut,
+        Fut: Future<Output = eyre::Result<()>>,
+    {
+        let mut report = FlushReport::default();
+        let mut remaining = Vec::new();
+
+        for entry in std::mem::take(&mut self.pending) {
+            match submit(entry.clone()).await {
+                Ok(()) => report.submitted += 1,
+                Err(err) => {
+                    report.errors.push(format!(
+                        "task {} ({}): {}",
+                        entry.request.task_id, entry.request.lab_slug, err
+                    ));
+                    remaining.push(entry);
+                }
+            }
+        }
+
+        self.pending = remaining;
+        report
+    }
+
+    fn outbox_path() -> eyre::Result<PathBuf> {
+        Ok(lux_home()?.join(OUTBOX_FILE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::TaskOutcome;
+
+    fn make_request(task_id: i32) -> SubmitAttemptRequest {
+        SubmitAttemptRequest {
+            lab_slug: "tcp-echo-server".to_string(),
+            task_id,
+            task_outcome: TaskOutcome::Passed,
+            points_achieved: None,
+            task_outcome_context: Some("#1 [PASS] server listening on port 4221".to_string()),
+            idempotency_key: uuid::Uuid::new_v4(),
+        }
+    }
+
+    #[test]
+    fn test_enqueue_roundtrips_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("outbox.json");
+
+        let mut outbox = Outbox::default();
+        outbox.enqueue(make_request(7), "echo");
+        outbox.save_to_path(&path).unwrap();
+
+        let loaded = Outbox::load_from_path(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded.pending[0].task_slug, "echo");
+        let request = &loaded.pending[0].request;
+        assert_eq!(request.task_id, 7);
+        assert_eq!(request.lab_slug, "tcp-echo-server");
+        assert!(matches!(request.task_outcome, TaskOutcome::Passed));
+        assert_eq!(
+            request.task_outcome_context.as_deref(),
+            Some("#1 [PASS] server listening on port 4221")
+        );
+    }
+
+    #[test]
+    fn test_idempotency_key_survives_queueing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("outbox.json");
+
+        let first_run = make_request(7);
+        let second_run = make_request(7);
+        assert_ne!(first_run.idempotency_key, second_run.idempotency_key);
+
+        let mut outbox = Outbox::default();
+        outbox.enqueue(first_run.clone(), "echo");
+        outbox.save_to_path(&path).unwrap();
+
+        // a flush later is a retry of the same submission
+        let loaded = Outbox::load_from_path(&path).unwrap();
+        assert_eq!(
+            loaded.pending[0].request.idempotency_key,
+            first_run.idempotency_key
+        );
+    }
+
+    #[test]
+    fn test_entries_queued_without_slug_use_task_id() {
+        let mut outbox = Outbox::default();
+        outbox.enqueue(make_request(7), "echo");
+        assert_eq!(outbox.pending[0].task_label(), "echo");
+
+        // older outboxes have no task_slug field
+        let mut json = serde_json::to_value(&outbox.pending[0]).unwrap();
+        json.as_object_mut().unwrap().remove("task_slug");
+        let old: PendingSubmission = serde_json::from_value(json).unwrap();
+        assert_eq!(old.task_label(), "7");
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let outbox = Outbox::load_from_path(&dir.path().join("outbox.json")).unwrap();
+        assert!(outbox.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_flush_drains_outbox() {
+        let mut outbox = Outbox::default();
+        outbox.enqueue(make_request(1), "echo");
+        outbox.enqueue(make_request(2), "echo");
+
+        let report = outbox.flush(|_| async { Ok(()) }).await;
+        assert_eq!(report.submitted, 2);
+        assert!(report.errors.is_empty());
+        assert!(outbox.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_flush_keeps_failed_submissions() {
+        let mut outbox = Outbox::default();
+        outbox.enqueue(make_request(1), "echo");
+        outbox.enqueue(make_request(2), "echo");
+
+        let report = outbox
+            .flush(|entry| async move {
+                if entry.request.task_id == 2 {
+                    Err(eyre::eyre!("connection reset"))
+                } else {
+                    Ok(())
+                }
+            })
+            .await;
+        assert_eq!(report.submitted, 1);
+        assert_eq!(report.errors.len(), 1);
+        assert!(report.errors[0].contains("connection reset"));
+        assert_eq!(outbox.len(), 1);
+        assert_eq!(outbox.pending[0].request.task_id, 2);
+    }
+}
@@ -1,19 +1,84 @@
 //! Shell command execution for prologue/epilogue hooks
 
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio::process::Command;
 
+/// default per-command timeout for hooks
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// default cap on captured stdout/stderr, per stream
+pub const DEFAULT_MAX_OUTPUT_BYTES: usize = 64 * 1024;
+
 /// result of running a shell command
 #[derive(Debug)]
 pub struct CommandResult {
     pub exit_code: i32,
     pub stdout: String,
     pub stderr: String,
+    pub timed_out: bool,
 }
 
 impl CommandResult {
     pub fn success(&self) -> bool {
-        self.exit_code == 0
+        self.exit_code == 0 && !self.timed_out
+    }
+
+    /// result for a command that never produced an exit status
+    fn failed(message: String) -> Self {
+        Self {
+            exit_code: -1,
+            stdout: String::new(),
+            stderr: message,
+            timed_out: false,
+        }
+    }
+}
+
+/// limits and environment applied to each command
+#[derive(Debug, Clone)]
+pub struct CommandOptions {
+    pub timeout: Duration,
+    pub max_output_bytes: usize,
+    pub env: HashMap<String, String>,
+    pub cwd: Option<PathBuf>,
+}
+
+impl Default for CommandOptions {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            env: HashMap::new(),
+            cwd: None,
+        }
+    }
+}
+
+impl CommandOptions {
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_max_output_bytes(mut self, max: usize) -> Self {
+        self.max_output_bytes = max;
+        self
+    }
+
+    /// add an environment variable visible to every command
+    pub fn with_env(mut self, key: &str, value: &str) -> Self {
+        self.env.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// run commands from this directory instead of the current one
+    pub fn with_cwd(mut self, dir: impl AsRef<Path>) -> Self {
+        self.cwd = Some(dir.as_ref().to_path_buf());
+        self
     }
 }
 
@@ -30,21 +95,83 @@ fn shell_command() -> (&'static str, &'static str) {
 
 /// run a shell command and capture output
 pub async fn run_command(cmd: &str) -> Result<CommandResult, String> {
+    run_command_with(cmd, &CommandOptions::default()).await
+}
+
+/// run a shell command with a timeout and capped output capture
+pub async fn run_command_with(cmd: &str, opts: &CommandOptions) -> Result<CommandResult, String> {
     log::debug!("running command: {}", cmd);
 
     let (shell, flag) = shell_command();
-    let output = Command::new(shell)
+    let mut command = Command::new(shell);
+    command
         .args([flag, cmd])
+        .envs(&opts.env)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
-        .output()
-        .await
+        .kill_on_drop(true);
+
+    if let Some(ref dir) = opts.cwd {
+        if !dir.is_dir() {
+            return Err(format!(
+                "working directory '{}' does not exist",
+                dir.display()
+            ));
+        }
+        command.current_dir(dir);
+    }
+
+    // own process group so a timeout can take down everything the shell started
+    #[cfg(unix)]
+    command.process_group(0);
+
+    let mut child = command
+        .spawn()
         .map_err(|e| format!("failed to execute command: {}", e))?;
 
+    let stdout = child.stdout.take().map(|r| {
+        let max = opts.max_output_bytes;
+        tokio::spawn(read_capped(r, max))
+    });
+    let stderr = child.stderr.take().map(|r| {
+        let max = opts.max_output_bytes;
+        tokio::spawn(read_capped(r, max))
+    });
+
+    let (exit_code, timed_out) = match tokio::time::timeout(opts.timeout, child.wait()).await {
+        Ok(Ok(status)) => (status.code().unwrap_or(-1), false),
+        Ok(Err(e)) => return Err(format!("failed to wait for command: {}", e)),
+        Err(_) => {
+            log::debug!("command timed out after {:?}, killing", opts.timeout);
+            kill_process_tree(&mut child).await;
+            (-1, true)
+        }
+    };
+
+    let stdout = match stdout {
+        Some(handle) => handle.await.unwrap_or_default(),
+        None => String::new(),
+    };
+    let mut stderr = match stderr {
+        Some(handle) => handle.await.unwrap_or_default(),
+        None => String::new(),
+    };
+
+    if timed_out {
+        if !stderr.is_empty() && !stderr.ends_with('\n') {
+            stderr.push('\n');
+        }
+        stderr.push_str(&format!(
+            "command timed out after {}s",
+            opts.timeout.as_secs_f64()
+        ));
+    }
+
     let result = CommandResult {
-        exit_code: output.status.code().unwrap_or(-1),
-        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        exit_code,
+        stdout,
+        stderr,
+        timed_out,
     };
 
     log::debug!(
@@ -57,20 +184,64 @@ pub async fn run_command(cmd: &str) -> Result<CommandResult, String> {
     Ok(result)
 }
 
+/// read a stream to the end, keeping at most `max` bytes
+/// the rest is drained so the child never blocks on a full pipe
+async fn read_capped<R: AsyncRead + Unpin>(mut reader: R, max: usize) -> String {
+    let mut kept = Vec::new();
+    let mut buf = [0u8; 8192];
+    let mut truncated = false;
+
+    loop {
+        match reader.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                let room = max.saturating_sub(kept.len());
+                if n > room {
+                    truncated = true;
+                }
+                kept.extend_from_slice(&buf[..n.min(room)]);
+            }
+        }
+    }
+
+    let mut output = String::from_utf8_lossy(&kept).to_string();
+    if truncated {
+        output.push_str("\n...[output truncated]");
+    }
+    output
+}
+
+#[cfg(unix)]
+async fn kill_process_tree(child: &mut tokio::process::Child) {
+    use nix::sys::signal::{killpg, Signal};
+    use nix::unistd::Pid;
+
+    if let Some(pid) = child.id() {
+        let _ = killpg(Pid::from_raw(pid as i32), Signal::SIGKILL);
+    }
+    let _ = child.kill().await;
+}
+
+#[cfg(not(unix))]
+async fn kill_process_tree(child: &mut tokio::process::Child) {
+    let _ = child.kill().await;
+}
+
 /// run a list of commands sequentially, stopping on first failure
 /// returns Ok(()) if all commands succeed, Err with the failing command on failure
 pub async fn run_commands(commands: &[String]) -> Result<(), (String, CommandResult)> {
+    run_commands_with(commands, &CommandOptions::default()).await
+}
+
+/// like `run_commands`, with explicit per-command options
+pub async fn run_commands_with(
+    commands: &[String],
+    opts: &CommandOptions,
+) -> Result<(), (String, CommandResult)> {
     for cmd in commands {
-        let result = run_command(cmd).await.map_err(|e| {
-            (
-                cmd.clone(),
-                CommandResult {
-                    exit_code: -1,
-                    stdout: String::new(),
-                    stderr: e,
-                },
-            )
-        })?;
+        let result = run_command_with(cmd, opts)
+            .await
+            .map_err(|e| (cmd.clone(), CommandResult::failed(e)))?;
 
         if !result.success() {
             return Err((cmd.clone(), result));
@@ -82,22 +253,23 @@ pub async fn run_commands(commands: &[String]) -> Result<(), (String, CommandRes
 /// run a list of commands, continuing even on failure (for cleanup)
 /// returns a list of (command, result) for any failed commands
 pub async fn run_commands_best_effort(commands: &[String]) -> Vec<(String, CommandResult)> {
+    run_commands_best_effort_with(commands, &CommandOptions::default()).await
+}
+
+/// like `run_commands_best_effort`, with explicit per-command options
+pub async fn run_commands_best_effort_with(
+    commands: &[String],
+    opts: &CommandOptions,
+) -> Vec<(String, CommandResult)> {
     let mut failures = Vec::new();
 
     for cmd in commands {
-        match run_command(cmd).await {
+        match run_command_with(cmd, opts).await {
             Ok(result) if !result.success() => {
                 failures.push((cmd.clone(), result));
             }
             Err(e) => {
-                failures.push((
-                    cmd.clone(),
-                    CommandResult {
-                        exit_code: -1,
-                        stdout: String::new(),
-                        stderr: e,
-                    },
-                ));
+                failures.push((cmd.clone(), CommandResult::failed(e)));
             }
             _ => {}
         }
@@ -154,4 +326,81 @@ mod tests {
         let failures = run_commands_best_effort(&commands).await;
         assert_eq!(failures.len(), 2);
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_command_timeout_kills_child() {
+        let opts = CommandOptions::default().with_timeout(Duration::from_millis(200));
+        let started = std::time::Instant::now();
+        let result = run_command_with("echo started; sleep 10", &opts)
+            .await
+            .unwrap();
+
+        assert!(result.timed_out);
+        assert!(!result.success());
+        assert_eq!(result.stdout.trim(), "started");
+        assert!(result.stderr.contains("timed out after 0.2s"));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_non_zero_exit_is_not_a_timeout() {
+        let result = run_command("exit 3").await.unwrap();
+        assert!(!result.timed_out);
+        assert_eq!(result.exit_code, 3);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_commands_stops_on_timeout() {
+        let opts = CommandOptions::default().with_timeout(Duration::from_millis(200));
+        let commands = vec!["sleep 10".to_string(), "echo after".to_string()];
+        let (cmd, result) = run_commands_with(&commands, &opts).await.unwrap_err();
+        assert_eq!(cmd, "sleep 10");
+        assert!(result.timed_out);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_output_capture_is_capped() {
+        let opts = CommandOptions::default().with_max_output_bytes(16);
+        let result = run_command_with("yes | head -c 100000", &opts)
+            .await
+            .unwrap();
+        assert!(result.success());
+        assert!(result.stdout.starts_with("y\ny\n"));
+        assert!(result.stdout.ends_with("...[output truncated]"));
+        assert!(result.stdout.len() < 64);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_command_sees_injected_env() {
+        let opts = CommandOptions::default()
+            .with_env("LUX_TASK_SLUG", "bind-to-port")
+            .with_env("LUX_PORT", "4221");
+        let result = run_command_with("echo $LUX_TASK_SLUG:$LUX_PORT", &opts)
+            .await
+            .unwrap();
+        assert_eq!(result.stdout.trim(), "bind-to-port:4221");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_command_in_configured_cwd() {
+        let dir = tempfile::tempdir().unwrap();
+        let opts = CommandOptions::default().with_cwd(dir.path());
+        let result = run_command_with("pwd -P", &opts).await.unwrap();
+        assert_eq!(
+            PathBuf::from(result.stdout.trim()),
+            dir.path().canonicalize().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_command_missing_cwd() {
+        let opts = CommandOptions::default().with_cwd("/nonexistent/luxctl/workspace");
+        let err = run_command_with("echo hi", &opts).await.unwrap_err();
+        assert!(err.contains("does not exist"));
+    }
 }
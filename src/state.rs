@@ -3,11 +3,14 @@ use color_eyre::eyre::{self, Ok};
 use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use crate::api::{Task, TaskStatus};
+use crate::config::lux_home;
 
-static CFG_DIR: &str = ".luxctl";
 static STATE_FILE: &str = "state.json";
 
 // salt used for HMAC key derivation (combined with user token)
@@ -72,6 +75,18 @@ fn default_workspace() -> String {
 }
 
 impl ActiveLab {
+    /// a lab view over freshly fetched tasks, with the default workspace and no runtime
+    pub fn from_api(slug: &str, name: &str, tasks: &[Task]) -> Self {
+        ActiveLab {
+            slug: slug.to_string(),
+            name: name.to_string(),
+            fetched_at: Utc::now(),
+            tasks: tasks.iter().map(CachedTask::from_api_task).collect(),
+            workspace: default_workspace(),
+            runtime: None,
+        }
+    }
+
     pub fn total_points(&self) -> i32 {
         self.tasks.iter().map(|t| t.points).sum()
     }
@@ -86,6 +101,40 @@ impl ActiveLab {
             .filter(|t| t.status.is_completed())
             .count()
     }
+
+    /// earned / total points as a percentage; 0 when the lab has no points
+    pub fn progress_percent(&self) -> f64 {
+        let total = self.total_points();
+        if total <= 0 {
+            return 0.0;
+        }
+        f64::from(self.earned_points()) * 100.0 / f64::from(total)
+    }
+
+    pub fn remaining_points(&self) -> i32 {
+        (self.total_points() - self.earned_points()).max(0)
+    }
+
+    pub fn task_by_slug(&self, slug: &str) -> Option<&CachedTask> {
+        self.tasks.iter().find(|t| t.slug == slug)
+    }
+
+    /// 1-based, as shown in task listings; 0 and out-of-range numbers are None
+    pub fn task_by_number(&self, number: usize) -> Option<&CachedTask> {
+        number.checked_sub(1).and_then(|i| self.tasks.get(i))
+    }
+
+    pub fn task_by_id(&self, id: i32) -> Option<&CachedTask> {
+        self.tasks.iter().find(|t| t.id == id)
+    }
+
+    /// a task as given on the command line: its 1-based number or its slug
+    pub fn task_by_ref(&self, task_ref: &str) -> Option<&CachedTask> {
+        match task_ref.parse::<usize>().ok() {
+            Some(number) => self.task_by_number(number),
+            None => self.task_by_slug(task_ref),
+        }
+    }
 }
 
 /// internal state file format (includes checksum)
@@ -108,28 +157,31 @@ impl LabState {
     }
 
     /// load state from disk, verifying integrity with HMAC
-    /// if checksum fails, returns empty state (forces re-fetch)
+    /// if the file is unparseable or the checksum fails, it is backed up and
+    /// an empty state is returned (forces re-fetch)
     pub fn load(token: &str) -> eyre::Result<Self> {
-        let path = Self::state_path()?;
+        Self::load_from_path(&Self::state_path()?, token)
+    }
 
+    fn load_from_path(path: &Path, token: &str) -> eyre::Result<Self> {
         if !path.exists() {
             return Ok(LabState::new());
         }
 
-        let content = fs::read_to_string(&path)
+        let content = fs::read_to_string(path)
             .map_err(|e| eyre::eyre!("failed to read state file: {}", e))?;
 
-        let state_file: StateFile = serde_json::from_str(&content)
-            .map_err(|e| eyre::eyre!("failed to parse state file: {}", e))?;
+        let parsed = serde_json::from_str::<StateFile>(&content)
+            .map_err(|e| log::warn!("failed to parse state file: {}, clearing state", e));
+        let Some(state_file) = parsed.ok() else {
+            return Self::reset_corrupt(path, token);
+        };
 
         // verify checksum
-        let expected = Self::compute_checksum(&state_file.active_lab, token);
-        if state_file.checksum != expected {
+        if !Self::verify_checksum(&state_file.active_lab, token, &state_file.checksum) {
             log::warn!("state file checksum mismatch, clearing state");
             // tampered or token changed - clear state
-            let empty = LabState::new();
-            empty.save(token)?;
-            return Ok(empty);
+            return Self::reset_corrupt(path, token);
         }
 
         Ok(LabState {
@@ -137,10 +189,29 @@ impl LabState {
         })
     }
 
+    /// move a bad state file to `state.corrupt.<timestamp>.json` and start fresh
+    fn reset_corrupt(path: &Path, token: &str) -> eyre::Result<Self> {
+        let backup = path.with_file_name(format!(
+            "state.corrupt.{}.json",
+            Utc::now().format("%Y%m%dT%H%M%S%.3f")
+        ));
+        if let Err(e) = fs::rename(path, &backup) {
+            log::warn!("failed to back up state file: {}", e);
+        } else {
+            log::warn!("previous state backed up to {}", backup.display());
+        }
+
+        let empty = LabState::new();
+        empty.save_to_path(path, token)?;
+        Ok(empty)
+    }
+
     /// save state to disk with HMAC checksum
     pub fn save(&self, token: &str) -> eyre::Result<()> {
-        let path = Self::state_path()?;
+        self.save_to_path(&Self::state_path()?, token)
+    }
 
+    fn save_to_path(&self, path: &Path, token: &str) -> eyre::Result<()> {
         if let Some(dir) = path.parent() {
             fs::create_dir_all(dir)?;
         }
@@ -154,7 +225,7 @@ impl LabState {
         let content = serde_json::to_string_pretty(&state_file)
             .map_err(|e| eyre::eyre!("failed to serialize state: {}", e))?;
 
-        fs::write(&path, content)?;
+        write_atomic(path, &content)?;
         log::debug!("state saved to {}", path.display());
 
         Ok(())
@@ -169,15 +240,10 @@ impl LabState {
         workspace: &str,
         runtime: Option<&str>,
     ) {
-        let cached_tasks: Vec<CachedTask> = tasks.iter().map(CachedTask::from_api_task).collect();
-
         self.active_lab = Some(ActiveLab {
-            slug: slug.to_string(),
-            name: name.to_string(),
-            fetched_at: Utc::now(),
-            tasks: cached_tasks,
             workspace: workspace.to_string(),
             runtime: runtime.map(|s| s.to_string()),
+            ..ActiveLab::from_api(slug, name, tasks)
         });
     }
 
@@ -228,32 +294,70 @@ impl LabState {
         });
     }
 
-    /// compute HMAC-SHA256 checksum of lab data
-    /// returns empty string if HMAC creation fails (should never happen for SHA256)
-    fn compute_checksum(lab: &Option<ActiveLab>, token: &str) -> String {
+    /// HMAC-SHA256 over the lab data as JSON, keyed by token + salt
+    fn checksum_mac(lab: &Option<ActiveLab>, token: &str) -> Option<HmacSha256> {
         // derive key from token + salt
         let key = format!("{}{}", token, HMAC_SALT);
 
         // HMAC-SHA256 accepts any key length, so this should never fail
         let Some(mut mac) = HmacSha256::new_from_slice(key.as_bytes()).ok() else {
             log::error!("failed to create HMAC - this should never happen");
-            return String::new();
+            return None;
         };
 
-        // hash the lab data as JSON
         let data = serde_json::to_string(lab).unwrap_or_default();
         mac.update(data.as_bytes());
+        Some(mac)
+    }
 
-        let result = mac.finalize();
-        hex::encode(result.into_bytes())
+    /// compute HMAC-SHA256 checksum of lab data
+    /// returns empty string if HMAC creation fails (should never happen for SHA256)
+    fn compute_checksum(lab: &Option<ActiveLab>, token: &str) -> String {
+        Self::checksum_mac(lab, token)
+            .map(|mac| hex::encode(mac.finalize().into_bytes()))
+            .unwrap_or_default()
     }
 
-    fn state_path() -> eyre::Result<PathBuf> {
-        let home =
-            dirs::home_dir().ok_or_else(|| eyre::eyre!("could not determine home directory"))?;
+    /// check a stored hex checksum in constant time
+    fn verify_checksum(lab: &Option<ActiveLab>, token: &str, checksum: &str) -> bool {
+        let Some(expected) = hex::decode(checksum).ok() else {
+            return false;
+        };
+        Self::checksum_mac(lab, token).is_some_and(|mac| mac.verify_slice(&expected).is_ok())
+    }
 
-        Ok(home.join(CFG_DIR).join(STATE_FILE))
+    /// directory holding the state file (and the rest of luxctl's files)
+    pub fn base_dir() -> eyre::Result<PathBuf> {
+        lux_home()
     }
+
+    pub(crate) fn state_path() -> eyre::Result<PathBuf> {
+        Ok(Self::state_path_in(&Self::base_dir()?))
+    }
+
+    fn state_path_in(lux_home: &Path) -> PathBuf {
+        lux_home.join(STATE_FILE)
+    }
+}
+
+/// write to a temp file next to `path`, then rename it over `path`, so readers
+/// see either the old contents or the new ones, never a truncated file
+fn write_atomic(path: &Path, content: &str) -> eyre::Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| eyre::eyre!("invalid state path: {}", path.display()))?;
+    let tmp = path.with_file_name(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
+
+    fs::write(&tmp, content)?;
+    if let Err(e) = fs::rename(&tmp, path) {
+        let _ = fs::remove_file(&tmp);
+        return Err(e.into());
+    }
+    Ok(())
 }
 
 impl Default for LabState {
@@ -289,6 +393,7 @@ mod tests {
             points_earned: 35,
             hints: vec![],
             validators: vec!["tcp_listening:int(8080)".to_string()],
+            port: None,
             prologue: vec![],
             epilogue: vec![],
         };
@@ -302,6 +407,80 @@ mod tests {
         assert_eq!(cached.validators.len(), 1);
     }
 
+    fn lab_with_tasks(slugs: &[&str]) -> ActiveLab {
+        let tasks = slugs
+            .iter()
+            .enumerate()
+            .map(|(i, slug)| CachedTask {
+                id: 100 + i as i32,
+                slug: slug.to_string(),
+                title: slug.to_string(),
+                points: 10,
+                points_earned: 0,
+                status: TaskStatus::Challenged,
+                sort_order: i as i32,
+                validators: vec![],
+            })
+            .collect();
+        ActiveLab {
+            slug: "test".to_string(),
+            name: "Test Lab".to_string(),
+            fetched_at: Utc::now(),
+            tasks,
+            workspace: ".".to_string(),
+            runtime: None,
+        }
+    }
+
+    #[test]
+    fn test_progress_of_empty_lab() {
+        let lab = lab_with_tasks(&[]);
+        assert_eq!(lab.progress_percent(), 0.0);
+        assert_eq!(lab.remaining_points(), 0);
+    }
+
+    #[test]
+    fn test_progress_of_partially_completed_lab() {
+        let mut lab = lab_with_tasks(&["bind-port", "echo", "concurrency", "shutdown"]);
+        lab.tasks[0].points_earned = 10;
+        lab.tasks[1].points_earned = 5;
+        assert_eq!(lab.progress_percent(), 37.5);
+        assert_eq!(lab.remaining_points(), 25);
+    }
+
+    #[test]
+    fn test_task_lookup_by_slug() {
+        let lab = lab_with_tasks(&["bind-port", "echo"]);
+        assert_eq!(lab.task_by_slug("echo").unwrap().id, 101);
+        assert!(lab.task_by_slug("missing").is_none());
+    }
+
+    #[test]
+    fn test_task_lookup_by_number() {
+        let lab = lab_with_tasks(&["bind-port", "echo"]);
+        assert_eq!(lab.task_by_number(1).unwrap().slug, "bind-port");
+        assert_eq!(lab.task_by_number(2).unwrap().slug, "echo");
+        assert!(lab.task_by_number(0).is_none());
+        assert!(lab.task_by_number(3).is_none());
+    }
+
+    #[test]
+    fn test_task_lookup_by_id() {
+        let lab = lab_with_tasks(&["bind-port", "echo"]);
+        assert_eq!(lab.task_by_id(100).unwrap().slug, "bind-port");
+        assert!(lab.task_by_id(7).is_none());
+    }
+
+    #[test]
+    fn test_task_lookup_by_ref() {
+        let lab = lab_with_tasks(&["bind-port", "echo"]);
+        assert_eq!(lab.task_by_ref("2").unwrap().slug, "echo");
+        assert_eq!(lab.task_by_ref("bind-port").unwrap().id, 100);
+        assert!(lab.task_by_ref("0").is_none());
+        assert!(lab.task_by_ref("3").is_none());
+        assert!(lab.task_by_ref("missing").is_none());
+    }
+
     #[test]
     fn test_compute_checksum_deterministic() {
         let lab = Some(ActiveLab {
@@ -321,6 +500,221 @@ mod tests {
         assert_eq!(checksum1, checksum2);
     }
 
+    fn backups_in(dir: &Path) -> Vec<String> {
+        fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .filter(|name| name.starts_with("state.corrupt."))
+            .collect()
+    }
+
+    #[test]
+    fn test_lux_home_relocates_state_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let home =
+            crate::config::resolve_lux_home(Some(dir.path().to_string_lossy().to_string()), None)
+                .unwrap();
+        let path = LabState::state_path_in(&home);
+        assert_eq!(path, dir.path().join("state.json"));
+
+        let mut state = LabState::new();
+        state.set_active("tcp-echo", "TCP Echo", &[], ".", None);
+        state.save_to_path(&path, test_token()).unwrap();
+        assert!(dir.path().join("state.json").exists());
+    }
+
+    #[test]
+    fn test_set_active_keeps_workspace_and_runtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+
+        let mut state = LabState::new();
+        state.set_active("tcp-echo", "TCP Echo", &[], "/work/echo", Some("go"));
+        state.save_to_path(&path, test_token()).unwrap();
+
+        let loaded = LabState::load_from_path(&path, test_token()).unwrap();
+        let lab = loaded.get_active().unwrap();
+        assert_eq!(lab.slug, "tcp-echo");
+        assert_eq!(lab.name, "TCP Echo");
+        assert_eq!(lab.workspace, "/work/echo");
+        assert_eq!(lab.runtime.as_deref(), Some("go"));
+    }
+
+    #[test]
+    fn test_runtime_and_workspace_changes_persist() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+
+        let mut state = LabState::new();
+        state.set_active("tcp-echo", "TCP Echo", &[], "/work/echo", None);
+        state.save_to_path(&path, test_token()).unwrap();
+
+        let mut state = LabState::load_from_path(&path, test_token()).unwrap();
+        state.set_runtime("rust");
+        state.set_workspace("/work/echo-rs");
+        state.save_to_path(&path, test_token()).unwrap();
+
+        let loaded = LabState::load_from_path(&path, test_token()).unwrap();
+        let lab = loaded.get_active().unwrap();
+        assert_eq!(lab.runtime.as_deref(), Some("rust"));
+        assert_eq!(lab.workspace, "/work/echo-rs");
+        assert!(backups_in(dir.path()).is_empty());
+    }
+
+    fn api_task(id: i32, status: TaskStatus, points_earned: i32) -> Task {
+        Task {
+            id,
+            uuid: String::new(),
+            slug: format!("task-{}", id),
+            title: format!("Task {}", id),
+            description: String::new(),
+            sort_order: id,
+            input_type: TaskInputType::None,
+            scores: "5:10:50".to_string(),
+            status,
+            is_free: false,
+            is_locked: false,
+            abandoned_deduction: 0,
+            points_earned,
+            hints: vec![],
+            validators: vec![],
+            port: None,
+            prologue: vec![],
+            epilogue: vec![],
+        }
+    }
+
+    #[test]
+    fn test_refresh_tasks_keeps_workspace_and_runtime() {
+        let mut state = LabState::new();
+        state.set_active(
+            "tcp-echo",
+            "TCP Echo",
+            &[api_task(1, TaskStatus::ChallengeAwaits, 0)],
+            "/work/echo",
+            Some("go"),
+        );
+        let fetched_before = state.get_active().unwrap().fetched_at;
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        state.refresh_tasks(&[
+            api_task(1, TaskStatus::ChallengeCompleted, 50),
+            api_task(2, TaskStatus::ChallengeAwaits, 0),
+        ]);
+
+        let lab = state.get_active().unwrap();
+        assert!(lab.fetched_at > fetched_before);
+        assert_eq!(lab.tasks.len(), 2);
+        assert!(matches!(
+            lab.tasks[0].status,
+            TaskStatus::ChallengeCompleted
+        ));
+        assert_eq!(lab.tasks[0].points_earned, 50);
+        assert_eq!(lab.workspace, "/work/echo");
+        assert_eq!(lab.runtime.as_deref(), Some("go"));
+    }
+
+    #[test]
+    fn test_malformed_state_is_backed_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        fs::write(&path, "{ not json").unwrap();
+
+        let state = LabState::load_from_path(&path, test_token()).unwrap();
+        assert!(state.get_active().is_none());
+
+        let backups = backups_in(dir.path());
+        assert_eq!(backups.len(), 1);
+        assert!(backups[0].ends_with(".json"));
+        assert_eq!(
+            fs::read_to_string(dir.path().join(&backups[0])).unwrap(),
+            "{ not json"
+        );
+
+        // the fresh state loads cleanly
+        let reloaded = LabState::load_from_path(&path, test_token()).unwrap();
+        assert!(reloaded.get_active().is_none());
+        assert_eq!(backups_in(dir.path()).len(), 1);
+    }
+
+    #[test]
+    fn test_checksum_mismatch_is_backed_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+
+        let mut state = LabState::new();
+        state.set_active("tcp-echo", "TCP Echo", &[], ".", None);
+        state.save_to_path(&path, test_token()).unwrap();
+        assert!(LabState::load_from_path(&path, test_token())
+            .unwrap()
+            .get_active()
+            .is_some());
+
+        let loaded = LabState::load_from_path(&path, "another-token").unwrap();
+        assert!(loaded.get_active().is_none());
+        assert_eq!(backups_in(dir.path()).len(), 1);
+    }
+
+    #[test]
+    fn test_write_atomic_replaces_file_without_leftovers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+
+        write_atomic(&path, "{\"old\": true}").unwrap();
+        write_atomic(&path, "{\"new\": true}").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"new\": true}");
+        let entries: Vec<_> = fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test]
+    fn test_write_atomic_readers_never_see_partial_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        let small = "{}".to_string();
+        let large = format!("{{\"data\": \"{}\"}}", "x".repeat(256 * 1024));
+        write_atomic(&path, &small).unwrap();
+
+        let reader_path = path.clone();
+        let (expected_small, expected_large) = (small.clone(), large.clone());
+        let reader = std::thread::spawn(move || {
+            for _ in 0..200 {
+                let content = fs::read_to_string(&reader_path).unwrap();
+                assert!(content == expected_small || content == expected_large);
+            }
+        });
+        for i in 0..50 {
+            let content = if i % 2 == 0 { &large } else { &small };
+            write_atomic(&path, content).unwrap();
+        }
+        reader.join().unwrap();
+    }
+
+    #[test]
+    fn test_verify_checksum_rejects_flipped_bit() {
+        let lab = Some(ActiveLab {
+            slug: "test".to_string(),
+            name: "Test Lab".to_string(),
+            fetched_at: DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+                .expect("valid date")
+                .with_timezone(&Utc),
+            tasks: vec![],
+            workspace: ".".to_string(),
+            runtime: None,
+        });
+
+        let checksum = LabState::compute_checksum(&lab, test_token());
+        assert!(LabState::verify_checksum(&lab, test_token(), &checksum));
+
+        let mut bytes = hex::decode(&checksum).unwrap();
+        bytes[0] ^= 0x01;
+        let flipped = hex::encode(bytes);
+        assert!(!LabState::verify_checksum(&lab, test_token(), &flipped));
+        assert!(!LabState::verify_checksum(&lab, test_token(), "not hex"));
+        assert!(!LabState::verify_checksum(&lab, "other-token", &checksum));
+    }
+
     #[test]
     fn test_checksum_changes_with_data() {
         let lab1 = Some(ActiveLab {
@@ -1,3 +1,8 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
 /// TestResults aggregates all test cases for a task
 #[derive(Debug)]
 pub struct TestResults {
@@ -18,7 +23,12 @@ impl TestResults {
     }
 
     pub fn failed(&self) -> usize {
-        self.tests.iter().filter(|t| !t.passed()).count()
+        self.tests.iter().filter(|t| t.failed()).count()
+    }
+
+    /// tests that could not run, counted as neither passed nor failed
+    pub fn skipped(&self) -> usize {
+        self.tests.iter().filter(|t| t.is_skipped()).count()
     }
 
     pub fn total(&self) -> usize {
@@ -28,6 +38,16 @@ impl TestResults {
     pub fn all_passed(&self) -> bool {
         self.tests.iter().all(|t| t.passed())
     }
+
+    /// sum of all test durations
+    pub fn total_duration(&self) -> Duration {
+        self.tests.iter().map(|t| t.duration).sum()
+    }
+
+    /// the test that took the longest to run
+    pub fn slowest(&self) -> Option<&TestCase> {
+        self.tests.iter().max_by_key(|t| t.duration)
+    }
 }
 
 impl Default for TestResults {
@@ -36,22 +56,202 @@ impl Default for TestResults {
     }
 }
 
+/// ValidationContext carries task-level settings validators fall back on
+#[derive(Debug, Clone, Default)]
+pub struct ValidationContext {
+    /// port the task's server listens on, preferred over the validator module default
+    pub port: Option<u16>,
+    /// lab workspace; builds, processes and relative file paths resolve against it
+    pub project_path: Option<PathBuf>,
+    /// lab runtime (e.g. "go"), skips build command detection when set
+    pub runtime: Option<String>,
+    /// user opted in to validators that run shell commands
+    pub allow_commands: bool,
+    /// the docker check's answer, shared by clones; a new context checks again
+    pub docker_available: Arc<OnceCell<bool>>,
+}
+
+impl ValidationContext {
+    /// the project path, or the current directory when unset
+    pub fn workspace(&self) -> PathBuf {
+        self.project_path
+            .clone()
+            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
+    }
+
+    /// resolve a validator path against the project path; absolute paths are kept
+    pub fn resolve(&self, path: &str) -> PathBuf {
+        match &self.project_path {
+            Some(root) if Path::new(path).is_relative() => root.join(path),
+            _ => PathBuf::from(path),
+        }
+    }
+}
+
 /// TestCase represents a single validation test result
 #[derive(Debug)]
 pub struct TestCase {
     pub name: String,
-    pub result: Result<String, String>, // Ok(success_msg) or Err(error_msg)
+    pub outcome: Outcome,
+    pub duration: Duration,         // set by the runner around validate()
+    pub mismatch: Option<Mismatch>, // expected vs actual, rendered as a diff on failure
+    pub details: Option<String>,    // request/response transcript, shown with --detailed
+}
+
+/// how a test case ended, with the message to show for it
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Passed(String),
+    Failed(String),
+    /// the check could not run for reasons outside the student's code (e.g. docker missing)
+    Skipped(String),
+}
+
+impl From<Result<String, String>> for Outcome {
+    fn from(result: Result<String, String>) -> Self {
+        match result {
+            Ok(msg) => Outcome::Passed(msg),
+            Err(msg) => Outcome::Failed(msg),
+        }
+    }
+}
+
+/// expected and actual values of a failed comparison
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Mismatch {
+    /// worth a line diff rather than an inline message
+    pub fn is_multiline(&self) -> bool {
+        self.expected.contains('\n') || self.actual.contains('\n')
+    }
 }
 
 impl TestCase {
+    /// a result with no mismatch, transcript or duration yet; add those with the `with_*` builders
+    pub fn new(name: impl Into<String>, result: Result<String, String>) -> Self {
+        Self {
+            name: name.into(),
+            outcome: result.into(),
+            duration: Duration::ZERO,
+            mismatch: None,
+            details: None,
+        }
+    }
+
+    /// a check that could not run for reasons outside the student's code
+    pub fn skip(name: &str, reason: &str) -> Self {
+        Self {
+            outcome: Outcome::Skipped(reason.to_string()),
+            ..Self::new(name, Ok(String::new()))
+        }
+    }
+
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    pub fn with_details(mut self, details: String) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn with_mismatch(mut self, expected: &str, actual: &str) -> Self {
+        self.mismatch = Some(Mismatch {
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        });
+        self
+    }
+
     pub fn passed(&self) -> bool {
-        self.result.is_ok()
+        matches!(self.outcome, Outcome::Passed(_))
+    }
+
+    pub fn failed(&self) -> bool {
+        matches!(self.outcome, Outcome::Failed(_))
+    }
+
+    pub fn is_skipped(&self) -> bool {
+        matches!(self.outcome, Outcome::Skipped(_))
     }
 
     pub fn message(&self) -> &str {
-        match &self.result {
-            Ok(msg) => msg,
-            Err(msg) => msg,
+        match &self.outcome {
+            Outcome::Passed(msg) | Outcome::Failed(msg) | Outcome::Skipped(msg) => msg,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case(name: &str, millis: u64) -> TestCase {
+        TestCase::new(name, Ok(String::new())).with_duration(Duration::from_millis(millis))
+    }
+
+    #[test]
+    fn test_context_resolves_relative_paths() {
+        let ctx = ValidationContext {
+            project_path: Some(PathBuf::from("/work/lab")),
+            ..Default::default()
+        };
+        assert_eq!(ctx.resolve("out.txt"), PathBuf::from("/work/lab/out.txt"));
+        assert_eq!(ctx.resolve("/tmp/out.txt"), PathBuf::from("/tmp/out.txt"));
+        assert_eq!(ctx.workspace(), PathBuf::from("/work/lab"));
+
+        let ctx = ValidationContext::default();
+        assert_eq!(ctx.resolve("out.txt"), PathBuf::from("out.txt"));
+    }
+
+    #[test]
+    fn test_skipped_is_neither_passed_nor_failed() {
+        let skipped = TestCase::skip("docker:Go1.22-race", "docker not available");
+        assert!(skipped.is_skipped());
+        assert!(!skipped.passed());
+        assert!(!skipped.failed());
+        assert_eq!(skipped.message(), "docker not available");
+
+        let mut results = TestResults::new();
+        results.add(case("a", 10));
+        results.add(skipped);
+        results.add(TestCase {
+            outcome: Outcome::Failed("boom".to_string()),
+            ..case("b", 10)
+        });
+        assert_eq!(results.passed(), 1);
+        assert_eq!(results.failed(), 1);
+        assert_eq!(results.skipped(), 1);
+        assert!(!results.all_passed());
+    }
+
+    #[test]
+    fn test_total_duration_accumulates() {
+        let mut results = TestResults::new();
+        results.add(case("a", 120));
+        results.add(case("b", 30));
+        results.add(case("c", 50));
+        assert_eq!(results.total_duration(), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_slowest_returns_longest() {
+        let mut results = TestResults::new();
+        results.add(case("fast", 10));
+        results.add(case("slow", 900));
+        results.add(case("medium", 300));
+        assert_eq!(results.slowest().map(|t| t.name.as_str()), Some("slow"));
+    }
+
+    #[test]
+    fn test_empty_results_timing() {
+        let results = TestResults::new();
+        assert_eq!(results.total_duration(), Duration::ZERO);
+        assert!(results.slowest().is_none());
+    }
+}
@@ -1,5 +1,11 @@
+use std::future::Future;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
 use colored::Colorize;
 
+use crate::tasks::{Mismatch, TestCase};
 use crate::VERSION;
 
 const SYM_STEP: &str = "▸";
@@ -8,11 +14,68 @@ const SYM_FAIL: &str = "✗";
 const SYM_WARN: &str = "!";
 const SYM_SKIP: &str = "○";
 const INDENT: &str = "  ";
+/// braille spinner shown next to a running validator
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+/// off a TTY, validators running longer than this get a one-off "running..." line
+const SLOW_VALIDATOR: Duration = Duration::from_secs(5);
+/// width of the test name column; longer names are cut with an ellipsis
+const NAME_WIDTH: usize = 48;
+
+/// set by `--quiet`: only results and errors are printed
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// turn quiet mode on or off for the whole process, call once at startup
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// set by `--yes`: every confirmation is answered yes without prompting
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+pub fn set_assume_yes(yes: bool) {
+    ASSUME_YES.store(yes, Ordering::Relaxed);
+}
+
+/// ask a yes/no question on the terminal, defaulting to no. `--yes` answers yes
+/// up front; without it, a non-interactive stdin answers no
+pub fn confirm(question: &str) -> bool {
+    confirm_with(ASSUME_YES.load(Ordering::Relaxed), || {
+        if !std::io::stdin().is_terminal() {
+            return None;
+        }
+        print!("{}{} [y/N] ", INDENT, question);
+        let _ = std::io::Write::flush(&mut std::io::stdout());
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer).ok()?;
+        Some(answer)
+    })
+}
+
+/// `ask` prompts and returns the raw answer, or None when no one can answer
+fn confirm_with(assume_yes: bool, ask: impl FnOnce() -> Option<String>) -> bool {
+    assume_yes
+        || ask().is_some_and(|answer| matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// where RunUI lines go; tests capture them instead of printing
+enum Output {
+    Terminal,
+    #[cfg(test)]
+    Captured(std::sync::Mutex<Vec<String>>),
+}
 
 /// UI output for running validators, matching HeroTerminal visual style
 pub struct RunUI {
     task_name: String,
     total_validators: usize,
+    interactive: bool,
+    quiet: bool,
+    output: Output,
 }
 
 impl RunUI {
@@ -20,31 +83,152 @@ impl RunUI {
         Self {
             task_name: task_name.to_string(),
             total_validators: validator_count,
+            interactive: std::io::stdout().is_terminal(),
+            quiet: is_quiet(),
+            output: Output::Terminal,
+        }
+    }
+
+    #[cfg(test)]
+    fn capturing(task_name: &str, validator_count: usize, quiet: bool) -> Self {
+        Self {
+            task_name: task_name.to_string(),
+            total_validators: validator_count,
+            interactive: false,
+            quiet,
+            output: Output::Captured(std::sync::Mutex::new(Vec::new())),
+        }
+    }
+
+    #[cfg(test)]
+    fn captured(&self) -> Vec<String> {
+        match &self.output {
+            Output::Captured(lines) => lines.lock().map(|l| l.clone()).unwrap_or_default(),
+            Output::Terminal => Vec::new(),
+        }
+    }
+
+    /// print a result line; these stay in quiet mode
+    fn emit(&self, line: String) {
+        match &self.output {
+            Output::Terminal => println!("{}", line),
+            #[cfg(test)]
+            Output::Captured(lines) => {
+                if let Ok(mut lines) = lines.lock() {
+                    lines.push(line);
+                }
+            }
+        }
+    }
+
+    /// print a failure line: stdout normally, stderr in quiet mode
+    fn emit_error(&self, line: String) {
+        match &self.output {
+            Output::Terminal if self.quiet => eprintln!("{}", line),
+            _ => self.emit(line),
+        }
+    }
+
+    /// print a decorative line (headers, steps, hints), dropped in quiet mode
+    fn decor(&self, line: String) {
+        if !self.quiet {
+            self.emit(line);
+        }
+    }
+
+    /// await `work` while a spinner and "[k/N] name" tick on a TTY; elsewhere a
+    /// single static "running..." line is printed once the validator gets slow
+    pub async fn spin_while<F: Future>(&self, completed: usize, name: &str, work: F) -> F::Output {
+        tokio::pin!(work);
+        let mut ticker = tokio::time::interval(SPINNER_INTERVAL);
+        let started = Instant::now();
+        let mut tick = 0;
+        let mut announced = false;
+
+        loop {
+            tokio::select! {
+                output = &mut work => {
+                    self.clear_progress();
+                    return output;
+                }
+                _ = ticker.tick() => {
+                    if self.quiet {
+                        // results only
+                    } else if self.interactive {
+                        print!(
+                            "\r\x1b[2K{}{} {} {}",
+                            INDENT,
+                            spinner_frame(tick).cyan(),
+                            progress_label(completed, self.total_validators).dimmed(),
+                            name.dimmed()
+                        );
+                        let _ = std::io::Write::flush(&mut std::io::stdout());
+                    } else if !announced && started.elapsed() >= SLOW_VALIDATOR {
+                        self.decor(format!("{}{} {} running...", INDENT, SYM_STEP, name));
+                        announced = true;
+                    }
+                    tick += 1;
+                }
+            }
+        }
+    }
+
+    /// erase the progress line before printing a result
+    pub fn clear_progress(&self) {
+        if self.interactive && !self.quiet {
+            print!("\r\x1b[2K");
         }
     }
 
     /// print version header: "projectlighthouse CLI v1.2.0"
     pub fn header(&self) {
-        println!("{}projectlighthouse CLI v{}", INDENT, VERSION.dimmed());
+        self.decor(format!(
+            "{}projectlighthouse CLI v{}",
+            INDENT,
+            VERSION.dimmed()
+        ));
     }
 
     /// print progress step: "▸ Compiling project..."
     pub fn step(&self, msg: &str) {
-        println!("{}{} {}", INDENT, SYM_STEP.blue(), msg);
+        self.decor(format!("{}{} {}", INDENT, SYM_STEP.blue(), msg));
     }
 
     pub fn blank_line(&self) {
-        println!();
+        self.decor(String::new());
     }
 
-    /// print passing test: "✓ server listening on port 4221"
-    pub fn test_pass(&self, name: &str) {
-        println!("{}{} {}", INDENT, SYM_PASS.green(), name);
+    /// print passing test: "✓ server listening on port 4221        12ms"
+    pub fn test_pass(&self, name: &str, duration: Duration) {
+        self.decor(format!(
+            "{}{} {}  {}",
+            INDENT,
+            SYM_PASS.green(),
+            fit_name(name, NAME_WIDTH),
+            format_duration(duration).dimmed()
+        ));
     }
 
-    /// print failing test with optional detail
-    pub fn test_fail(&self, name: &str, detail: Option<&str>) {
-        println!("{}{} {}", INDENT, SYM_FAIL.red(), name.red());
+    /// print a test that could not run, with the reason
+    pub fn test_skip(&self, name: &str, reason: &str) {
+        self.decor(format!(
+            "{}{} {}  {}",
+            INDENT,
+            SYM_SKIP.yellow(),
+            fit_name(name, NAME_WIDTH).yellow(),
+            reason.dimmed()
+        ));
+    }
+
+    /// print failing test with its duration and optional detail
+    pub fn test_fail(&self, name: &str, detail: Option<&str>, duration: Duration) {
+        self.emit_error(format!(
+            "{}{} {}  {}",
+            INDENT,
+            SYM_FAIL.red(),
+            fit_name(name, NAME_WIDTH).red(),
+            format_duration(duration).dimmed()
+        ));
 
         if let Some(d) = detail {
             if !d.is_empty() && d != name {
@@ -56,55 +240,109 @@ impl RunUI {
                 };
 
                 for line in display.lines() {
-                    println!("{}  {}", INDENT, line.red());
+                    self.emit_error(format!("{}  {}", INDENT, line.red()));
                 }
             }
         }
     }
 
+    /// print a line diff of expected vs actual under a failed test
+    pub fn diff(&self, mismatch: &Mismatch) {
+        self.emit_error(format!(
+            "{}  {} {}",
+            INDENT,
+            "-expected".red(),
+            "+actual".green()
+        ));
+        for line in diff_lines(&mismatch.expected, &mismatch.actual) {
+            let styled = if line.starts_with('-') {
+                line.red()
+            } else if line.starts_with('+') {
+                line.green()
+            } else {
+                line.dimmed()
+            };
+            self.emit_error(format!("{}  {}", INDENT, styled));
+        }
+    }
+
+    /// print a validator's request/response transcript under its result
+    pub fn details(&self, transcript: &str) {
+        for line in transcript.lines() {
+            self.decor(format!("{}    {}", INDENT, line.dimmed()));
+        }
+    }
+
+    /// print a suggested fix under a failed test: "→ is your server running on the right port?"
+    pub fn suggestion(&self, text: &str) {
+        self.decor(format!("{}  {} {}", INDENT, "→".yellow(), text.yellow()));
+    }
+
     /// print success summary: "PASSED  All 3 tests passed!"
     pub fn summary_pass(&self, total: usize) {
-        println!(
-            "{}{}  All {} tests passed!",
-            INDENT,
-            "PASSED".green().bold(),
-            total
-        );
+        self.emit(summary_pass_line(total));
     }
 
     /// print failure summary: "FAILED  1 of 3 tests failed"
-    pub fn summary_fail(&self, passed: usize, total: usize) {
-        let failed = total - passed;
-        println!(
-            "{}{}  {} of {} tests failed",
-            INDENT,
-            "FAILED".red().bold(),
-            failed,
-            total
-        );
+    pub fn summary_fail(&self, failed: usize, total: usize) {
+        self.emit(summary_fail_line(failed, total));
+    }
+
+    /// print summary when nothing failed but some tests could not run
+    pub fn summary_skipped(&self, skipped: usize, total: usize) {
+        self.emit(summary_skipped_line(skipped, total));
+    }
+
+    /// print timing summary: "took 1.24s (slowest: GET / returns 200, 820ms)"
+    pub fn timing(&self, total: Duration, slowest: Option<&TestCase>) {
+        let mut line = format!("took {}", format_duration(total));
+        if let Some(t) = slowest {
+            line.push_str(&format!(
+                " (slowest: {}, {})",
+                t.name,
+                format_duration(t.duration)
+            ));
+        }
+        self.decor(format!("{}{}", INDENT, line.dimmed()));
     }
 
     /// print hint: "Hint: Check that your response includes the comma."
     pub fn hint(&self, text: &str) {
-        println!();
-        println!("{}{} {}", INDENT, "Hint:".dimmed(), text);
+        self.decor(String::new());
+        self.decor(format!("{}{} {}", INDENT, "Hint:".dimmed(), text));
     }
 
     /// print task separator for multi-task validation
     pub fn task_separator(&self, current: usize, total: usize, task_slug: &str) {
-        println!(
+        self.decor(format!(
             "{}━━━ Task {}/{}: {} ━━━",
             INDENT,
             current,
             total,
             task_slug.bold()
-        );
+        ));
+    }
+
+    /// print separator before a watch-mode re-run
+    pub fn rerun_separator(&self, changed: &str) {
+        self.decor(String::new());
+        self.decor(format!(
+            "{}━━━ {} changed, re-running {} ━━━",
+            INDENT,
+            changed.bold(),
+            self.task_name
+        ));
+        self.decor(String::new());
     }
 
     /// print points earned on success
     pub fn points_earned(&self, points: i32) {
         if points > 0 {
-            println!("{}{}", INDENT, format!("+{} XP", points).green().bold());
+            self.decor(format!(
+                "{}{}",
+                INDENT,
+                format!("+{} XP", points).green().bold()
+            ));
         }
     }
 
@@ -119,6 +357,209 @@ impl RunUI {
     }
 }
 
+/// lines compared per side; longer bodies are cut before diffing
+const MAX_DIFF_LINES: usize = 200;
+
+/// line diff of `expected` against `actual`: " same", "-expected only", "+actual only"
+pub fn diff_lines(expected: &str, actual: &str) -> Vec<String> {
+    let old: Vec<&str> = expected.lines().take(MAX_DIFF_LINES).collect();
+    let new: Vec<&str> = actual.lines().take(MAX_DIFF_LINES).collect();
+
+    // lcs[i][j] = longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::new();
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            out.push(format!(" {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            out.push(format!("-{}", old[i]));
+            i += 1;
+        } else {
+            out.push(format!("+{}", new[j]));
+            j += 1;
+        }
+    }
+    out.extend(old[i..].iter().map(|l| format!("-{}", l)));
+    out.extend(new[j..].iter().map(|l| format!("+{}", l)));
+    out
+}
+
+/// failure patterns mapped to a short actionable fix, checked in order
+const SUGGESTIONS: &[(&[&str], &str)] = &[
+    (
+        &[
+            "connection refused",
+            "connection failed",
+            "failed to connect",
+        ],
+        "is your server running on the right port?",
+    ),
+    (
+        &[
+            "connection timeout",
+            "timeout waiting for response",
+            "timeout on request",
+        ],
+        "server accepted the connection but never answered; check you write a response",
+    ),
+    (
+        &[
+            "connection closed before response",
+            "connection closed after request",
+        ],
+        "server closed the connection early; make sure the full response is written before closing",
+    ),
+    (
+        &["invalid json"],
+        "endpoint returned non-JSON; check the body and Content-Type",
+    ),
+    (
+        &["got 404"],
+        "route not registered; check the path and method your router handles",
+    ),
+    (
+        &["got 405"],
+        "route exists but not for this method; register the right HTTP method",
+    ),
+    (
+        &["got 500"],
+        "your server hit an internal error; check its logs",
+    ),
+    (
+        &["invalid status line"],
+        "status line should look like \"HTTP/1.1 200 OK\\r\\n\"",
+    ),
+    (
+        &["does not exist"],
+        "file not found; check the path relative to your workspace",
+    ),
+];
+
+/// short suggestion for a failed test based on its message, if a known pattern matches
+pub fn suggest(test: &TestCase) -> Option<&'static str> {
+    if test.passed() {
+        return None;
+    }
+    let message = test.message().to_lowercase();
+    SUGGESTIONS
+        .iter()
+        .find(|(patterns, _)| patterns.iter().any(|p| message.contains(p)))
+        .map(|(_, suggestion)| *suggestion)
+}
+
+/// "[k/N]" with k padded to the width of N so the label doesn't jitter
+pub fn progress_label(completed: usize, total: usize) -> String {
+    let width = total.to_string().len();
+    format!("[{:>width$}/{}]", completed, total, width = width)
+}
+
+fn summary_pass_line(total: usize) -> String {
+    format!(
+        "{}{}  All {} tests passed!",
+        INDENT,
+        "PASSED".green().bold(),
+        total
+    )
+}
+
+fn summary_fail_line(failed: usize, total: usize) -> String {
+    format!(
+        "{}{}  {} of {} tests failed",
+        INDENT,
+        "FAILED".red().bold(),
+        failed,
+        total
+    )
+}
+
+fn summary_skipped_line(skipped: usize, total: usize) -> String {
+    format!(
+        "{}{}  {} of {} tests skipped, the rest passed",
+        INDENT,
+        "SKIPPED".yellow().bold(),
+        skipped,
+        total
+    )
+}
+
+/// decide whether to emit ANSI colors: --no-color and NO_COLOR win, otherwise only on a TTY
+pub fn should_color(no_color_flag: bool, no_color_env: bool, is_tty: bool) -> bool {
+    !no_color_flag && !no_color_env && is_tty
+}
+
+/// apply the color decision globally, call once at startup
+pub fn init_color(no_color_flag: bool) {
+    // per no-color.org, only a non-empty NO_COLOR disables color
+    let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let enabled = should_color(no_color_flag, no_color_env, std::io::stdout().is_terminal());
+    colored::control::set_override(enabled);
+}
+
+/// spinner frame for the `tick`th redraw, cycling through SPINNER_FRAMES
+pub fn spinner_frame(tick: usize) -> &'static str {
+    SPINNER_FRAMES[tick % SPINNER_FRAMES.len()]
+}
+
+/// pad `name` to `width` characters, or cut it to fit with a trailing ellipsis
+pub fn fit_name(name: &str, width: usize) -> String {
+    let len = name.chars().count();
+    if len <= width {
+        return format!("{}{}", name, " ".repeat(width - len));
+    }
+    let kept: String = name.chars().take(width.saturating_sub(1)).collect();
+    format!("{}…", kept)
+}
+
+/// format a duration as "820ms", "1.5s" or "1.24s"
+pub fn format_duration(d: Duration) -> String {
+    if d.as_millis() < 1000 {
+        format!("{}ms", d.as_millis())
+    } else {
+        format!("{}s", trim_decimals(&format!("{:.2}", d.as_secs_f64())))
+    }
+}
+
+/// format a byte count as "999 B", "1.5 KiB" or "2 MiB"
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!(
+        "{} {}",
+        trim_decimals(&format!("{:.1}", value)),
+        UNITS[unit]
+    )
+}
+
+/// "1.50" -> "1.5", "2.00" -> "2"
+fn trim_decimals(number: &str) -> &str {
+    if number.contains('.') {
+        number.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        number
+    }
+}
+
 /// General-purpose UI output functions (no instance needed)
 pub struct UI;
 
@@ -274,3 +715,224 @@ impl UI {
         println!("{}━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━", INDENT);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Mutex, MutexGuard};
+
+    /// colored's override is process-global; tests that set it hold this lock
+    static COLOR_OVERRIDE: Mutex<()> = Mutex::new(());
+
+    /// forces color on or off until dropped, then restores the default
+    struct ColorOverride {
+        _lock: MutexGuard<'static, ()>,
+    }
+
+    impl ColorOverride {
+        fn set(enabled: bool) -> Self {
+            let lock = COLOR_OVERRIDE
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            colored::control::set_override(enabled);
+            Self { _lock: lock }
+        }
+    }
+
+    impl Drop for ColorOverride {
+        fn drop(&mut self) {
+            colored::control::unset_override();
+        }
+    }
+
+    fn failed(message: &str) -> TestCase {
+        TestCase::new("check", Err(message.to_string()))
+    }
+
+    #[test]
+    fn test_suggest_connection_failed() {
+        let test = failed("connection failed: Connection refused (os error 111)");
+        assert_eq!(
+            suggest(&test),
+            Some("is your server running on the right port?")
+        );
+    }
+
+    #[test]
+    fn test_suggest_patterns() {
+        assert!(suggest(&failed("invalid JSON: expected value at line 1"))
+            .unwrap()
+            .contains("non-JSON"));
+        assert!(suggest(&failed("expected status 200, got 404"))
+            .unwrap()
+            .contains("route not registered"));
+        assert_eq!(suggest(&failed("expected body 'a', got 'b'")), None);
+    }
+
+    #[test]
+    fn test_suggest_ignores_passing_tests() {
+        let test = TestCase::new(
+            "check",
+            Ok("connection failed earlier but recovered".to_string()),
+        );
+        assert_eq!(suggest(&test), None);
+    }
+
+    #[test]
+    fn test_fit_name_aligns_and_truncates() {
+        assert_eq!(fit_name("GET /", 8), "GET /   ");
+        assert_eq!(fit_name("exactly8", 8), "exactly8");
+        assert_eq!(fit_name("GET /users returns 200", 8), "GET /us…");
+        assert_eq!(fit_name("ünïcødé names", 6), "ünïcø…");
+
+        // every line ends up the same width
+        for name in ["a", "server listening on port 4221", &"x".repeat(80)] {
+            assert_eq!(fit_name(name, NAME_WIDTH).chars().count(), NAME_WIDTH);
+        }
+    }
+
+    /// the lines a passing single-validator run prints
+    fn passing_run(ui: &RunUI) {
+        ui.header();
+        ui.blank_line();
+        ui.step("Running 1 validators...");
+        ui.test_pass("server listening on port 4221", Duration::from_millis(3));
+        ui.summary_pass(1);
+        ui.timing(Duration::from_millis(3), None);
+        ui.points_earned(50);
+    }
+
+    #[test]
+    fn test_quiet_run_prints_only_the_result() {
+        let _color = ColorOverride::set(false);
+
+        let loud = RunUI::capturing("bind-port", 1, false);
+        passing_run(&loud);
+        assert!(loud.captured().len() > 3);
+        assert!(loud
+            .captured()
+            .iter()
+            .any(|l| l.contains("projectlighthouse CLI")));
+
+        let quiet = RunUI::capturing("bind-port", 1, true);
+        passing_run(&quiet);
+        assert_eq!(quiet.captured(), vec![summary_pass_line(1)]);
+
+        quiet.test_fail(
+            "GET / returns 200",
+            Some("expected status 200, got 500"),
+            Duration::ZERO,
+        );
+        assert_eq!(quiet.captured().len(), 3);
+    }
+
+    #[test]
+    fn test_confirm_with_yes_skips_reading() {
+        let mut asked = false;
+        assert!(confirm_with(true, || {
+            asked = true;
+            None
+        }));
+        assert!(!asked);
+
+        assert!(confirm_with(false, || Some("Y\n".to_string())));
+        assert!(confirm_with(false, || Some("yes\n".to_string())));
+        assert!(!confirm_with(false, || Some("\n".to_string())));
+        assert!(!confirm_with(false, || None));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::ZERO), "0ms");
+        assert_eq!(format_duration(Duration::from_millis(999)), "999ms");
+        assert_eq!(format_duration(Duration::from_millis(1000)), "1s");
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1.5s");
+        assert_eq!(format_duration(Duration::from_millis(1240)), "1.24s");
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(999), "999 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1024), "1 KiB");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5 MiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3 GiB");
+    }
+
+    #[test]
+    fn test_spinner_frame_sequence() {
+        let frames: Vec<&str> = (0..12).map(spinner_frame).collect();
+        assert_eq!(&frames[..10], &SPINNER_FRAMES[..]);
+        assert_eq!(frames[10], "⠋");
+        assert_eq!(frames[11], "⠙");
+        // consecutive frames always differ, so the spinner visibly moves
+        assert!(frames.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+
+    #[tokio::test]
+    async fn test_spin_while_returns_output() {
+        let ui = RunUI::new("task", 1);
+        let output = ui
+            .spin_while(0, "slow", async {
+                tokio::time::sleep(Duration::from_millis(250)).await;
+                42
+            })
+            .await;
+        assert_eq!(output, 42);
+    }
+
+    #[test]
+    fn test_progress_label() {
+        assert_eq!(progress_label(0, 3), "[0/3]");
+        assert_eq!(progress_label(3, 3), "[3/3]");
+        assert_eq!(progress_label(7, 12), "[ 7/12]");
+        assert_eq!(progress_label(12, 12), "[12/12]");
+    }
+
+    #[test]
+    fn test_should_color() {
+        assert!(should_color(false, false, true));
+        assert!(!should_color(true, false, true));
+        assert!(!should_color(false, true, true));
+        assert!(!should_color(false, false, false));
+    }
+
+    #[test]
+    fn test_no_ansi_when_color_disabled() {
+        let color = ColorOverride::set(false);
+        let pass = summary_pass_line(3);
+        let fail = summary_fail_line(2, 3);
+        let skipped = summary_skipped_line(1, 3);
+        drop(color);
+
+        assert_eq!(pass, "  PASSED  All 3 tests passed!");
+        assert_eq!(fail, "  FAILED  2 of 3 tests failed");
+        assert_eq!(skipped, "  SKIPPED  1 of 3 tests skipped, the rest passed");
+        assert!(!pass.contains('\x1b'));
+        assert!(!fail.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_diff_lines_marks_changed_lines() {
+        let expected = "{\n  \"status\": \"ok\",\n  \"count\": 2\n}";
+        let actual = "{\n  \"status\": \"error\",\n  \"count\": 2\n}";
+        assert_eq!(
+            diff_lines(expected, actual),
+            vec![
+                " {",
+                "-  \"status\": \"ok\",",
+                "+  \"status\": \"error\",",
+                "   \"count\": 2",
+                " }",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_extra_and_missing_lines() {
+        assert_eq!(diff_lines("a\nb", "a"), vec![" a", "-b"]);
+        assert_eq!(diff_lines("a", "a\nc"), vec![" a", "+c"]);
+    }
+}
@@ -0,0 +1,232 @@
+use crate::shell::{run_command_with, CommandOptions, CommandResult};
+use crate::tasks::{TestCase, ValidationContext};
+use std::time::Duration;
+
+/// max output lines echoed when a command check fails
+const MAX_OUTPUT_LINES: usize = 15;
+
+/// why a command validator didn't run without the user's opt-in
+const NOT_ALLOWED: &str = "runs a shell command; re-run with --allow-commands \
+     or set allow_commands = true in config.toml";
+
+/// Validator: run a command in the workspace and check its exit code and/or output
+pub struct CommandExitValidator {
+    pub command: String,
+    pub expected_exit_code: Option<i32>,
+    pub output_contains: Option<String>,
+    pub timeout: Option<Duration>,
+}
+
+impl CommandExitValidator {
+    /// passes when `command` exits with `expected_exit_code`
+    pub fn new(command: &str, expected_exit_code: i32) -> Self {
+        Self {
+            command: command.to_string(),
+            expected_exit_code: Some(expected_exit_code),
+            output_contains: None,
+            timeout: None,
+        }
+    }
+
+    /// passes when stdout or stderr contains `pattern`, whatever the exit code
+    pub fn output_containing(command: &str, pattern: &str) -> Self {
+        Self {
+            command: command.to_string(),
+            expected_exit_code: None,
+            output_contains: Some(pattern.to_string()),
+            timeout: None,
+        }
+    }
+
+    pub fn with_expected_exit_code(mut self, code: i32) -> Self {
+        self.expected_exit_code = Some(code);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub async fn validate(&self, ctx: &ValidationContext) -> Result<TestCase, String> {
+        let name = format!("`{}`", self.command);
+        // task definitions come from the server, so arbitrary shell needs an explicit opt-in
+        if !ctx.allow_commands {
+            log::debug!(
+                "skipping command validator without opt-in: {}",
+                self.command
+            );
+            return Ok(TestCase::skip(&name, NOT_ALLOWED));
+        }
+
+        let mut opts = CommandOptions::default().with_cwd(ctx.workspace());
+        if let Some(timeout) = self.timeout {
+            opts = opts.with_timeout(timeout);
+        }
+
+        let result = match run_command_with(&self.command, &opts).await {
+            Ok(output) => self.check(&output),
+            Err(e) => Err(e),
+        };
+
+        Ok(TestCase::new(name, result))
+    }
+
+    fn check(&self, output: &CommandResult) -> Result<String, String> {
+        if output.timed_out {
+            return Err(format!("`{}` timed out", self.command));
+        }
+
+        if let Some(expected) = self.expected_exit_code {
+            if output.exit_code != expected {
+                return Err(format!(
+                    "`{}` exited with code {}, expected {}{}",
+                    self.command,
+                    output.exit_code,
+                    expected,
+                    output_excerpt(output)
+                ));
+            }
+        }
+
+        if let Some(ref pattern) = self.output_contains {
+            if !output.stdout.contains(pattern.as_str())
+                && !output.stderr.contains(pattern.as_str())
+            {
+                return Err(format!(
+                    "`{}` output does not contain '{}'{}",
+                    self.command,
+                    pattern,
+                    output_excerpt(output)
+                ));
+            }
+            return Ok(format!("`{}` printed '{}'", self.command, pattern));
+        }
+
+        Ok(format!(
+            "`{}` exited with code {}",
+            self.command, output.exit_code
+        ))
+    }
+}
+
+/// the tail of stderr (or stdout when stderr is empty), for failure messages
+fn output_excerpt(output: &CommandResult) -> String {
+    let text = if output.stderr.trim().is_empty() {
+        &output.stdout
+    } else {
+        &output.stderr
+    };
+    let lines: Vec<&str> = text.trim_end().lines().collect();
+    if lines.is_empty() {
+        return String::new();
+    }
+    let start = lines.len().saturating_sub(MAX_OUTPUT_LINES);
+    format!(":\n{}", lines[start..].join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx_in(dir: &tempfile::TempDir) -> ValidationContext {
+        ValidationContext {
+            project_path: Some(dir.path().to_path_buf()),
+            allow_commands: true,
+            ..ValidationContext::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_command_skipped_without_opt_in() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("marker.txt"), "here").unwrap();
+        let ctx = ValidationContext {
+            allow_commands: false,
+            ..ctx_in(&dir)
+        };
+
+        let validator = CommandExitValidator::new("rm marker.txt", 0);
+        let result = validator.validate(&ctx).await.unwrap();
+        assert!(result.is_skipped());
+        assert!(result.message().contains("--allow-commands"));
+        assert!(dir.path().join("marker.txt").exists());
+
+        // opted in, the same command runs
+        let result = validator.validate(&ctx_in(&dir)).await.unwrap();
+        assert!(result.passed());
+        assert!(!result.is_skipped());
+        assert!(!dir.path().join("marker.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_command_exit_code_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = ctx_in(&dir);
+
+        let passing = CommandExitValidator::new("exit 0", 0);
+        assert!(passing.validate(&ctx).await.unwrap().passed());
+
+        let expected_failure = CommandExitValidator::new("exit 3", 3);
+        assert!(expected_failure.validate(&ctx).await.unwrap().passed());
+    }
+
+    #[tokio::test]
+    async fn test_command_exit_code_mismatch_shows_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let validator = CommandExitValidator::new("echo '2 tests failed' >&2; exit 1", 0);
+
+        let result = validator.validate(&ctx_in(&dir)).await.unwrap();
+        assert!(!result.passed());
+        assert!(result.message().contains("exited with code 1, expected 0"));
+        assert!(result.message().contains("2 tests failed"));
+    }
+
+    #[tokio::test]
+    async fn test_command_runs_in_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("marker.txt"), "here").unwrap();
+
+        let validator = CommandExitValidator::new("test -f marker.txt", 0);
+        assert!(validator.validate(&ctx_in(&dir)).await.unwrap().passed());
+    }
+
+    #[tokio::test]
+    async fn test_command_output_contains() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = ctx_in(&dir);
+
+        let stdout = CommandExitValidator::output_containing("echo 'all PASS'", "PASS");
+        assert!(stdout.validate(&ctx).await.unwrap().passed());
+
+        let stderr = CommandExitValidator::output_containing("echo PASS >&2; exit 1", "PASS");
+        assert!(stderr.validate(&ctx).await.unwrap().passed());
+
+        let missing = CommandExitValidator::output_containing("echo FAIL", "PASS");
+        let result = missing.validate(&ctx).await.unwrap();
+        assert!(!result.passed());
+        assert!(result.message().contains("does not contain 'PASS'"));
+    }
+
+    #[tokio::test]
+    async fn test_command_output_with_exit_code() {
+        let dir = tempfile::tempdir().unwrap();
+        let validator = CommandExitValidator::output_containing("echo PASS; exit 1", "PASS")
+            .with_expected_exit_code(0);
+
+        let result = validator.validate(&ctx_in(&dir)).await.unwrap();
+        assert!(!result.passed());
+        assert!(result.message().contains("exited with code 1"));
+    }
+
+    #[tokio::test]
+    async fn test_command_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let validator =
+            CommandExitValidator::new("sleep 5", 0).with_timeout(Duration::from_millis(100));
+
+        let result = validator.validate(&ctx_in(&dir)).await.unwrap();
+        assert!(!result.passed());
+        assert!(result.message().contains("timed out"));
+    }
+}
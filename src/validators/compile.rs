@@ -1,29 +1,72 @@
-use crate::config::Config;
 use crate::runtime::SupportedRuntime;
-use crate::state::LabState;
-use crate::tasks::TestCase;
-use std::path::{Path, PathBuf};
+use crate::tasks::{TestCase, ValidationContext};
+use std::path::Path;
 use std::process::Stdio;
 use tokio::process::Command;
 
+/// max compiler output lines shown when compilation fails
+const MAX_ERROR_LINES: usize = 15;
+
+/// languages can_compile knows how to build
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompileLanguage {
+    Go,
+    Rust,
+    C,
+}
+
+impl CompileLanguage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompileLanguage::Go => "go",
+            CompileLanguage::Rust => "rust",
+            CompileLanguage::C => "c",
+        }
+    }
+}
+
+impl std::str::FromStr for CompileLanguage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "go" | "golang" => Ok(CompileLanguage::Go),
+            "rust" | "rs" => Ok(CompileLanguage::Rust),
+            "c" => Ok(CompileLanguage::C),
+            _ => Err(format!(
+                "unsupported language '{}'. supported: go, rust, c",
+                s
+            )),
+        }
+    }
+}
+
 /// Validator: check if project compiles successfully
 pub struct CanCompileValidator {
     pub expected_success: bool,
+    pub language: Option<CompileLanguage>,
 }
 
 impl CanCompileValidator {
     pub fn new(expected_success: bool) -> Self {
-        Self { expected_success }
+        Self {
+            expected_success,
+            language: None,
+        }
     }
 
-    pub async fn validate(&self) -> Result<TestCase, String> {
-        // get workspace and runtime from lab state
-        let (workspace, runtime) = get_lab_context();
-        let workspace_path = workspace
-            .map(PathBuf::from)
-            .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    /// build with a specific toolchain instead of the lab runtime
+    pub fn with_language(mut self, language: CompileLanguage) -> Self {
+        self.language = Some(language);
+        self
+    }
 
-        let (cmd, args) = detect_build_command(runtime.as_deref(), &workspace_path)?;
+    pub async fn validate(&self, ctx: &ValidationContext) -> Result<TestCase, String> {
+        let workspace_path = ctx.workspace();
+        let (cmd, args) = match self.language {
+            Some(language) => build_command_for_language(language, &workspace_path)?,
+            None => detect_build_command(ctx.runtime.as_deref(), &workspace_path)?,
+        };
 
         let output = Command::new(&cmd)
             .args(&args)
@@ -35,21 +78,36 @@ impl CanCompileValidator {
             .map_err(|e| format!("failed to run '{}': {}", cmd, e))?;
 
         let compiled_ok = output.status.success();
-        let stderr = String::from_utf8_lossy(&output.stderr);
 
         let result = match (compiled_ok, self.expected_success) {
             (true, true) => Ok(format!("{} {} succeeded", cmd, args.join(" "))),
             (false, false) => Ok("compilation failed as expected".to_string()),
             (true, false) => Err("expected compilation to fail, but it succeeded".to_string()),
             (false, true) => {
-                let err_preview = stderr.lines().take(5).collect::<Vec<_>>().join("\n");
-                Err(format!("compilation failed:\n{}", err_preview))
+                // some toolchains report errors on stdout
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                let compiler_output = if stderr.trim().is_empty() {
+                    stdout
+                } else {
+                    stderr
+                };
+                Err(format!(
+                    "compilation failed:\n{}",
+                    error_excerpt(&compiler_output, MAX_ERROR_LINES)
+                ))
             }
         };
 
-        Ok(TestCase {
-            name: format!(
-                "lab compiles{}",
+        let language = self
+            .language
+            .map(|l| format!(" ({})", l.as_str()))
+            .unwrap_or_default();
+
+        Ok(TestCase::new(
+            format!(
+                "lab compiles{}{}",
+                language,
                 if self.expected_success {
                     ""
                 } else {
@@ -57,26 +115,55 @@ impl CanCompileValidator {
                 }
             ),
             result,
-        })
+        ))
+    }
+}
+
+/// first `max_lines` lines starting at the first error, or the head of the output
+fn error_excerpt(output: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = output.lines().collect();
+    let start = lines
+        .iter()
+        .position(|l| l.to_lowercase().contains("error"))
+        .unwrap_or(0);
+    let mut excerpt = lines[start..]
+        .iter()
+        .take(max_lines)
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+    let remaining = lines.len() - start;
+    if remaining > max_lines {
+        excerpt.push_str(&format!("\n... ({} more lines)", remaining - max_lines));
     }
+    excerpt
 }
 
-/// get workspace and runtime from active lab state
-fn get_lab_context() -> (Option<String>, Option<String>) {
-    let config = match Config::load() {
-        Ok(c) => c,
-        Err(_) => return (None, None),
-    };
-    if !config.has_auth_token() {
-        return (None, None);
-    }
-    let state = match LabState::load(config.expose_token()) {
-        Ok(s) => s,
-        Err(_) => return (None, None),
-    };
-    match state.get_active() {
-        Some(lab) => (Some(lab.workspace.clone()), lab.runtime.clone()),
-        None => (None, None),
+/// build command for an explicitly selected language
+fn build_command_for_language(
+    language: CompileLanguage,
+    workspace: &Path,
+) -> Result<(String, Vec<String>), String> {
+    match language {
+        CompileLanguage::Go => get_build_command_for_runtime(SupportedRuntime::Go, workspace),
+        CompileLanguage::Rust => get_build_command_for_runtime(SupportedRuntime::Rust, workspace),
+        CompileLanguage::C => {
+            let mut sources: Vec<String> = std::fs::read_dir(workspace)
+                .map_err(|e| format!("failed to read '{}': {}", workspace.display(), e))?
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().map(|x| x == "c").unwrap_or(false))
+                .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+                .collect();
+            if sources.is_empty() {
+                return Err("no .c source files found in project directory".to_string());
+            }
+            sources.sort();
+
+            let mut args = vec!["-Wall".to_string(), "-fsyntax-only".to_string()];
+            args.extend(sources);
+            Ok(("cc".to_string(), args))
+        }
     }
 }
 
@@ -156,4 +243,60 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("unsupported runtime"));
     }
+
+    #[test]
+    fn test_language_from_str() {
+        assert_eq!(
+            "Rust".parse::<CompileLanguage>().unwrap(),
+            CompileLanguage::Rust
+        );
+        assert_eq!(
+            "go".parse::<CompileLanguage>().unwrap(),
+            CompileLanguage::Go
+        );
+        assert_eq!("c".parse::<CompileLanguage>().unwrap(), CompileLanguage::C);
+    }
+
+    #[test]
+    fn test_language_invalid() {
+        let result = "cobol".parse::<CompileLanguage>();
+        assert!(result.unwrap_err().contains("unsupported language 'cobol'"));
+    }
+
+    #[test]
+    fn test_build_command_for_language_rust() {
+        let (cmd, args) =
+            build_command_for_language(CompileLanguage::Rust, &PathBuf::from("/tmp")).unwrap();
+        assert_eq!(cmd, "cargo");
+        assert_eq!(args, vec!["check"]);
+    }
+
+    #[test]
+    fn test_build_command_for_language_c() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("main.c"), "int main() { return 0; }").unwrap();
+        std::fs::write(dir.path().join("util.c"), "").unwrap();
+        std::fs::write(dir.path().join("README.md"), "").unwrap();
+
+        let (cmd, args) = build_command_for_language(CompileLanguage::C, dir.path()).unwrap();
+        assert_eq!(cmd, "cc");
+        assert_eq!(args, vec!["-Wall", "-fsyntax-only", "main.c", "util.c"]);
+    }
+
+    #[test]
+    fn test_build_command_for_language_c_without_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = build_command_for_language(CompileLanguage::C, dir.path());
+        assert!(result.unwrap_err().contains("no .c source files"));
+    }
+
+    #[test]
+    fn test_error_excerpt_starts_at_first_error() {
+        let output = "   Compiling app v0.1.0\nerror[E0425]: cannot find value `x`\n --> src/main.rs:2:5\n  |\nerror: aborting";
+        let excerpt = error_excerpt(output, 2);
+        assert_eq!(
+            excerpt,
+            "error[E0425]: cannot find value `x`\n --> src/main.rs:2:5\n... (2 more lines)"
+        );
+    }
 }
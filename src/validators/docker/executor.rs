@@ -2,12 +2,16 @@
 //!
 //! for security, only images registered in the registry module can be executed.
 
+use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use tokio::process::Command;
+use tokio::sync::OnceCell;
 use tokio::time::{timeout, Duration};
 
-use super::registry::{self, ImageSource};
+use super::registry::{self, ImageSource, RegisteredImage};
+use crate::api::shared_client;
+use crate::config::lux_home;
 
 const DOCKERFILE_BASE_URL: &str =
     "https://raw.githubusercontent.com/thearyanahmed/luxctl/master/docker";
@@ -34,10 +38,7 @@ pub struct DockerExecutor {
 
 impl DockerExecutor {
     pub fn new() -> Result<Self, String> {
-        let cache_dir = dirs::home_dir()
-            .ok_or("could not determine home directory")?
-            .join(".luxctl")
-            .join("docker_cache");
+        let cache_dir = lux_home().map_err(|e| e.to_string())?.join("docker_cache");
 
         std::fs::create_dir_all(&cache_dir)
             .map_err(|e| format!("failed to create cache dir: {}", e))?;
@@ -51,7 +52,9 @@ impl DockerExecutor {
         let cache_path = self.cache_dir.join(name);
 
         // fetch from GitHub
-        let response = reqwest::get(&url)
+        let response = shared_client()
+            .get(&url)
+            .send()
             .await
             .map_err(|e| format!("failed to fetch Dockerfile '{}': {}", name, e))?;
 
@@ -76,12 +79,13 @@ impl DockerExecutor {
     }
 
     /// build and run a container from a registered image
-    /// rejects unregistered images for security
+    /// rejects unregistered images for security; `docker_check` is the run's cached docker check
     pub async fn run(
         &self,
         image_key: &str,
         workspace: &str,
         timeout_secs: Option<u64>,
+        docker_check: &OnceCell<bool>,
     ) -> Result<ExecutorResult, String> {
         // security check: only allow registered images
         let registered = registry::lookup(image_key).ok_or_else(|| {
@@ -93,7 +97,7 @@ impl DockerExecutor {
         })?;
 
         // check docker availability
-        if !is_docker_available().await {
+        if !is_docker_available(docker_check).await {
             return Err("docker not available".to_string());
         }
 
@@ -103,10 +107,10 @@ impl DockerExecutor {
                 // download from GitHub (local means bundled in luxctl repo)
                 self.download_dockerfile(path).await?
             }
-            ImageSource::Remote(image_url) => {
+            ImageSource::Remote(_) => {
                 // for remote images, pull and run directly
                 return self
-                    .run_remote_image(image_url, workspace, timeout_secs)
+                    .run_remote_image(registered, workspace, timeout_secs)
                     .await;
             }
         };
@@ -157,7 +161,7 @@ impl DockerExecutor {
     /// run a pre-built remote image (pulled from registry)
     async fn run_remote_image(
         &self,
-        image_url: &str,
+        image: &RegisteredImage,
         workspace: &str,
         timeout_secs: Option<u64>,
     ) -> Result<ExecutorResult, String> {
@@ -166,11 +170,12 @@ impl DockerExecutor {
             .map_err(|e| format!("cannot resolve workspace '{}': {}", workspace, e))?;
 
         let workspace_str = workspace_path.to_string_lossy();
+        let image_url = image.pull_reference();
 
         // pull the image
         eprintln!("  pulling {} ...", image_url);
         let pull_result = Command::new("docker")
-            .args(["pull", image_url])
+            .args(pull_args(image))
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .output()
@@ -185,10 +190,42 @@ impl DockerExecutor {
             });
         }
 
+        if let Some(digest) = image.digest {
+            self.verify_digest(&image_url, digest).await?;
+        }
+
         // run the container
         eprintln!("  running validation...");
-        self.docker_run(image_url, &workspace_str, timeout_secs)
+        self.docker_run(&image_url, &workspace_str, timeout_secs)
+            .await
+    }
+
+    /// refuse to run a pulled image unless it resolved to the registered digest
+    async fn verify_digest(&self, image_url: &str, digest: &str) -> Result<(), String> {
+        let output = Command::new("docker")
+            .args([
+                "image",
+                "inspect",
+                "--format",
+                "{{range .RepoDigests}}{{println .}}{{end}}",
+                image_url,
+            ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
             .await
+            .map_err(|e| format!("failed to inspect image: {}", e))?;
+
+        let repo_digests = String::from_utf8_lossy(&output.stdout);
+        if !output.status.success() || !digest_matches(&repo_digests, digest) {
+            return Err(format!(
+                "image digest mismatch for {}: expected {}, got '{}'",
+                image_url,
+                digest,
+                repo_digests.trim()
+            ));
+        }
+        Ok(())
     }
 
     async fn docker_build(
@@ -273,8 +310,34 @@ fn sanitize_for_docker_tag(s: &str) -> String {
         .collect()
 }
 
-/// check if docker is available
-pub async fn is_docker_available() -> bool {
+/// arguments for `docker pull`, pinned to the registered digest when there is one
+fn pull_args(image: &RegisteredImage) -> Vec<String> {
+    vec!["pull".to_string(), image.pull_reference()]
+}
+
+/// `docker image inspect` prints one "repo@sha256:..." per line
+fn digest_matches(repo_digests: &str, digest: &str) -> bool {
+    repo_digests
+        .lines()
+        .filter_map(|line| line.trim().rsplit_once('@'))
+        .any(|(_, d)| d == digest)
+}
+
+/// check if docker is available; `docker version` is slow, so the first answer
+/// is kept in `cache` and reused by the rest of the run
+pub async fn is_docker_available(cache: &OnceCell<bool>) -> bool {
+    cached_check(cache, probe_docker).await
+}
+
+async fn cached_check<F, Fut>(cell: &OnceCell<bool>, check: F) -> bool
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    *cell.get_or_init(check).await
+}
+
+async fn probe_docker() -> bool {
     Command::new("docker")
         .args(["version", "--format", "{{.Server.Version}}"])
         .stdout(Stdio::null())
@@ -309,16 +372,57 @@ mod tests {
         assert!(!result.success());
     }
 
+    #[test]
+    fn test_pull_args_use_digest() {
+        let image = RegisteredImage {
+            key: "pinned",
+            description: "pinned test image",
+            source: ImageSource::Remote("ghcr.io/org/repo"),
+            digest: Some("sha256:0123abcd"),
+        };
+        assert_eq!(
+            pull_args(&image),
+            vec!["pull", "ghcr.io/org/repo@sha256:0123abcd"]
+        );
+    }
+
+    #[test]
+    fn test_digest_matches() {
+        let inspected = "ghcr.io/org/repo@sha256:0123abcd\nmirror.io/repo@sha256:ffff\n";
+        assert!(digest_matches(inspected, "sha256:0123abcd"));
+        assert!(!digest_matches(inspected, "sha256:9999"));
+        assert!(!digest_matches("", "sha256:0123abcd"));
+    }
+
+    #[tokio::test]
+    async fn test_cached_check_runs_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let cell = OnceCell::new();
+        let calls = AtomicUsize::new(0);
+        for _ in 0..3 {
+            let available = cached_check(&cell, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                true
+            })
+            .await;
+            assert!(available);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
     #[tokio::test]
     async fn test_is_docker_available_returns_bool() {
         // just verify it doesn't panic
-        let _ = is_docker_available().await;
+        let _ = is_docker_available(&OnceCell::new()).await;
     }
 
     #[tokio::test]
     async fn test_run_rejects_unregistered_image() {
         let executor = DockerExecutor::new().unwrap();
-        let result = executor.run("malicious-image", ".", None).await;
+        let result = executor
+            .run("malicious-image", ".", None, &OnceCell::new())
+            .await;
 
         assert!(result.is_err());
         let err = result.unwrap_err();
@@ -330,7 +434,9 @@ mod tests {
     async fn test_run_rejects_arbitrary_url() {
         let executor = DockerExecutor::new().unwrap();
         // even if it looks like a valid image URL, it must be registered
-        let result = executor.run("ghcr.io/evil/malware:latest", ".", None).await;
+        let result = executor
+            .run("ghcr.io/evil/malware:latest", ".", None, &OnceCell::new())
+            .await;
 
         assert!(result.is_err());
         let err = result.unwrap_err();
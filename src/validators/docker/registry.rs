@@ -1,9 +1,18 @@
 //! Docker image registry - hardcoded for security
 //!
-//! all allowed Docker images must be defined here at compile time.
+//! all allowed Docker images must be defined here at compile time, or be
+//! explicitly approved by the user in ~/.luxctl/docker_allowlist.toml.
 //! this prevents arbitrary images from being executed on user machines.
 
+use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::config::lux_home;
+
+static ALLOWLIST_FILE: &str = "docker_allowlist.toml";
 
 /// represents a source for a Docker image
 #[derive(Debug, Clone, Copy)]
@@ -33,6 +42,18 @@ pub struct RegisteredImage {
     pub key: &'static str,
     pub description: &'static str,
     pub source: ImageSource,
+    /// content digest ("sha256:...") a remote image must resolve to; tags are mutable
+    pub digest: Option<&'static str>,
+}
+
+impl RegisteredImage {
+    /// reference to pull: the remote url, pinned with `@digest` when one is registered
+    pub fn pull_reference(&self) -> String {
+        match self.digest {
+            Some(digest) => format!("{}@{}", self.source.path(), digest),
+            None => self.source.path().to_string(),
+        }
+    }
 }
 
 impl fmt::Display for RegisteredImage {
@@ -47,24 +68,89 @@ const REGISTERED_IMAGES: &[RegisteredImage] = &[
         key: "go1.22",
         description: "Go 1.22 build and test environment",
         source: ImageSource::Local("docker/Go1.22"),
+        digest: None,
     },
     RegisteredImage {
         key: "go1.22-race",
         description: "Go 1.22 with race detector enabled",
         source: ImageSource::Local("docker/Go1.22-race"),
+        digest: None,
     },
     RegisteredImage {
         key: "api-client-test",
         description: "Salvo.rs test server for API client validation",
         source: ImageSource::Remote("ghcr.io/projectlighthouse/api-client-test:latest"),
+        digest: None,
     },
 ];
 
-/// lookup a registered image by key
-/// returns None if the image is not in the registry (security measure)
-pub fn lookup(key: &str) -> Option<&'static RegisteredImage> {
+/// user-approved remote images, e.g.
+///
+/// ```toml
+/// [images]
+/// my-lab = "ghcr.io/me/my-lab:1.0"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+struct Allowlist {
+    #[serde(default)]
+    images: BTreeMap<String, String>,
+}
+
+/// parse an allowlist into registry entries. they live for the whole run, so the
+/// strings are leaked once rather than threading lifetimes through every caller
+fn parse_allowlist(content: &str) -> Result<&'static [RegisteredImage], String> {
+    let allowlist: Allowlist =
+        toml::from_str(content).map_err(|e| format!("invalid {}: {}", ALLOWLIST_FILE, e))?;
+
+    let images: Vec<RegisteredImage> = allowlist
+        .images
+        .into_iter()
+        .map(|(key, source)| RegisteredImage {
+            key: Box::leak(key.to_lowercase().into_boxed_str()),
+            description: "user allowlisted image",
+            source: ImageSource::Remote(Box::leak(source.into_boxed_str())),
+            digest: None,
+        })
+        .collect();
+    Ok(Box::leak(images.into_boxed_slice()))
+}
+
+fn load_allowlist(path: &Path) -> &'static [RegisteredImage] {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return &[];
+    };
+    parse_allowlist(&content).unwrap_or_else(|e| {
+        log::warn!("ignoring docker allowlist: {}", e);
+        &[]
+    })
+}
+
+fn allowlist_path() -> Option<PathBuf> {
+    lux_home().ok().map(|home| home.join(ALLOWLIST_FILE))
+}
+
+/// allowlisted images, read once per run
+fn allowlisted() -> &'static [RegisteredImage] {
+    static ALLOWLISTED: OnceLock<&'static [RegisteredImage]> = OnceLock::new();
+    ALLOWLISTED.get_or_init(|| allowlist_path().map(|p| load_allowlist(&p)).unwrap_or(&[]))
+}
+
+/// built-ins win over an allowlist entry with the same key
+fn lookup_in(
+    allowlisted: &'static [RegisteredImage],
+    key: &str,
+) -> Option<&'static RegisteredImage> {
     let key_lower = key.to_lowercase();
-    REGISTERED_IMAGES.iter().find(|img| img.key == key_lower)
+    REGISTERED_IMAGES
+        .iter()
+        .chain(allowlisted)
+        .find(|img| img.key == key_lower)
+}
+
+/// lookup a registered or allowlisted image by key
+/// returns None if the image is in neither (security measure)
+pub fn lookup(key: &str) -> Option<&'static RegisteredImage> {
+    lookup_in(allowlisted(), key)
 }
 
 /// check if an image key is registered
@@ -72,9 +158,13 @@ pub fn is_registered(key: &str) -> bool {
     lookup(key).is_some()
 }
 
-/// list all registered image keys
+/// list all registered and allowlisted image keys
 pub fn list_keys() -> Vec<&'static str> {
-    REGISTERED_IMAGES.iter().map(|img| img.key).collect()
+    REGISTERED_IMAGES
+        .iter()
+        .chain(allowlisted())
+        .map(|img| img.key)
+        .collect()
 }
 
 #[cfg(test)]
@@ -108,6 +198,56 @@ mod tests {
         assert!(!is_registered("unknown"));
     }
 
+    #[test]
+    fn test_pull_reference_pins_digest() {
+        let pinned = RegisteredImage {
+            key: "pinned",
+            description: "pinned test image",
+            source: ImageSource::Remote("ghcr.io/org/repo:latest"),
+            digest: Some("sha256:abc123"),
+        };
+        assert_eq!(
+            pinned.pull_reference(),
+            "ghcr.io/org/repo:latest@sha256:abc123"
+        );
+
+        let img = lookup("api-client-test").unwrap();
+        assert_eq!(
+            img.pull_reference(),
+            "ghcr.io/projectlighthouse/api-client-test:latest"
+        );
+    }
+
+    #[test]
+    fn test_allowlisted_key_resolves() {
+        let allowlisted =
+            parse_allowlist("[images]\nMy-Lab = \"ghcr.io/me/my-lab:1.0\"\n").unwrap();
+
+        let img = lookup_in(allowlisted, "my-lab").unwrap();
+        assert!(img.source.is_remote());
+        assert_eq!(img.source.path(), "ghcr.io/me/my-lab:1.0");
+        assert!(lookup_in(allowlisted, "go1.22").is_some());
+    }
+
+    #[test]
+    fn test_absent_key_still_rejected() {
+        let allowlisted =
+            parse_allowlist("[images]\nmy-lab = \"ghcr.io/me/my-lab:1.0\"\n").unwrap();
+        assert!(lookup_in(allowlisted, "malicious-image").is_none());
+        assert!(lookup_in(&[], "my-lab").is_none());
+    }
+
+    #[test]
+    fn test_load_allowlist_missing_or_invalid() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(ALLOWLIST_FILE);
+        assert!(load_allowlist(&path).is_empty());
+
+        std::fs::write(&path, "[images\n").unwrap();
+        assert!(load_allowlist(&path).is_empty());
+        assert!(parse_allowlist("[images]\nbad = 1\n").is_err());
+    }
+
     #[test]
     fn test_list_keys() {
         let keys = list_keys();
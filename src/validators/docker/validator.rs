@@ -1,11 +1,8 @@
 //! Docker validator - runs Dockerfiles and interprets results based on DSL
 
-use crate::config::Config;
-use crate::state::LabState;
-use crate::tasks::TestCase;
+use crate::tasks::{TestCase, ValidationContext};
 
-use super::executor::DockerExecutor;
-use std::path::PathBuf;
+use super::executor::{is_docker_available, DockerExecutor};
 
 /// expectation for validating container output
 #[derive(Debug, Clone)]
@@ -101,16 +98,28 @@ impl DockerValidator {
         self
     }
 
-    pub async fn validate(&self) -> Result<TestCase, String> {
+    pub async fn validate(&self, ctx: &ValidationContext) -> Result<TestCase, String> {
+        // missing infrastructure isn't a wrong answer
+        if !is_docker_available(&ctx.docker_available).await {
+            return Ok(TestCase::skip(
+                &format!("docker:{}", self.dockerfile_name),
+                "docker not available",
+            ));
+        }
+
         let executor = DockerExecutor::new()?;
 
-        // get workspace from project state
-        let workspace = get_workspace();
+        let workspace = ctx.workspace();
         let workspace_str = workspace.to_string_lossy();
 
         // run the container
         let result = executor
-            .run(&self.dockerfile_name, &workspace_str, self.timeout_secs)
+            .run(
+                &self.dockerfile_name,
+                &workspace_str,
+                self.timeout_secs,
+                &ctx.docker_available,
+            )
             .await?;
 
         // interpret result based on expectation
@@ -161,32 +170,10 @@ impl DockerValidator {
             }
         };
 
-        Ok(TestCase {
-            name: format!("docker:{}", self.dockerfile_name),
-            result: test_result,
-        })
-    }
-}
-
-/// get workspace path from lab state
-fn get_workspace() -> PathBuf {
-    let config = match Config::load() {
-        Ok(c) => c,
-        Err(_) => return PathBuf::from("."),
-    };
-
-    if !config.has_auth_token() {
-        return PathBuf::from(".");
-    }
-
-    let state = match LabState::load(config.expose_token()) {
-        Ok(s) => s,
-        Err(_) => return PathBuf::from("."),
-    };
-
-    match state.get_active() {
-        Some(lab) => PathBuf::from(&lab.workspace),
-        None => PathBuf::from("."),
+        Ok(TestCase::new(
+            format!("docker:{}", self.dockerfile_name),
+            test_result,
+        ))
     }
 }
 
@@ -220,6 +207,38 @@ fn extract_context(s: &str, pattern: &str, context_chars: usize) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_docker_check_is_cached_per_context() {
+        let ctx = ValidationContext::default();
+        ctx.docker_available.set(false).unwrap();
+        // clones within a run share the answer
+        assert_eq!(ctx.clone().docker_available.get(), Some(&false));
+        // the next run gets a fresh context and checks again
+        assert!(ValidationContext::default()
+            .docker_available
+            .get()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_race_detector_uses_shared_docker_check() {
+        use crate::validators::{create_validator, docker};
+
+        // the run's answer, as the re-exported check caches it
+        let ctx = ValidationContext::default();
+        ctx.docker_available.set(false).unwrap();
+        assert!(!docker::is_docker_available(&ctx.docker_available).await);
+
+        // the race-detector validator acts on that answer instead of probing docker itself
+        let validator = create_validator(
+            "docker:string(Go1.22-race),string(fail_if:stderr contains DATA RACE)",
+        )
+        .unwrap();
+        let result = validator.validate(&ctx).await.unwrap();
+        assert!(result.is_skipped());
+        assert_eq!(result.message(), "docker not available");
+    }
+
     #[test]
     fn test_parse_exit_code() {
         let exp = Expectation::parse("exit:0").unwrap();
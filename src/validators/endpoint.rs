@@ -1,6 +1,8 @@
+use super::http::{http_request, DEFAULT_PORT};
 use crate::tasks::TestCase;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
+use tokio::task::JoinSet;
 
 pub struct EndpointValidator {
     endpoint: String,
@@ -51,9 +53,139 @@ impl EndpointValidator {
                 ))
             };
 
-        Ok(TestCase {
-            name: format!("endpoint {} returns 200 ok", self.endpoint),
-            result: test_result,
-        })
+        Ok(TestCase::new(
+            format!("endpoint {} returns 200 ok", self.endpoint),
+            test_result,
+        ))
+    }
+}
+
+/// Validator: GET several routes concurrently, each expecting its own status
+pub struct EndpointsValidator {
+    pub port: u16,
+    pub routes: Vec<(String, u16)>,
+}
+
+impl EndpointsValidator {
+    pub fn new(routes: Vec<(String, u16)>) -> Self {
+        Self {
+            port: DEFAULT_PORT,
+            routes,
+        }
+    }
+
+    pub async fn validate(&self) -> Result<TestCase, String> {
+        let mut set = JoinSet::new();
+        for (index, (path, _)) in self.routes.iter().enumerate() {
+            let port = self.port;
+            let path = path.clone();
+            set.spawn(async move {
+                let status = http_request(port, "GET", &path, &[], None)
+                    .await
+                    .map(|r| r.status_code);
+                (index, status)
+            });
+        }
+
+        let mut statuses: Vec<Result<u16, String>> =
+            vec![Err("request did not complete".to_string()); self.routes.len()];
+        while let Some(joined) = set.join_next().await {
+            if let Ok((index, status)) = joined {
+                statuses[index] = status;
+            }
+        }
+
+        Ok(TestCase::new(
+            format!("{} routes return expected status", self.routes.len()),
+            summarize_routes(&self.routes, &statuses),
+        ))
+    }
+}
+
+/// parse "path=status" (or "path=>status"); splits on the last '=' so queries keep theirs
+pub fn parse_route(pair: &str) -> Result<(String, u16), String> {
+    let (path, status) = pair
+        .rsplit_once('=')
+        .ok_or_else(|| format!("invalid route '{}'. expected path=status", pair))?;
+    let status = status.strip_prefix('>').unwrap_or(status);
+    let status = status
+        .trim()
+        .parse::<u16>()
+        .map_err(|_| format!("invalid status '{}' in route '{}'", status, pair))?;
+    if path.is_empty() {
+        return Err(format!("missing path in route '{}'", pair));
+    }
+    Ok((path.trim().to_string(), status))
+}
+
+/// "4/5 routes ok" plus one line per failing route
+fn summarize_routes(
+    routes: &[(String, u16)],
+    statuses: &[Result<u16, String>],
+) -> Result<String, String> {
+    let mut failures = Vec::new();
+    for ((path, expected), status) in routes.iter().zip(statuses) {
+        match status {
+            Ok(actual) if actual == expected => {}
+            Ok(actual) => failures.push(format!("{}: expected {}, got {}", path, expected, actual)),
+            Err(e) => failures.push(format!("{}: {}", path, e)),
+        }
+    }
+
+    let ok = routes.len() - failures.len();
+    let summary = format!("{}/{} routes ok", ok, routes.len());
+    if failures.is_empty() {
+        Ok(summary)
+    } else {
+        Err(format!("{}\n{}", summary, failures.join("\n")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_route() {
+        assert_eq!(parse_route("/=200").unwrap(), ("/".to_string(), 200));
+        assert_eq!(
+            parse_route("/health=>200").unwrap(),
+            ("/health".to_string(), 200)
+        );
+        assert_eq!(
+            parse_route("/search?q=a=404").unwrap(),
+            ("/search?q=a".to_string(), 404)
+        );
+    }
+
+    #[test]
+    fn test_parse_route_invalid() {
+        assert!(parse_route("/health")
+            .unwrap_err()
+            .contains("expected path=status"));
+        assert!(parse_route("/health=ok")
+            .unwrap_err()
+            .contains("invalid status"));
+        assert!(parse_route("=200").unwrap_err().contains("missing path"));
+    }
+
+    #[test]
+    fn test_summarize_routes() {
+        let routes = vec![
+            ("/".to_string(), 200),
+            ("/health".to_string(), 200),
+            ("/missing".to_string(), 404),
+        ];
+        let all_ok = summarize_routes(&routes, &[Ok(200), Ok(200), Ok(404)]);
+        assert_eq!(all_ok.unwrap(), "3/3 routes ok");
+
+        let some_failed = summarize_routes(
+            &routes,
+            &[Ok(200), Err("connection failed".to_string()), Ok(200)],
+        );
+        assert_eq!(
+            some_failed.unwrap_err(),
+            "1/3 routes ok\n/health: connection failed\n/missing: expected 404, got 200"
+        );
     }
 }
@@ -1,27 +1,34 @@
-use super::compile::CanCompileValidator;
+use super::command::CommandExitValidator;
+use super::compile::{CanCompileValidator, CompileLanguage};
 use super::docker::{DockerValidator, Expectation};
-use super::file::FileContentsMatchValidator;
+use super::endpoint::{parse_route, EndpointsValidator};
+use super::file::{FileContentsMatchValidator, FileMatchMode, FileSha256Validator};
 use super::http::{
-    ConcurrentRequestsValidator, HttpChunkedValidator, HttpContentTypeValidator,
-    HttpGetCompressedValidator, HttpGetFileValidator, HttpGetValidator, HttpGetWithHeaderValidator,
-    HttpHeaderPresentValidator, HttpHeaderValueValidator, HttpJsonExistsValidator,
-    HttpJsonFieldValidator, HttpKeepaliveValidator, HttpPipeliningValidator, HttpPostFileValidator,
-    HttpPostJsonValidator, HttpStatusValidator, RateLimitValidator,
+    BodyMatch, ConcurrentRequestsValidator, HttpChunkedValidator, HttpContentTypeValidator,
+    HttpCorsValidator, HttpGetCompressedValidator, HttpGetFileValidator, HttpGetValidator,
+    HttpGetWithHeaderValidator, HttpHeaderPresentValidator, HttpHeaderValueValidator,
+    HttpJsonExistsValidator, HttpJsonFieldValidator, HttpKeepaliveValidator, HttpPipelineValidator,
+    HttpPipeliningValidator, HttpPostFileValidator, HttpPostJsonValidator, HttpStatusValidator,
+    HttpVersionValidator, RateLimitValidator, DEFAULT_PORT,
 };
-use super::parser::{parse_validator, ParsedValidator};
+use super::json_response::{JsonMatch, JsonResponseValidator};
+use super::parser::{parse_validator, ParamValue, ParsedValidator};
 use super::port::PortValidator;
 use super::process::{ConcurrentAccessValidator, GracefulShutdownValidator};
 use super::scenario::{
     HttpHealthCheck, HttpJsonFieldNested, HttpJsonFieldValue, HttpRequestWithBody, HttpStatusCheck,
-    JobPriorityVerified, JobProcessingVerified, JobResultVerified, JobRetryVerified,
-    JobSubmissionVerified, JobTimeoutReasonVerified, JobTimeoutVerified, WorkerPoolConcurrent,
-    WorkerScaleDown, WorkerScaleUp,
+    JobDeadLetterVerified, JobFifoVerified, JobPriorityVerified, JobProcessingVerified,
+    JobResultVerified, JobRetryVerified, JobSubmissionVerified, JobTimeoutReasonVerified,
+    JobTimeoutVerified, WorkerPoolConcurrent, WorkerScaleDown, WorkerScaleUp,
 };
-use crate::tasks::TestCase;
+use crate::tasks::{TestCase, ValidationContext};
+use std::net::IpAddr;
+use std::sync::OnceLock;
 
 /// Runtime validator that can execute any parsed validator type
 pub enum RuntimeValidator {
     TcpListening(PortValidator),
+    TcpNotListening(PortValidator),
     HttpResponseStatus(HttpStatusValidator),
     HttpGet(HttpGetValidator),
     HttpHeaderPresent(HttpHeaderPresentValidator),
@@ -32,7 +39,9 @@ pub enum RuntimeValidator {
     HttpGetFile(HttpGetFileValidator),
     HttpGetCompressed(HttpGetCompressedValidator),
     FileContentsMatch(FileContentsMatchValidator),
+    FileSha256(FileSha256Validator),
     CanCompile(CanCompileValidator),
+    CommandExit(CommandExitValidator),
     // http validators
     HttpJsonExists(HttpJsonExistsValidator),
     HttpJsonField(HttpJsonFieldValidator),
@@ -62,14 +71,78 @@ pub enum RuntimeValidator {
     HttpKeepalive(HttpKeepaliveValidator),
     HttpChunked(HttpChunkedValidator),
     HttpPipelining(HttpPipeliningValidator),
+    HttpCors(HttpCorsValidator),
+    HttpVersion(HttpVersionValidator),
+    HttpPipeline(HttpPipelineValidator),
+    Endpoints(EndpointsValidator),
+    JsonResponse(JsonResponseValidator),
+    JobFifoVerified(JobFifoVerified),
+    JobDeadLetterVerified(JobDeadLetterVerified),
     // placeholder for validators not yet implemented
     NotImplemented(String),
 }
 
 impl RuntimeValidator {
-    pub async fn validate(&self) -> Result<TestCase, String> {
+    /// point validators that fell back to a module default port at `port`.
+    /// validators taking the port as a DSL param keep it.
+    fn set_default_port(&mut self, port: u16) {
+        match self {
+            RuntimeValidator::HttpResponseStatus(v) => v.port = port,
+            RuntimeValidator::HttpGet(v) => v.port = port,
+            RuntimeValidator::HttpHeaderPresent(v) => v.port = port,
+            RuntimeValidator::HttpHeaderValue(v) => v.port = port,
+            RuntimeValidator::HttpGetWithHeader(v) => v.port = port,
+            RuntimeValidator::ConcurrentRequests(v) => v.port = port,
+            RuntimeValidator::HttpPostFile(v) => v.port = port,
+            RuntimeValidator::HttpGetFile(v) => v.port = port,
+            RuntimeValidator::HttpGetCompressed(v) => v.port = port,
+            RuntimeValidator::HttpJsonExists(v) => v.port = port,
+            RuntimeValidator::HttpJsonField(v) => v.port = port,
+            RuntimeValidator::HttpPostJson(v) => v.port = port,
+            RuntimeValidator::RateLimit(v) => v.port = port,
+            RuntimeValidator::JobSubmissionVerified(v) => v.port = port,
+            RuntimeValidator::JobProcessingVerified(v) => v.port = port,
+            RuntimeValidator::WorkerPoolConcurrent(v) => v.port = port,
+            RuntimeValidator::JobResultVerified(v) => v.port = port,
+            RuntimeValidator::JobPriorityVerified(v) => v.port = port,
+            RuntimeValidator::JobTimeoutVerified(v) => v.port = port,
+            RuntimeValidator::JobTimeoutReasonVerified(v) => v.port = port,
+            RuntimeValidator::JobRetryVerified(v) => v.port = port,
+            RuntimeValidator::WorkerScaleUp(v) => v.port = port,
+            RuntimeValidator::WorkerScaleDown(v) => v.port = port,
+            RuntimeValidator::HttpRequestWithBody(v) => v.port = port,
+            RuntimeValidator::HttpJsonFieldNested(v) => v.port = port,
+            RuntimeValidator::HttpHealthCheck(v) => v.port = port,
+            RuntimeValidator::HttpJsonFieldValue(v) => v.port = port,
+            RuntimeValidator::HttpStatusCheck(v) => v.port = port,
+            RuntimeValidator::HttpContentType(v) => v.port = port,
+            RuntimeValidator::HttpKeepalive(v) => v.port = port,
+            RuntimeValidator::HttpChunked(v) => v.port = port,
+            RuntimeValidator::HttpPipelining(v) => v.port = port,
+            RuntimeValidator::HttpCors(v) => v.port = port,
+            RuntimeValidator::HttpVersion(v) => v.port = port,
+            RuntimeValidator::HttpPipeline(v) => v.port = port,
+            RuntimeValidator::Endpoints(v) => v.port = port,
+            RuntimeValidator::JsonResponse(v) => v.port = port,
+            RuntimeValidator::JobFifoVerified(v) => v.port = port,
+            RuntimeValidator::JobDeadLetterVerified(v) => v.port = port,
+            RuntimeValidator::TcpListening(_)
+            | RuntimeValidator::TcpNotListening(_)
+            | RuntimeValidator::ConcurrentAccess(_)
+            | RuntimeValidator::FileContentsMatch(_)
+            | RuntimeValidator::FileSha256(_)
+            | RuntimeValidator::CanCompile(_)
+            | RuntimeValidator::CommandExit(_)
+            | RuntimeValidator::GracefulShutdown(_)
+            | RuntimeValidator::Docker(_)
+            | RuntimeValidator::NotImplemented(_) => {}
+        }
+    }
+
+    pub async fn validate(&self, ctx: &ValidationContext) -> Result<TestCase, String> {
         match self {
             RuntimeValidator::TcpListening(v) => v.validate().await,
+            RuntimeValidator::TcpNotListening(v) => v.validate().await,
             RuntimeValidator::HttpResponseStatus(v) => v.validate().await,
             RuntimeValidator::HttpGet(v) => v.validate().await,
             RuntimeValidator::HttpHeaderPresent(v) => v.validate().await,
@@ -79,13 +152,15 @@ impl RuntimeValidator {
             RuntimeValidator::HttpPostFile(v) => v.validate().await,
             RuntimeValidator::HttpGetFile(v) => v.validate().await,
             RuntimeValidator::HttpGetCompressed(v) => v.validate().await,
-            RuntimeValidator::FileContentsMatch(v) => v.validate().await,
-            RuntimeValidator::CanCompile(v) => v.validate().await,
+            RuntimeValidator::FileContentsMatch(v) => v.validate(ctx).await,
+            RuntimeValidator::FileSha256(v) => v.validate(ctx).await,
+            RuntimeValidator::CanCompile(v) => v.validate(ctx).await,
+            RuntimeValidator::CommandExit(v) => v.validate(ctx).await,
             RuntimeValidator::HttpJsonExists(v) => v.validate().await,
             RuntimeValidator::HttpJsonField(v) => v.validate().await,
             RuntimeValidator::HttpPostJson(v) => v.validate().await,
             RuntimeValidator::RateLimit(v) => v.validate().await,
-            RuntimeValidator::GracefulShutdown(v) => v.validate().await,
+            RuntimeValidator::GracefulShutdown(v) => v.validate(ctx).await,
             RuntimeValidator::ConcurrentAccess(v) => v.validate().await,
             // scenario validators
             RuntimeValidator::JobSubmissionVerified(v) => v.validate().await,
@@ -103,21 +178,29 @@ impl RuntimeValidator {
             RuntimeValidator::HttpHealthCheck(v) => v.validate().await,
             RuntimeValidator::HttpJsonFieldValue(v) => v.validate().await,
             RuntimeValidator::HttpStatusCheck(v) => v.validate().await,
-            RuntimeValidator::Docker(v) => v.validate().await,
+            RuntimeValidator::Docker(v) => v.validate(ctx).await,
             RuntimeValidator::HttpContentType(v) => v.validate().await,
             RuntimeValidator::HttpKeepalive(v) => v.validate().await,
             RuntimeValidator::HttpChunked(v) => v.validate().await,
             RuntimeValidator::HttpPipelining(v) => v.validate().await,
-            RuntimeValidator::NotImplemented(name) => Ok(TestCase {
-                name: format!("validator '{}'", name),
-                result: Err(format!("validator '{}' not implemented yet", name)),
-            }),
+            RuntimeValidator::HttpCors(v) => v.validate().await,
+            RuntimeValidator::HttpVersion(v) => v.validate().await,
+            RuntimeValidator::HttpPipeline(v) => v.validate().await,
+            RuntimeValidator::Endpoints(v) => v.validate().await,
+            RuntimeValidator::JsonResponse(v) => v.validate().await,
+            RuntimeValidator::JobFifoVerified(v) => v.validate().await,
+            RuntimeValidator::JobDeadLetterVerified(v) => v.validate().await,
+            RuntimeValidator::NotImplemented(name) => Ok(TestCase::new(
+                format!("validator '{}'", name),
+                Err(format!("validator '{}' not implemented yet", name)),
+            )),
         }
     }
 
     pub fn name(&self) -> &str {
         match self {
             RuntimeValidator::TcpListening(_) => "tcp_listening",
+            RuntimeValidator::TcpNotListening(_) => "tcp_not_listening",
             RuntimeValidator::HttpResponseStatus(_) => "http_response_status",
             RuntimeValidator::HttpGet(_) => "http_get",
             RuntimeValidator::HttpHeaderPresent(_) => "http_header_present",
@@ -128,7 +211,10 @@ impl RuntimeValidator {
             RuntimeValidator::HttpGetFile(_) => "http_get_file",
             RuntimeValidator::HttpGetCompressed(_) => "http_get_compressed",
             RuntimeValidator::FileContentsMatch(_) => "file_contents_match",
+            RuntimeValidator::FileSha256(_) => "file_sha256",
             RuntimeValidator::CanCompile(_) => "can_compile",
+            RuntimeValidator::CommandExit(v) if v.expected_exit_code.is_none() => "command_output",
+            RuntimeValidator::CommandExit(_) => "command_exit",
             RuntimeValidator::HttpJsonExists(_) => "http_json_exists",
             RuntimeValidator::HttpJsonField(_) => "http_json_field",
             RuntimeValidator::HttpPostJson(_) => "http_post_json",
@@ -156,6 +242,13 @@ impl RuntimeValidator {
             RuntimeValidator::HttpKeepalive(_) => "http_keepalive",
             RuntimeValidator::HttpChunked(_) => "http_chunked",
             RuntimeValidator::HttpPipelining(_) => "http_pipelining",
+            RuntimeValidator::HttpCors(_) => "http_cors",
+            RuntimeValidator::HttpVersion(_) => "http_version",
+            RuntimeValidator::HttpPipeline(_) => "http_pipeline",
+            RuntimeValidator::Endpoints(_) => "endpoints",
+            RuntimeValidator::JsonResponse(_) => "json_response",
+            RuntimeValidator::JobFifoVerified(_) => "job_fifo",
+            RuntimeValidator::JobDeadLetterVerified(_) => "job_dead_letter",
             RuntimeValidator::NotImplemented(name) => name,
         }
     }
@@ -163,82 +256,289 @@ impl RuntimeValidator {
 
 /// Create a RuntimeValidator from a validator DSL string
 pub fn create_validator(validator_str: &str) -> Result<RuntimeValidator, String> {
-    let parsed = parse_validator(validator_str)?;
+    let parsed = effective_params(validator_str)?;
     create_from_parsed(&parsed)
 }
 
+/// Parse a validator DSL string with omitted optional params filled in,
+/// i.e. the values the validator will actually run with
+pub fn effective_params(validator_str: &str) -> Result<ParsedValidator, String> {
+    let mut parsed = parse_validator(validator_str)?;
+    let defaults = param_defaults(&parsed.name);
+    if !parsed.named.is_empty() {
+        resolve_named(&mut parsed, defaults.as_ref())?;
+    }
+    if let Some((first_optional, defaults)) = defaults {
+        parsed.fill_defaults(first_optional, &defaults);
+    }
+    Ok(parsed)
+}
+
+/// Move `name=type(value)` params into their positional slots; positional
+/// params fill the slots left free, in order. A gap before the last given
+/// param takes its default, or is an error when the param is required.
+fn resolve_named(
+    parsed: &mut ParsedValidator,
+    defaults: Option<&(usize, Vec<ParamValue>)>,
+) -> Result<(), String> {
+    let names = param_names(&parsed.name).unwrap_or_default();
+    let mut slots: Vec<Option<ParamValue>> = Vec::new();
+    for (name, value) in std::mem::take(&mut parsed.named) {
+        let index = names
+            .iter()
+            .position(|n| *n == name)
+            .ok_or_else(|| format!("{} has no param named '{}'", parsed.name, name))?;
+        if slots.len() <= index {
+            slots.resize(index + 1, None);
+        }
+        slots[index] = Some(value);
+    }
+
+    let mut positional = std::mem::take(&mut parsed.params).into_iter();
+    for slot in slots.iter_mut().filter(|slot| slot.is_none()) {
+        *slot = positional.next();
+    }
+    slots.extend(positional.map(Some));
+
+    for (index, slot) in slots.into_iter().enumerate() {
+        let value = match slot {
+            Some(value) => value,
+            None => defaults
+                .and_then(|(first_optional, values)| {
+                    values.get(index.checked_sub(*first_optional)?)
+                })
+                .cloned()
+                .ok_or_else(|| {
+                    format!(
+                        "{} param {} ('{}'): missing",
+                        parsed.name, index, names[index]
+                    )
+                })?,
+        };
+        parsed.params.push(value);
+    }
+    Ok(())
+}
+
+/// Names for each validator's positional params, so `count=int(10)` is
+/// the same as giving `int(10)` at that position. Aliases and validators
+/// taking a variable number of params accept no names.
+fn param_names(name: &str) -> Option<&'static [&'static str]> {
+    let names: &[&str] = match name {
+        "tcp_listening" => &["port", "host"],
+        "tcp_not_listening" => &["port"],
+        "http_response_status" => &["status"],
+        "http_get" | "http_path" => &["path", "status", "body", "mode"],
+        "http_header_present" => &["header", "present"],
+        "http_header_value" => &["header", "value", "should_match"],
+        "http_get_with_header" => &["path", "header", "value", "status", "body"],
+        "concurrent_requests" => &["connections", "path", "status"],
+        "http_post_file" => &["path", "body", "status"],
+        "can_compile" => &["language", "success"],
+        "command_exit" => &["command", "exit_code"],
+        "command_output" => &["command", "pattern"],
+        "http_get_file" => &["path", "status"],
+        "http_get_compressed" => &["path", "encoding"],
+        "file_contents_match" => &["path", "expected", "mode"],
+        "file_sha256" => &["path", "sha256"],
+        "http_json_field" => &["path", "method", "field", "value"],
+        "http_post_json" => &["path", "body", "status"],
+        "rate_limit" => &[
+            "path",
+            "method",
+            "total",
+            "window_ms",
+            "expected_rejected",
+            "rejection_status",
+        ],
+        "graceful_shutdown" => &["binary", "timeout_ms"],
+        "concurrent_access" => &["port", "path", "clients", "operations", "max_failures"],
+        "job_submission_verified" => &["job_type", "payload"],
+        "job_processing_verified" => &["wait_ms", "status"],
+        "worker_pool_concurrent" => &["workers", "jobs", "max_time_ms"],
+        "job_result" => &["job_type", "payload", "expected"],
+        "job_priority" => &["high", "low"],
+        "job_timeout" => &["duration_ms", "status"],
+        "job_timeout_reason" => &["reason"],
+        "job_retry" => &["job_type", "max_retries"],
+        "worker_scale_up" => &["initial_workers", "jobs", "min_workers"],
+        "worker_scale_down" => &["initial_workers", "max_workers"],
+        "http_request" => &["method", "path", "body", "status"],
+        "http_json_field_nested" => &["path", "field"],
+        "http_health_check" => &["path", "status", "field", "value"],
+        "http_json_field_value" => &["path", "field", "value"],
+        "http_status_check" => &["path", "status"],
+        "docker" => &["dockerfile", "expect", "timeout_secs"],
+        "http_content_type" => &["filename", "mime"],
+        "http_keepalive" | "http_pipelining" => &["requests"],
+        "http_gzip_content" | "http_gzip_body" => &["path", "expected"],
+        "http_chunked_stream" => &["chunks"],
+        "http_cors" => &["path", "origin", "method"],
+        "http_version" => &["path", "version"],
+        "http_pipeline" => &["first_path", "first_status", "second_path", "second_status"],
+        "json_response" => &["path", "expected", "mode"],
+        "job_fifo" => &["count"],
+        "job_dead_letter" => &["job_type", "max_retries"],
+        _ => return None,
+    };
+    Some(names)
+}
+
+/// Defaults for optional trailing params: the index of the first optional
+/// param and the values used when it and the ones after it are omitted
+fn param_defaults(name: &str) -> Option<(usize, Vec<ParamValue>)> {
+    use ParamValue::{Bool, Int};
+    let string = |s: &str| ParamValue::String(s.to_string());
+
+    let defaults = match name {
+        "tcp_listening" => (1, vec![string("127.0.0.1")]),
+        // an empty expected body skips the body check
+        "http_get" | "http_path" => (2, vec![string(""), string("trimmed")]),
+        "http_get_with_header" => (4, vec![string("")]),
+        "http_header_value" => (2, vec![Bool(true)]),
+        "command_exit" => (1, vec![Int(0)]),
+        "concurrent_access" => (4, vec![Int(0)]),
+        "file_contents_match" => (2, vec![string("trimmed")]),
+        "json_response" => (2, vec![string("exact")]),
+        // expected success after the language; the bool-only form ignores it
+        "can_compile" => (1, vec![Bool(true)]),
+        // an empty body means the request is sent without one
+        "http_request" => (2, vec![string(""), Int(200)]),
+        "rate_limit" => (5, vec![Int(429)]),
+        "job_submission_verified" => (0, vec![string("test"), string("data")]),
+        "job_processing_verified" => (0, vec![Int(200), string("completed")]),
+        "worker_pool_concurrent" => (0, vec![Int(4), Int(4), Int(1000)]),
+        "job_priority" => (0, vec![Int(10), Int(1)]),
+        "job_timeout" => (0, vec![Int(5000), string("failed")]),
+        "job_timeout_reason" => (0, vec![string("timeout")]),
+        "job_retry" => (0, vec![string("flaky"), Int(3)]),
+        "worker_scale_up" => (0, vec![Int(2), Int(50), Int(4)]),
+        "worker_scale_down" => (0, vec![Int(8), Int(4)]),
+        "job_fifo" => (0, vec![Int(5)]),
+        "job_dead_letter" => (0, vec![string("always_fail"), Int(3)]),
+        _ => return None,
+    };
+    Some(defaults)
+}
+
+/// Like `create_validator`, but the task's port replaces the module default
+pub fn create_validator_with(
+    validator_str: &str,
+    ctx: &ValidationContext,
+) -> Result<RuntimeValidator, String> {
+    let mut validator = create_validator(validator_str)?;
+    if let Some(port) = ctx.port {
+        validator.set_default_port(port);
+    }
+    Ok(validator)
+}
+
+type Constructor = fn(&ParsedValidator) -> Result<RuntimeValidator, String>;
+
+/// every canonical validator name and its constructor; aliases live in `ALIASES`
+const CONSTRUCTORS: &[(&str, Constructor)] = &[
+    ("tcp_listening", create_tcp_listening),
+    ("tcp_not_listening", create_tcp_not_listening),
+    ("http_response_status", create_http_response_status),
+    ("http_get", create_http_get),
+    ("http_header_present", create_http_header_present),
+    ("http_header_value", create_http_header_value),
+    ("http_get_with_header", create_http_get_with_header),
+    ("concurrent_requests", create_concurrent_requests),
+    ("http_post_file", create_http_post_file),
+    ("can_compile", create_can_compile),
+    ("command_exit", create_command_exit),
+    ("command_output", create_command_output),
+    ("http_get_file", create_http_get_file),
+    ("http_get_compressed", create_http_get_compressed),
+    ("file_contents_match", create_file_contents_match),
+    ("file_sha256", create_file_sha256),
+    ("http_json_exists", create_http_json_exists),
+    ("http_json_field", create_http_json_field),
+    ("http_post_json", create_http_post_json),
+    ("rate_limit", create_rate_limit),
+    ("graceful_shutdown", create_graceful_shutdown),
+    ("concurrent_access", create_concurrent_access),
+    // scenario validators
+    ("job_submission_verified", create_job_submission_verified),
+    ("job_processing_verified", create_job_processing_verified),
+    ("worker_pool_concurrent", create_worker_pool_concurrent),
+    ("job_result", create_job_result),
+    ("job_priority", create_job_priority),
+    ("job_timeout", create_job_timeout),
+    ("job_timeout_reason", create_job_timeout_reason),
+    ("job_retry", create_job_retry),
+    ("worker_scale_up", create_worker_scale_up),
+    ("worker_scale_down", create_worker_scale_down),
+    ("http_request", create_http_request),
+    ("http_json_field_nested", create_http_json_field_nested),
+    ("http_health_check", create_http_health_check),
+    ("http_json_field_value", create_http_json_field_value),
+    ("http_status_check", create_http_status_check),
+    ("docker", create_docker),
+    ("http_path", create_http_get),
+    ("http_content_type", create_http_content_type),
+    ("http_keepalive", create_http_keepalive),
+    ("http_gzip_content", create_http_gzip_content),
+    ("http_gzip_body", create_http_gzip_content),
+    ("http_pipelining", create_http_pipelining),
+    ("http_chunked_stream", create_http_chunked_stream),
+    ("http_chunked_format", create_http_chunked_format),
+    ("http_cors", create_http_cors),
+    ("http_version", create_http_version),
+    ("http_pipeline", create_http_pipeline),
+    ("endpoints", create_endpoints),
+    ("json_response", create_json_response),
+    ("job_fifo", create_job_fifo),
+    ("job_dead_letter", create_job_dead_letter),
+];
+
+/// every validator name the DSL accepts: canonical names, then aliases
+pub fn known_validators() -> &'static [&'static str] {
+    static NAMES: OnceLock<Vec<&'static str>> = OnceLock::new();
+    NAMES.get_or_init(|| {
+        CONSTRUCTORS
+            .iter()
+            .map(|(name, _)| *name)
+            .chain(ALIASES.iter().map(|alias| alias.name))
+            .collect()
+    })
+}
+
 /// Create a RuntimeValidator from a parsed validator definition
 fn create_from_parsed(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    match parsed.name.as_str() {
-        "tcp_listening" => create_tcp_listening(parsed),
-        "http_response_status" => create_http_response_status(parsed),
-        "http_get" => create_http_get(parsed),
-        "http_header_present" => create_http_header_present(parsed),
-        "http_header_value" => create_http_header_value(parsed),
-        "http_get_with_header" => create_http_get_with_header(parsed),
-        "concurrent_requests" => create_concurrent_requests(parsed),
-        "http_post_file" => create_http_post_file(parsed),
-        "can_compile" => create_can_compile(parsed),
-        "http_get_file" => create_http_get_file(parsed),
-        "http_get_compressed" => create_http_get_compressed(parsed),
-        "file_contents_match" => create_file_contents_match(parsed),
-        "http_json_exists" => create_http_json_exists(parsed),
-        "http_json_field" => create_http_json_field(parsed),
-        "http_post_json" => create_http_post_json(parsed),
-        "rate_limit" => create_rate_limit(parsed),
-        "graceful_shutdown" => create_graceful_shutdown(parsed),
-        "concurrent_access" => create_concurrent_access(parsed),
-        // scenario validators
-        "job_submission_verified" => create_job_submission_verified(parsed),
-        "job_processing_verified" => create_job_processing_verified(parsed),
-        "worker_pool_concurrent" => create_worker_pool_concurrent(parsed),
-        "job_result" => create_job_result(parsed),
-        "job_priority" => create_job_priority(parsed),
-        "job_timeout" => create_job_timeout(parsed),
-        "job_timeout_reason" => create_job_timeout_reason(parsed),
-        "job_retry" => create_job_retry(parsed),
-        "worker_scale_up" => create_worker_scale_up(parsed),
-        "worker_scale_down" => create_worker_scale_down(parsed),
-        "http_request" => create_http_request(parsed),
-        "http_json_field_nested" => create_http_json_field_nested(parsed),
-        "http_health_check" => create_http_health_check(parsed),
-        "http_json_field_value" => create_http_json_field_value(parsed),
-        "http_status_check" => create_http_status_check(parsed),
-        "docker" => create_docker(parsed),
-        "http_path_root" => create_http_path_root(parsed),
-        "http_path_unknown" => create_http_path_unknown(parsed),
-        "http_path" => create_http_get(parsed),
-        "http_header_server" => create_http_header_server(parsed),
-        "http_header_date" => create_http_header_date(parsed),
-        "http_header_connection" => create_http_header_connection(parsed),
-        "http_echo" => create_http_echo(parsed),
-        "http_user_agent" => create_http_user_agent(parsed),
-        "http_concurrent_clients" => create_http_concurrent_clients(parsed),
-        "http_query_param" => create_http_query_param(parsed),
-        "http_query_missing" => create_http_query_missing(parsed),
-        "http_file_not_found" => create_http_file_not_found(parsed),
-        "http_content_type" => create_http_content_type(parsed),
-        "http_gzip_encoding" => create_http_gzip_encoding(parsed),
-        "http_file_get" => create_http_file_get_alias(parsed),
-        "http_file_traversal" => create_http_file_traversal(parsed),
-        "http_query_encoded" => create_http_query_encoded(parsed),
-        "tcp_read_request" => create_tcp_read_request(parsed),
-        "http_keepalive" => create_http_keepalive(parsed),
-        "http_connection_close" => create_http_connection_close(parsed),
-        "http_gzip_content" => create_http_gzip_content(parsed),
-        "http_pipelining" => create_http_pipelining(parsed),
-        "http_chunked_stream" => create_http_chunked_stream(parsed),
-        "http_chunked_format" => create_http_chunked_format(parsed),
-        "http_file_post" => create_http_file_post(parsed),
-        "http_file_verify" => create_http_file_verify(parsed),
-        _ => Ok(RuntimeValidator::NotImplemented(parsed.name.clone())),
-    }
-}
-
-// tcp_listening:int(4221)
+    let name = parsed.name.as_str();
+    // constructors read positions only; `effective_params` resolves the names first
+    if let Some((param, _)) = parsed.named.first() {
+        return Err(format!("{} has no param named '{}'", name, param));
+    }
+    if let Some((_, create)) = CONSTRUCTORS.iter().find(|(n, _)| *n == name) {
+        return create(parsed);
+    }
+    match ALIASES.iter().find(|alias| alias.name == name) {
+        Some(alias) => create_alias(alias, parsed),
+        None => Ok(RuntimeValidator::NotImplemented(parsed.name.clone())),
+    }
+}
+
+// tcp_listening:int(4221) OR tcp_listening:int(4221),string(0.0.0.0)
 fn create_tcp_listening(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
     let port = parsed.param_as_int(0)? as u16;
-    Ok(RuntimeValidator::TcpListening(PortValidator::new(port)))
+    let host = parsed.param_as_string(1)?;
+    let host = host
+        .parse::<IpAddr>()
+        .map_err(|_| format!("invalid bind host '{}'", host))?;
+    Ok(RuntimeValidator::TcpListening(
+        PortValidator::new(port).with_host(host),
+    ))
+}
+
+// tcp_not_listening:int(4221)
+fn create_tcp_not_listening(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
+    let port = parsed.param_as_int(0)? as u16;
+    Ok(RuntimeValidator::TcpNotListening(
+        PortValidator::new(port).not_listening(),
+    ))
 }
 
 // http_response_status:int(200)
@@ -250,19 +550,18 @@ fn create_http_response_status(parsed: &ParsedValidator) -> Result<RuntimeValida
 }
 
 // http_get:string(/path),int(200) OR http_get:string(/path),int(200),string(expected_body)
+// optional 4th param selects body comparison: exact, trimmed (default), ignore_case, contains
 fn create_http_get(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
     let path = parsed.param_as_string(0)?;
     let status = parsed.param_as_int(1)? as u16;
-    let expected_body = parsed
-        .param(2)
-        .and_then(|p| p.as_string())
-        .map(|s| s.to_string());
+    let expected_body = Some(parsed.param_as_string(2)?)
+        .filter(|body| !body.is_empty())
+        .map(|body| body.to_string());
+    let body_match = parsed.param_as_string(3)?.parse::<BodyMatch>()?;
 
-    Ok(RuntimeValidator::HttpGet(HttpGetValidator::new(
-        path,
-        status,
-        expected_body,
-    )))
+    Ok(RuntimeValidator::HttpGet(
+        HttpGetValidator::new(path, status, expected_body).with_body_match(body_match),
+    ))
 }
 
 // http_header_present:string(Content-Type),bool(true)
@@ -279,10 +578,11 @@ fn create_http_header_present(parsed: &ParsedValidator) -> Result<RuntimeValidat
 fn create_http_header_value(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
     let header_name = parsed.param_as_string(0)?;
     let expected_value = parsed.param_as_string(1)?;
-    // third param (bool) indicates if it should match, we ignore false case for now
+    // third param (bool) indicates if it should match; false asserts the value is absent
+    let should_match = parsed.param_as_bool(2)?;
 
     Ok(RuntimeValidator::HttpHeaderValue(
-        HttpHeaderValueValidator::new(header_name, expected_value),
+        HttpHeaderValueValidator::new(header_name, expected_value).with_should_match(should_match),
     ))
 }
 
@@ -292,10 +592,9 @@ fn create_http_get_with_header(parsed: &ParsedValidator) -> Result<RuntimeValida
     let header_name = parsed.param_as_string(1)?;
     let header_value = parsed.param_as_string(2)?;
     let expected_status = parsed.param_as_int(3)? as u16;
-    let expected_body = parsed
-        .param(4)
-        .and_then(|p| p.as_string())
-        .map(|s| s.to_string());
+    let expected_body = Some(parsed.param_as_string(4)?)
+        .filter(|body| !body.is_empty())
+        .map(|body| body.to_string());
 
     Ok(RuntimeValidator::HttpGetWithHeader(
         HttpGetWithHeaderValidator::new(
@@ -332,14 +631,41 @@ fn create_http_post_file(parsed: &ParsedValidator) -> Result<RuntimeValidator, S
     )))
 }
 
-// can_compile:bool(true)
+// can_compile:bool(true) OR can_compile:string(rust),bool(true)
 fn create_can_compile(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
+    if let Some(language) = parsed.param(0).and_then(|p| p.as_string()) {
+        let language = language.parse::<CompileLanguage>()?;
+        let expected_success = parsed.param_as_bool(1)?;
+        return Ok(RuntimeValidator::CanCompile(
+            CanCompileValidator::new(expected_success).with_language(language),
+        ));
+    }
+
     let expected_success = parsed.param_as_bool(0)?;
     Ok(RuntimeValidator::CanCompile(CanCompileValidator::new(
         expected_success,
     )))
 }
 
+// command_exit:string(make test) OR command_exit:string(make test),int(0)
+fn create_command_exit(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
+    let command = parsed.param_as_string(0)?;
+    let expected_exit_code = parsed.param_as_int(1)? as i32;
+    Ok(RuntimeValidator::CommandExit(CommandExitValidator::new(
+        command,
+        expected_exit_code,
+    )))
+}
+
+// command_output:string(./run.sh),string(PASS)
+fn create_command_output(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
+    let command = parsed.param_as_string(0)?;
+    let pattern = parsed.param_as_string(1)?;
+    Ok(RuntimeValidator::CommandExit(
+        CommandExitValidator::output_containing(command, pattern),
+    ))
+}
+
 // http_get_file:string(/files/test.txt),int(200)
 fn create_http_get_file(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
     let path = parsed.param_as_string(0)?;
@@ -360,14 +686,32 @@ fn create_http_get_compressed(parsed: &ParsedValidator) -> Result<RuntimeValidat
 }
 
 // file_contents_match:string(/path/to/file),string(expected content)
+// optional 3rd param selects the mode: exact, trimmed (default), regex
 fn create_file_contents_match(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
     let path = parsed.param_as_string(0)?;
     let expected_content = parsed.param_as_string(1)?;
+    let mode = parsed.param_as_string(2)?.parse::<FileMatchMode>()?;
     Ok(RuntimeValidator::FileContentsMatch(
-        FileContentsMatchValidator::new(path, expected_content),
+        FileContentsMatchValidator::new(path, expected_content).with_mode(mode)?,
     ))
 }
 
+// file_sha256:string(/path/to/file),string(hexdigest)
+fn create_file_sha256(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
+    let path = parsed.param_as_string(0)?;
+    let expected_hex = parsed.param_as_string(1)?;
+    if expected_hex.len() != 64 || !expected_hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "invalid sha256 digest '{}': expected 64 hex characters",
+            expected_hex
+        ));
+    }
+    Ok(RuntimeValidator::FileSha256(FileSha256Validator::new(
+        path,
+        expected_hex,
+    )))
+}
+
 // http_json_exists:string(/path),string(GET),string(field1),string(field2)
 fn create_http_json_exists(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
     let path = parsed.param_as_string(0)?;
@@ -414,21 +758,19 @@ fn create_http_post_json(parsed: &ParsedValidator) -> Result<RuntimeValidator, S
 }
 
 // rate_limit:string(/path),string(POST),int(100),int(1000),int(90)
-// params: path, method, total_requests, window_ms, expected_rejected
+// params: path, method, total_requests, window_ms, expected_rejected[, rejection_status (default 429)]
 fn create_rate_limit(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
     let path = parsed.param_as_string(0)?;
     let method = parsed.param_as_string(1)?;
     let requests = parsed.param_as_int(2)? as u32;
     let window_ms = parsed.param_as_int(3)? as u64;
     let expected_rejected = parsed.param_as_int(4)? as u32;
+    let rejection_status = parsed.param_as_int(5)? as u16;
 
-    Ok(RuntimeValidator::RateLimit(RateLimitValidator::new(
-        path,
-        method,
-        requests,
-        window_ms,
-        expected_rejected,
-    )))
+    Ok(RuntimeValidator::RateLimit(
+        RateLimitValidator::new(path, method, requests, window_ms, expected_rejected)
+            .with_rejection_status(rejection_status),
+    ))
 }
 
 // graceful_shutdown:string(./binary),int(5000)
@@ -441,16 +783,18 @@ fn create_graceful_shutdown(parsed: &ParsedValidator) -> Result<RuntimeValidator
     ))
 }
 
-// concurrent_access:int(4221),string(/path),int(10),int(100)
-// params: port, path, concurrent_clients, operations_per_client
+// concurrent_access:int(4221),string(/path),int(10),int(100),int(0)
+// params: port, path, concurrent_clients, operations_per_client, max_failures (optional, default 0)
 fn create_concurrent_access(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
     let port = parsed.param_as_int(0)? as u16;
     let path = parsed.param_as_string(1)?;
     let concurrent_count = parsed.param_as_int(2)? as u32;
     let operations = parsed.param_as_int(3)? as u32;
+    let max_failures = parsed.param_as_int(4)? as u32;
 
     Ok(RuntimeValidator::ConcurrentAccess(
-        ConcurrentAccessValidator::new(port, path, concurrent_count, operations),
+        ConcurrentAccessValidator::new(port, path, concurrent_count, operations)
+            .with_max_failures(max_failures),
     ))
 }
 
@@ -460,8 +804,8 @@ fn create_concurrent_access(parsed: &ParsedValidator) -> Result<RuntimeValidator
 
 // job_submission_verified:string(test),string(payload)
 fn create_job_submission_verified(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let job_type = parsed.param_as_string(0).unwrap_or("test");
-    let payload = parsed.param_as_string(1).unwrap_or("data");
+    let job_type = parsed.param_as_string(0)?;
+    let payload = parsed.param_as_string(1)?;
 
     Ok(RuntimeValidator::JobSubmissionVerified(
         JobSubmissionVerified::new(job_type, payload),
@@ -470,8 +814,8 @@ fn create_job_submission_verified(parsed: &ParsedValidator) -> Result<RuntimeVal
 
 // job_processing_verified:int(200),string(completed)
 fn create_job_processing_verified(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let wait_ms = parsed.param_as_int(0).unwrap_or(200) as u64;
-    let expected_status = parsed.param_as_string(1).unwrap_or("completed");
+    let wait_ms = parsed.param_as_int(0)? as u64;
+    let expected_status = parsed.param_as_string(1)?;
 
     Ok(RuntimeValidator::JobProcessingVerified(
         JobProcessingVerified::new(wait_ms, expected_status),
@@ -481,9 +825,9 @@ fn create_job_processing_verified(parsed: &ParsedValidator) -> Result<RuntimeVal
 // worker_pool_concurrent:int(4),int(4),int(500)
 // params: workers, jobs, max_time_ms
 fn create_worker_pool_concurrent(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let workers = parsed.param_as_int(0).unwrap_or(4) as u32;
-    let jobs = parsed.param_as_int(1).unwrap_or(4) as u32;
-    let max_time_ms = parsed.param_as_int(2).unwrap_or(1000) as u64;
+    let workers = parsed.param_as_int(0)? as u32;
+    let jobs = parsed.param_as_int(1)? as u32;
+    let max_time_ms = parsed.param_as_int(2)? as u64;
 
     Ok(RuntimeValidator::WorkerPoolConcurrent(
         WorkerPoolConcurrent::new(workers, jobs, max_time_ms),
@@ -505,8 +849,8 @@ fn create_job_result(parsed: &ParsedValidator) -> Result<RuntimeValidator, Strin
 
 // job_priority:int(10),int(1)
 fn create_job_priority(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let high_priority = parsed.param_as_int(0).unwrap_or(10) as u32;
-    let low_priority = parsed.param_as_int(1).unwrap_or(1) as u32;
+    let high_priority = parsed.param_as_int(0)? as u32;
+    let low_priority = parsed.param_as_int(1)? as u32;
 
     Ok(RuntimeValidator::JobPriorityVerified(
         JobPriorityVerified::new(high_priority, low_priority),
@@ -515,8 +859,8 @@ fn create_job_priority(parsed: &ParsedValidator) -> Result<RuntimeValidator, Str
 
 // job_timeout:int(5000),string(failed)
 fn create_job_timeout(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let job_duration_ms = parsed.param_as_int(0).unwrap_or(5000) as u64;
-    let expected_status = parsed.param_as_string(1).unwrap_or("failed");
+    let job_duration_ms = parsed.param_as_int(0)? as u64;
+    let expected_status = parsed.param_as_string(1)?;
 
     Ok(RuntimeValidator::JobTimeoutVerified(
         JobTimeoutVerified::new(job_duration_ms, expected_status),
@@ -525,7 +869,7 @@ fn create_job_timeout(parsed: &ParsedValidator) -> Result<RuntimeValidator, Stri
 
 // job_timeout_reason:string(timeout)
 fn create_job_timeout_reason(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let expected_reason = parsed.param_as_string(0).unwrap_or("timeout");
+    let expected_reason = parsed.param_as_string(0)?;
 
     Ok(RuntimeValidator::JobTimeoutReasonVerified(
         JobTimeoutReasonVerified::new(expected_reason),
@@ -534,8 +878,8 @@ fn create_job_timeout_reason(parsed: &ParsedValidator) -> Result<RuntimeValidato
 
 // job_retry:string(flaky),int(3)
 fn create_job_retry(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let job_type = parsed.param_as_string(0).unwrap_or("flaky");
-    let max_retries = parsed.param_as_int(1).unwrap_or(3) as u32;
+    let job_type = parsed.param_as_string(0)?;
+    let max_retries = parsed.param_as_int(1)? as u32;
 
     Ok(RuntimeValidator::JobRetryVerified(JobRetryVerified::new(
         job_type,
@@ -545,9 +889,9 @@ fn create_job_retry(parsed: &ParsedValidator) -> Result<RuntimeValidator, String
 
 // worker_scale_up:int(2),int(50),int(4)
 fn create_worker_scale_up(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let initial_workers = parsed.param_as_int(0).unwrap_or(2) as u32;
-    let job_count = parsed.param_as_int(1).unwrap_or(50) as u32;
-    let expected_min_workers = parsed.param_as_int(2).unwrap_or(4) as u32;
+    let initial_workers = parsed.param_as_int(0)? as u32;
+    let job_count = parsed.param_as_int(1)? as u32;
+    let expected_min_workers = parsed.param_as_int(2)? as u32;
 
     Ok(RuntimeValidator::WorkerScaleUp(WorkerScaleUp::new(
         initial_workers,
@@ -558,8 +902,8 @@ fn create_worker_scale_up(parsed: &ParsedValidator) -> Result<RuntimeValidator,
 
 // worker_scale_down:int(8),int(4)
 fn create_worker_scale_down(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let initial_workers = parsed.param_as_int(0).unwrap_or(8) as u32;
-    let expected_max_workers = parsed.param_as_int(1).unwrap_or(4) as u32;
+    let initial_workers = parsed.param_as_int(0)? as u32;
+    let expected_max_workers = parsed.param_as_int(1)? as u32;
 
     Ok(RuntimeValidator::WorkerScaleDown(WorkerScaleDown::new(
         initial_workers,
@@ -568,17 +912,15 @@ fn create_worker_scale_down(parsed: &ParsedValidator) -> Result<RuntimeValidator
 }
 
 // http_request:string(POST),string(/jobs),string({"type":"test"}),int(201)
+// body and expected status are optional: http_request:string(GET),string(/health)
 fn create_http_request(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
     let method = parsed.param_as_string(0)?;
     let path = parsed.param_as_string(1)?;
-    let body = parsed
-        .param(2)
-        .and_then(|p| p.as_string())
-        .map(String::from);
-    let expected_status = parsed.param_as_int(3).unwrap_or(200) as u16;
+    let body = Some(parsed.param_as_string(2)?).filter(|body| !body.is_empty());
+    let expected_status = parsed.param_as_int(3)? as u16;
 
     Ok(RuntimeValidator::HttpRequestWithBody(
-        HttpRequestWithBody::new(method, path, body.as_deref(), expected_status),
+        HttpRequestWithBody::new(method, path, body, expected_status),
     ))
 }
 
@@ -650,112 +992,208 @@ fn create_docker(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
     Ok(RuntimeValidator::Docker(validator))
 }
 
-// http_path_root:int(200) - alias for http_get:string(/),int(status)
-fn create_http_path_root(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let status = parsed.param_as_int(0)? as u16;
-    Ok(RuntimeValidator::HttpGet(HttpGetValidator::new(
-        "/", status, None,
-    )))
-}
-
-// http_path_unknown:int(404) - GET to nonexistent path, expect given status
-fn create_http_path_unknown(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let status = parsed.param_as_int(0)? as u16;
-    Ok(RuntimeValidator::HttpGet(HttpGetValidator::new(
-        "/nonexistent-path-for-testing",
-        status,
-        None,
-    )))
-}
-
-// http_header_server:bool(true) - check Server header is present
-fn create_http_header_server(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let should_exist = parsed.param_as_bool(0)?;
-    Ok(RuntimeValidator::HttpHeaderPresent(
-        HttpHeaderPresentValidator::new("Server", should_exist),
-    ))
-}
-
-// http_header_date:bool(true) - check Date header is present
-fn create_http_header_date(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let should_exist = parsed.param_as_bool(0)?;
-    Ok(RuntimeValidator::HttpHeaderPresent(
-        HttpHeaderPresentValidator::new("Date", should_exist),
-    ))
-}
-
-// http_header_connection:string(close) - check Connection header has given value
-fn create_http_header_connection(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let expected_value = parsed.param_as_string(0)?;
-    Ok(RuntimeValidator::HttpHeaderValue(
-        HttpHeaderValueValidator::new("Connection", expected_value),
-    ))
-}
-
-// http_echo:string(input),string(expected) - GET /echo/{input}, verify body equals expected
-fn create_http_echo(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let input = parsed.param_as_string(0)?;
-    let expected = parsed.param_as_string(1)?;
-    let path = format!("/echo/{}", input);
-    Ok(RuntimeValidator::HttpGet(HttpGetValidator::new(
-        &path,
-        200,
-        Some(expected.to_string()),
-    )))
-}
-
-// http_user_agent:string(agent),string(expected) - GET /user-agent with User-Agent header
-fn create_http_user_agent(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let agent = parsed.param_as_string(0)?;
-    let expected = parsed.param_as_string(1)?;
-    Ok(RuntimeValidator::HttpGetWithHeader(
-        HttpGetWithHeaderValidator::new(
-            "/user-agent",
-            "User-Agent",
-            agent,
-            200,
-            Some(expected.to_string()),
-        ),
-    ))
-}
-
-// http_concurrent_clients:int(n) - open n connections simultaneously
-fn create_http_concurrent_clients(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let num_clients = parsed.param_as_int(0)? as u32;
-    Ok(RuntimeValidator::ConcurrentRequests(
-        ConcurrentRequestsValidator::new(num_clients, "/", 200),
-    ))
-}
+// ============================================
+// ALIASES (rewritten to a canonical validator)
+// ============================================
 
-// http_query_param:string(name),string(value),string(expected) - GET /search?name=value
-fn create_http_query_param(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let name = parsed.param_as_string(0)?;
-    let value = parsed.param_as_string(1)?;
-    let expected = parsed.param_as_string(2)?;
-    let path = format!("/search?{}={}", name, value);
-    Ok(RuntimeValidator::HttpGet(HttpGetValidator::new(
-        &path,
-        200,
-        Some(expected.to_string()),
-    )))
-}
+/// how an alias builds one param of its canonical validator
+enum AliasParam {
+    /// the alias's own param at this index, unchanged
+    Arg(usize),
+    /// a string where `{N}` is replaced by the alias's string param N
+    Format(&'static str),
+    Str(&'static str),
+    Int(i64),
+}
+
+struct Alias {
+    name: &'static str,
+    canonical: &'static str,
+    params: &'static [AliasParam],
+}
+
+const ALIASES: &[Alias] = {
+    use AliasParam::{Arg, Format, Int, Str};
+    &[
+        // http_path_root:int(200)
+        Alias {
+            name: "http_path_root",
+            canonical: "http_get",
+            params: &[Str("/"), Arg(0)],
+        },
+        // http_path_unknown:int(404) - GET to a nonexistent path
+        Alias {
+            name: "http_path_unknown",
+            canonical: "http_get",
+            params: &[Str("/nonexistent-path-for-testing"), Arg(0)],
+        },
+        // http_header_server:bool(true)
+        Alias {
+            name: "http_header_server",
+            canonical: "http_header_present",
+            params: &[Str("Server"), Arg(0)],
+        },
+        // http_header_date:bool(true)
+        Alias {
+            name: "http_header_date",
+            canonical: "http_header_present",
+            params: &[Str("Date"), Arg(0)],
+        },
+        // http_header_connection:string(close)
+        Alias {
+            name: "http_header_connection",
+            canonical: "http_header_value",
+            params: &[Str("Connection"), Arg(0)],
+        },
+        // http_connection_close:bool(true) - the param is ignored
+        Alias {
+            name: "http_connection_close",
+            canonical: "http_header_value",
+            params: &[Str("Connection"), Str("close")],
+        },
+        // http_echo:string(input),string(expected) - GET /echo/{input}
+        Alias {
+            name: "http_echo",
+            canonical: "http_get",
+            params: &[Format("/echo/{0}"), Int(200), Arg(1)],
+        },
+        // http_user_agent:string(agent),string(expected) - GET /user-agent with User-Agent header
+        Alias {
+            name: "http_user_agent",
+            canonical: "http_get_with_header",
+            params: &[
+                Str("/user-agent"),
+                Str("User-Agent"),
+                Arg(0),
+                Int(200),
+                Arg(1),
+            ],
+        },
+        // http_concurrent_clients:int(n) - open n connections simultaneously
+        Alias {
+            name: "http_concurrent_clients",
+            canonical: "concurrent_requests",
+            params: &[Arg(0), Str("/"), Int(200)],
+        },
+        // http_query_param:string(name),string(value),string(expected) - GET /search?name=value
+        Alias {
+            name: "http_query_param",
+            canonical: "http_get",
+            params: &[Format("/search?{0}={1}"), Int(200), Arg(2)],
+        },
+        // http_query_missing:int(status) - GET /search with no params
+        Alias {
+            name: "http_query_missing",
+            canonical: "http_get",
+            params: &[Str("/search"), Arg(0)],
+        },
+        // http_query_encoded:string(encoded),string(decoded) - GET /search?q=encoded
+        Alias {
+            name: "http_query_encoded",
+            canonical: "http_get",
+            params: &[Format("/search?q={0}"), Int(200), Arg(1)],
+        },
+        // http_file_not_found:string(filename),int(status)
+        Alias {
+            name: "http_file_not_found",
+            canonical: "http_get",
+            params: &[Format("/files/{0}"), Arg(1)],
+        },
+        // http_file_traversal:string(path),int(status) - path traversal attempt
+        Alias {
+            name: "http_file_traversal",
+            canonical: "http_get",
+            params: &[Format("/files/{0}"), Arg(1)],
+        },
+        // http_file_get:string(filename),string(content)
+        Alias {
+            name: "http_file_get",
+            canonical: "http_get",
+            params: &[Format("/files/{0}"), Int(200), Arg(1)],
+        },
+        // http_file_verify:string(filename),string(expected_content)
+        Alias {
+            name: "http_file_verify",
+            canonical: "http_get",
+            params: &[Format("/files/{0}"), Int(200), Arg(1)],
+        },
+        // http_file_post:string(filename),string(content),int(status)
+        Alias {
+            name: "http_file_post",
+            canonical: "http_post_file",
+            params: &[Format("/files/{0}"), Arg(1), Arg(2)],
+        },
+        // http_gzip_encoding:string(path),bool(true)
+        Alias {
+            name: "http_gzip_encoding",
+            canonical: "http_get_compressed",
+            params: &[Arg(0), Str("gzip")],
+        },
+        // tcp_read_request:bool(true) - the param is ignored
+        Alias {
+            name: "tcp_read_request",
+            canonical: "http_get",
+            params: &[Str("/"), Int(200)],
+        },
+    ]
+};
 
-// http_query_missing:int(status) - GET /search with no params, expect status
-fn create_http_query_missing(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let status = parsed.param_as_int(0)? as u16;
-    Ok(RuntimeValidator::HttpGet(HttpGetValidator::new(
-        "/search", status, None,
-    )))
-}
+/// rewrite an alias's params into its canonical validator and build that
+fn create_alias(alias: &Alias, parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
+    let mut params = Vec::with_capacity(alias.params.len());
+    for param in alias.params {
+        let value = match param {
+            AliasParam::Arg(i) => parsed
+                .param(*i)
+                .cloned()
+                .ok_or_else(|| format!("{} param {}: missing", alias.name, i))?,
+            AliasParam::Format(template) => {
+                ParamValue::String(fill_template(alias.name, template, parsed)?)
+            }
+            AliasParam::Str(s) => ParamValue::String(s.to_string()),
+            AliasParam::Int(i) => ParamValue::Int(*i),
+        };
+        params.push(value);
+    }
 
-// http_file_not_found:string(filename),int(status) - GET /files/filename, expect status
-fn create_http_file_not_found(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let filename = parsed.param_as_string(0)?;
-    let status = parsed.param_as_int(1)? as u16;
-    let path = format!("/files/{}", filename);
-    Ok(RuntimeValidator::HttpGet(HttpGetValidator::new(
-        &path, status, None,
-    )))
+    let mut canonical = ParsedValidator {
+        name: alias.canonical.to_string(),
+        params,
+        named: Vec::new(),
+    };
+    if let Some((first_optional, defaults)) = param_defaults(alias.canonical) {
+        canonical.fill_defaults(first_optional, &defaults);
+    }
+    create_from_parsed(&canonical)
+        .map_err(|e| format!("{} (alias of {}): {}", alias.name, alias.canonical, e))
+}
+
+/// replace each `{N}` in `template` with string param N in one pass, so `{`
+/// inside a param is kept as-is rather than read as another placeholder
+fn fill_template(
+    alias_name: &str,
+    template: &str,
+    parsed: &ParsedValidator,
+) -> Result<String, String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let (index, after) = rest[start + 1..]
+            .split_once('}')
+            .and_then(|(n, after)| Some((n.parse::<usize>().ok()?, after)))
+            .ok_or_else(|| format!("{}: bad placeholder in {}", alias_name, template))?;
+        if index >= parsed.params.len() {
+            return Err(format!(
+                "{}: missing parameter for {}",
+                alias_name, template
+            ));
+        }
+        out.push_str(parsed.param_as_string(index)?);
+        rest = after;
+    }
+    out.push_str(rest);
+    Ok(out)
 }
 
 // http_content_type:string(filename),string(mime) - GET /files/filename, verify Content-Type
@@ -768,57 +1206,6 @@ fn create_http_content_type(parsed: &ParsedValidator) -> Result<RuntimeValidator
     ))
 }
 
-// http_gzip_encoding:string(path),bool(true) - verify gzip Content-Encoding header
-fn create_http_gzip_encoding(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let path = parsed.param_as_string(0)?;
-    Ok(RuntimeValidator::HttpGetCompressed(
-        HttpGetCompressedValidator::new(path, "gzip"),
-    ))
-}
-
-// http_file_get:string(filename),string(content) - GET /files/filename, verify body
-fn create_http_file_get_alias(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let filename = parsed.param_as_string(0)?;
-    let expected_content = parsed.param_as_string(1)?;
-    let path = format!("/files/{}", filename);
-    Ok(RuntimeValidator::HttpGet(HttpGetValidator::new(
-        &path,
-        200,
-        Some(expected_content.to_string()),
-    )))
-}
-
-// http_file_traversal:string(path),int(status) - test path traversal attack, expect status
-fn create_http_file_traversal(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let traversal_path = parsed.param_as_string(0)?;
-    let expected_status = parsed.param_as_int(1)? as u16;
-    let path = format!("/files/{}", traversal_path);
-    Ok(RuntimeValidator::HttpGet(HttpGetValidator::new(
-        &path,
-        expected_status,
-        None,
-    )))
-}
-
-// http_query_encoded:string(encoded),string(decoded) - GET /search?q=encoded, verify decoding
-fn create_http_query_encoded(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let encoded = parsed.param_as_string(0)?;
-    let decoded = parsed.param_as_string(1)?;
-    let path = format!("/search?q={}", encoded);
-    Ok(RuntimeValidator::HttpGet(HttpGetValidator::new(
-        &path,
-        200,
-        Some(decoded.to_string()),
-    )))
-}
-
-// tcp_read_request:bool(true) - verify server reads and processes HTTP request
-fn create_tcp_read_request(_parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    Ok(RuntimeValidator::HttpGet(HttpGetValidator::new(
-        "/", 200, None,
-    )))
-}
-
 // http_keepalive:int(n) - send n requests on same connection
 fn create_http_keepalive(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
     let num_requests = parsed.param_as_int(0)? as u32;
@@ -827,18 +1214,13 @@ fn create_http_keepalive(parsed: &ParsedValidator) -> Result<RuntimeValidator, S
     ))
 }
 
-// http_connection_close:bool(true) - verify Connection: close header in response
-fn create_http_connection_close(_parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    Ok(RuntimeValidator::HttpHeaderValue(
-        HttpHeaderValueValidator::new("Connection", "close"),
-    ))
-}
-
 // http_gzip_content:string(path),string(expected) - decompress gzip body, verify content
+// http_gzip_body:string(/path),string(expected_plaintext) - same check
 fn create_http_gzip_content(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
     let path = parsed.param_as_string(0)?;
+    let expected = parsed.param_as_string(1)?;
     Ok(RuntimeValidator::HttpGetCompressed(
-        HttpGetCompressedValidator::new(path, "gzip"),
+        HttpGetCompressedValidator::new(path, "gzip").with_expected_body(expected),
     ))
 }
 
@@ -866,35 +1248,413 @@ fn create_http_chunked_format(_parsed: &ParsedValidator) -> Result<RuntimeValida
     )))
 }
 
-// http_file_post:string(filename),string(content),int(status) - POST to /files/filename
-fn create_http_file_post(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let filename = parsed.param_as_string(0)?;
-    let content = parsed.param_as_string(1)?;
-    let expected_status = parsed.param_as_int(2)? as u16;
-    let path = format!("/files/{}", filename);
-    Ok(RuntimeValidator::HttpPostFile(HttpPostFileValidator::new(
-        &path,
-        content,
-        expected_status,
+// http_cors:string(/api),string(https://example.com),string(POST) - OPTIONS preflight
+fn create_http_cors(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
+    let path = parsed.param_as_string(0)?;
+    let origin = parsed.param_as_string(1)?;
+    let method = parsed.param_as_string(2)?;
+    Ok(RuntimeValidator::HttpCors(HttpCorsValidator::new(
+        path, origin, method,
     )))
 }
 
-// http_file_verify:string(filename),string(expected_content) - GET /files/filename, verify body
-fn create_http_file_verify(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
-    let filename = parsed.param_as_string(0)?;
-    let expected_content = parsed.param_as_string(1)?;
-    let path = format!("/files/{}", filename);
-    Ok(RuntimeValidator::HttpGet(HttpGetValidator::new(
-        &path,
-        200,
-        Some(expected_content.to_string()),
+// http_version:string(/),string(HTTP/1.1)
+fn create_http_version(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
+    let path = parsed.param_as_string(0)?;
+    let expected_version = parsed.param_as_string(1)?;
+    Ok(RuntimeValidator::HttpVersion(HttpVersionValidator::new(
+        path,
+        expected_version,
+    )))
+}
+
+// http_pipeline:string(/a),int(200),string(/b),int(404) - two requests on one connection
+fn create_http_pipeline(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
+    let first_path = parsed.param_as_string(0)?;
+    let first_status = parsed.param_as_int(1)? as u16;
+    let second_path = parsed.param_as_string(2)?;
+    let second_status = parsed.param_as_int(3)? as u16;
+    Ok(RuntimeValidator::HttpPipeline(HttpPipelineValidator::new(
+        (first_path, first_status),
+        (second_path, second_status),
     )))
 }
 
+// endpoints:string(/=200),string(/health=200),string(/missing=404)
+fn create_endpoints(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
+    if parsed.params.is_empty() {
+        return Err("endpoints needs at least one path=status route".to_string());
+    }
+    let routes = (0..parsed.params.len())
+        .map(|i| parse_route(parsed.param_as_string(i)?))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(RuntimeValidator::Endpoints(EndpointsValidator::new(routes)))
+}
+
+// json_response:string(/data),string({"a":1}),string(subset) - mode defaults to exact
+fn create_json_response(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
+    let path = parsed.param_as_string(0)?;
+    let expected = serde_json::from_str(parsed.param_as_string(1)?)
+        .map_err(|e| format!("invalid expected JSON: {}", e))?;
+    let mode = parsed.param_as_string(2)?.parse::<JsonMatch>()?;
+    Ok(RuntimeValidator::JsonResponse(
+        JsonResponseValidator::new()
+            .with_endpoint(path)
+            .with_port(DEFAULT_PORT)
+            .with_expected_document(expected, mode),
+    ))
+}
+
+// job_fifo:int(5)
+fn create_job_fifo(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
+    let job_count = parsed.param_as_int(0)?;
+    if job_count < 2 {
+        return Err("job_fifo needs at least 2 jobs to check ordering".to_string());
+    }
+
+    Ok(RuntimeValidator::JobFifoVerified(JobFifoVerified::new(
+        job_count as u32,
+    )))
+}
+
+// job_dead_letter:string(always_fail),int(3)
+fn create_job_dead_letter(parsed: &ParsedValidator) -> Result<RuntimeValidator, String> {
+    let job_type = parsed.param_as_string(0)?;
+    let max_retries = parsed.param_as_int(1)? as u32;
+
+    Ok(RuntimeValidator::JobDeadLetterVerified(
+        JobDeadLetterVerified::new(job_type, max_retries),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// one representative DSL for every name `known_validators` lists
+    const REPRESENTATIVE_DSLS: &[&str] = &[
+        "tcp_listening:int(4221)",
+        "tcp_not_listening:int(4221)",
+        "http_response_status:int(200)",
+        "http_get:string(/),int(200)",
+        "http_header_present:string(Content-Type),bool(true)",
+        "http_header_value:string(Content-Encoding),string(gzip),bool(true)",
+        "http_get_with_header:string(/user-agent),string(User-Agent),string(agent),int(200)",
+        "concurrent_requests:int(3),string(/echo/test),int(200)",
+        "http_post_file:string(/files/upload.txt),string(hello),int(201)",
+        "can_compile:bool(true)",
+        "command_exit:string(make test),int(0)",
+        "command_output:string(./run.sh),string(PASS)",
+        "http_get_file:string(/files/test.txt),int(200)",
+        "http_get_compressed:string(/),string(gzip)",
+        "file_contents_match:string(/tmp/file),string(expected)",
+        "file_sha256:string(/tmp/file),string(e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855)",
+        "http_json_exists:string(/),string(GET),string(field)",
+        "http_json_field:string(/),string(GET),string(field),string(value)",
+        "http_post_json:string(/),string({\"key\":\"value\"}),int(201)",
+        "rate_limit:string(/),string(POST),int(100),int(1000),int(90)",
+        "graceful_shutdown:string(./binary),int(5000)",
+        "concurrent_access:int(4221),string(/),int(10),int(100),int(0)",
+        "job_submission_verified:string(test),string(payload)",
+        "job_processing_verified:int(200),string(completed)",
+        "worker_pool_concurrent:int(4),int(4),int(500)",
+        "job_result:string(echo),string(hello),string(hello)",
+        "job_priority:int(10),int(1)",
+        "job_timeout:int(5000),string(failed)",
+        "job_timeout_reason:string(timeout)",
+        "job_retry:string(flaky),int(3)",
+        "worker_scale_up:int(2),int(50),int(4)",
+        "worker_scale_down:int(8),int(4)",
+        "http_request:string(POST),string(/jobs),string({\"type\":\"test\"}),int(201)",
+        "http_json_field_nested:string(/stats),string(workers.total)",
+        "http_health_check:string(/health),int(200),string(status),string(ok)",
+        "http_json_field_value:string(/),string(field),string(value)",
+        "http_status_check:string(/),int(200)",
+        "docker:string(Go1.22),string(exit:0),int(120)",
+        "http_path:string(/),int(200)",
+        "http_content_type:string(index.html),string(text/html)",
+        "http_keepalive:int(3)",
+        "http_gzip_content:string(/),string(expected)",
+        "http_gzip_body:string(/),string(expected)",
+        "http_pipelining:int(3)",
+        "http_chunked_stream:int(3)",
+        "http_chunked_format:bool(true)",
+        "http_cors:string(/api),string(https://example.com),string(POST)",
+        "http_version:string(/),string(HTTP/1.1)",
+        "http_pipeline:string(/a),int(200),string(/b),int(404)",
+        "endpoints:string(/=200),string(/missing=404)",
+        "json_response:string(/data),string({\"a\":1}),string(subset)",
+        "job_fifo:int(5)",
+        "job_dead_letter:string(always_fail),int(3)",
+        "http_path_root:int(200)",
+        "http_path_unknown:int(404)",
+        "http_header_server:bool(true)",
+        "http_header_date:bool(true)",
+        "http_header_connection:string(close)",
+        "http_connection_close:bool(true)",
+        "http_echo:string(hi),string(hi)",
+        "http_user_agent:string(agent),string(agent)",
+        "http_concurrent_clients:int(3)",
+        "http_query_param:string(name),string(value),string(expected)",
+        "http_query_missing:int(400)",
+        "http_query_encoded:string(a%20b),string(a b)",
+        "http_file_not_found:string(missing.txt),int(404)",
+        "http_file_traversal:string(../etc/passwd),int(404)",
+        "http_file_get:string(test.txt),string(content)",
+        "http_file_verify:string(test.txt),string(content)",
+        "http_file_post:string(test.txt),string(content),int(201)",
+        "http_gzip_encoding:string(/),bool(true)",
+        "tcp_read_request:bool(true)",
+    ];
+
+    #[test]
+    fn test_known_validators_are_all_implemented() {
+        let names = known_validators();
+        assert_eq!(names.len(), REPRESENTATIVE_DSLS.len());
+
+        for name in names {
+            let dsl = REPRESENTATIVE_DSLS
+                .iter()
+                .find(|dsl| dsl.split(':').next() == Some(*name))
+                .unwrap_or_else(|| panic!("no representative DSL for {}", name));
+            let created =
+                create_validator(dsl).unwrap_or_else(|e| panic!("{} failed to build: {}", dsl, e));
+            assert!(
+                !matches!(created, RuntimeValidator::NotImplemented(_)),
+                "{} is listed but not implemented",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_known_validators_are_unique() {
+        let names = known_validators();
+        let unique: std::collections::HashSet<_> = names.iter().collect();
+        assert_eq!(unique.len(), names.len());
+    }
+
+    #[test]
+    fn test_omitted_optional_params_use_declared_defaults() {
+        let validator = create_validator("worker_pool_concurrent:int(8),int(16)").unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::WorkerPoolConcurrent(WorkerPoolConcurrent {
+                worker_count: 8,
+                job_count: 16,
+                max_total_ms: 1000,
+                ..
+            })
+        ));
+
+        let validator = create_validator("worker_pool_concurrent").unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::WorkerPoolConcurrent(WorkerPoolConcurrent {
+                worker_count: 4,
+                job_count: 4,
+                max_total_ms: 1000,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_effective_params_show_defaults() {
+        let parsed =
+            effective_params("rate_limit:string(/),string(GET),int(10),int(100),int(5)").unwrap();
+        assert_eq!(parsed.params.len(), 6);
+        assert_eq!(parsed.params[5], ParamValue::Int(429));
+
+        let parsed = effective_params("job_retry:string(flaky)").unwrap();
+        assert_eq!(parsed.params[1], ParamValue::Int(3));
+
+        let parsed = effective_params("json_response:string(/),string({})").unwrap();
+        assert_eq!(parsed.params[2], ParamValue::String("exact".to_string()));
+    }
+
+    #[test]
+    fn test_named_params_fill_their_positions() {
+        let validator = create_validator("job_fifo:count=int(10)").unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::JobFifoVerified(JobFifoVerified { job_count: 10, .. })
+        ));
+
+        let validator = create_validator(
+            "rate_limit:path=string(/x),method=string(POST),total=int(100),\
+             window_ms=int(1000),expected_rejected=int(90)",
+        )
+        .unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::RateLimit(ref v) if v.path == "/x"
+                && v.method == "POST"
+                && v.requests == 100
+                && v.rejection_status == 429
+        ));
+
+        // positional params fill the slots the named ones leave free
+        let parsed =
+            effective_params("http_get:string(/echo),status=int(201),string(hello)").unwrap();
+        assert_eq!(parsed.param_as_int(1).unwrap(), 201);
+        assert_eq!(parsed.param_as_string(2).unwrap(), "hello");
+    }
+
+    #[test]
+    fn test_undeclared_named_params_are_rejected() {
+        assert_eq!(
+            create_validator("job_fifo:jobs=int(10)").err().unwrap(),
+            "job_fifo has no param named 'jobs'"
+        );
+        // aliases take positional params only
+        assert!(create_validator("http_path_root:status=int(200)").is_err());
+        assert_eq!(
+            create_validator("rate_limit:path=string(/x),total=int(100)")
+                .err()
+                .unwrap(),
+            "rate_limit param 1 ('method'): missing"
+        );
+    }
+
+    #[test]
+    fn test_wrong_type_optional_param_is_an_error() {
+        // used to silently fall back to the default
+        assert!(create_validator("worker_pool_concurrent:int(4),int(4),string(slow)").is_err());
+        assert!(create_validator("tcp_listening:int(4221),int(0)").is_err());
+        assert!(create_validator("http_get:string(/),int(200),int(1)").is_err());
+        assert!(create_validator("http_get:string(/),int(200),string(hi),int(1)").is_err());
+        assert!(create_validator("file_contents_match:string(a.txt),string(x),int(1)").is_err());
+        assert!(create_validator(r#"json_response:string(/),string({}),int(1)"#).is_err());
+    }
+
+    #[test]
+    fn test_context_port_overrides_module_default() {
+        let ctx = ValidationContext {
+            port: Some(3000),
+            ..Default::default()
+        };
+
+        let validator = create_validator_with("http_get:string(/),int(200)", &ctx).unwrap();
+        assert!(matches!(validator, RuntimeValidator::HttpGet(ref v) if v.port == 3000));
+
+        let validator = create_validator_with("json_response:string(/),string({})", &ctx).unwrap();
+        assert!(matches!(validator, RuntimeValidator::JsonResponse(ref v) if v.port == 3000));
+        let validator = create_validator("json_response:string(/),string({})").unwrap();
+        assert!(
+            matches!(validator, RuntimeValidator::JsonResponse(ref v) if v.port == DEFAULT_PORT)
+        );
+
+        let validator = create_validator_with("job_fifo:int(3)", &ctx).unwrap();
+        assert!(matches!(validator, RuntimeValidator::JobFifoVerified(ref v) if v.port == 3000));
+
+        // an explicit port param wins
+        let validator = create_validator_with("tcp_listening:int(4221)", &ctx).unwrap();
+        assert!(matches!(validator, RuntimeValidator::TcpListening(ref v) if v.port() == 4221));
+
+        let validator =
+            create_validator_with("http_get:string(/),int(200)", &ValidationContext::default())
+                .unwrap();
+        assert!(matches!(validator, RuntimeValidator::HttpGet(ref v) if v.port == 4221));
+    }
+
+    #[test]
+    fn test_create_job_dead_letter() {
+        let validator = create_validator("job_dead_letter:string(always_fail),int(3)").unwrap();
+        assert_eq!(validator.name(), "job_dead_letter");
+        assert!(matches!(
+            validator,
+            RuntimeValidator::JobDeadLetterVerified(v) if v.job_type == "always_fail" && v.max_retries == 3
+        ));
+    }
+
+    #[test]
+    fn test_create_job_fifo() {
+        let validator = create_validator("job_fifo:int(5)").unwrap();
+        assert_eq!(validator.name(), "job_fifo");
+        assert!(matches!(validator, RuntimeValidator::JobFifoVerified(v) if v.job_count == 5));
+        assert!(create_validator("job_fifo:int(1)").is_err());
+    }
+
+    #[test]
+    fn test_create_json_response() {
+        let validator = create_validator(
+            r#"json_response:string(/data),string({"a":1,"b":[1,2]}),string(subset)"#,
+        )
+        .unwrap();
+        assert_eq!(validator.name(), "json_response");
+        assert!(create_validator("json_response:string(/data),string({oops})").is_err());
+        assert!(
+            create_validator(r#"json_response:string(/data),string({}),string(loose)"#).is_err()
+        );
+    }
+
+    #[test]
+    fn test_create_endpoints() {
+        let validator =
+            create_validator("endpoints:string(/=200),string(/health=200),string(/missing=404)")
+                .unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::Endpoints(ref v) if v.routes.len() == 3 && v.routes[2].1 == 404
+        ));
+        assert!(create_validator("endpoints:string(/health)").is_err());
+    }
+
+    #[test]
+    fn test_create_http_pipeline() {
+        let validator =
+            create_validator("http_pipeline:string(/a),int(200),string(/b),int(404)").unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::HttpPipeline(ref v)
+                if v.requests == vec![("/a".to_string(), 200), ("/b".to_string(), 404)]
+        ));
+    }
+
+    #[test]
+    fn test_create_http_version() {
+        let validator = create_validator("http_version:string(/),string(HTTP/1.1)").unwrap();
+        assert_eq!(validator.name(), "http_version");
+    }
+
+    #[test]
+    fn test_create_http_cors() {
+        let validator =
+            create_validator("http_cors:string(/api),string(https://example.com),string(post)")
+                .unwrap();
+        assert_eq!(validator.name(), "http_cors");
+        assert!(matches!(
+            validator,
+            RuntimeValidator::HttpCors(ref v) if v.origin == "https://example.com" && v.method == "POST"
+        ));
+    }
+
+    #[test]
+    fn test_create_tcp_listening_with_host() {
+        let validator = create_validator("tcp_listening:int(4221),string(0.0.0.0)").unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::TcpListening(ref v) if v.host().is_unspecified() && v.port() == 4221
+        ));
+    }
+
+    #[test]
+    fn test_create_tcp_listening_invalid_host() {
+        let result = create_validator("tcp_listening:int(4221),string(localhost:80)");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_create_tcp_not_listening() {
+        let validator = create_validator("tcp_not_listening:int(4221)").unwrap();
+        assert_eq!(validator.name(), "tcp_not_listening");
+        assert!(matches!(
+            validator,
+            RuntimeValidator::TcpNotListening(ref v) if !v.expects_listening()
+        ));
+    }
+
     #[test]
     fn test_create_tcp_listening() {
         let validator = create_validator("tcp_listening:int(4221)").unwrap();
@@ -920,6 +1680,39 @@ mod tests {
         assert_eq!(validator.name(), "http_get");
     }
 
+    #[test]
+    fn test_create_http_get_with_body_match() {
+        let validator =
+            create_validator("http_get:string(/),int(200),string(Hello),string(ignore_case)")
+                .unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::HttpGet(ref v) if v.body_match == BodyMatch::IgnoreCase
+        ));
+    }
+
+    #[test]
+    fn test_create_http_get_invalid_body_match() {
+        let result = create_validator("http_get:string(/),int(200),string(Hello),string(fuzzy)");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_create_rate_limit_with_rejection_status() {
+        let validator =
+            create_validator("rate_limit:string(/),string(GET),int(10),int(100),int(5)").unwrap();
+        assert!(
+            matches!(validator, RuntimeValidator::RateLimit(ref v) if v.rejection_status == 429)
+        );
+
+        let validator =
+            create_validator("rate_limit:string(/),string(GET),int(10),int(100),int(5),int(503)")
+                .unwrap();
+        assert!(
+            matches!(validator, RuntimeValidator::RateLimit(ref v) if v.rejection_status == 503)
+        );
+    }
+
     #[test]
     fn test_create_http_header_present() {
         let validator =
@@ -927,6 +1720,17 @@ mod tests {
         assert_eq!(validator.name(), "http_header_present");
     }
 
+    #[test]
+    fn test_create_http_header_value_negated() {
+        let validator =
+            create_validator("http_header_value:string(Server),string(lux/1.0),bool(false)")
+                .unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::HttpHeaderValue(ref v) if !v.should_match
+        ));
+    }
+
     #[test]
     fn test_create_http_get_with_header() {
         let validator = create_validator(
@@ -942,6 +1746,27 @@ mod tests {
         assert_eq!(validator.name(), "concurrent_requests");
     }
 
+    #[test]
+    fn test_create_concurrent_access_max_failures() {
+        let strict = create_validator("concurrent_access:int(4221),string(/),int(10),int(100)");
+        assert!(matches!(
+            strict,
+            Ok(RuntimeValidator::ConcurrentAccess(ref v)) if v.max_failures == 0
+        ));
+
+        let tolerant =
+            create_validator("concurrent_access:int(4221),string(/),int(10),int(100),int(5)");
+        assert!(matches!(
+            tolerant,
+            Ok(RuntimeValidator::ConcurrentAccess(ref v))
+                if v.operations_per_client == 100 && v.max_failures == 5
+        ));
+
+        let mistyped =
+            create_validator("concurrent_access:int(4221),string(/),int(10),int(100),string(5)");
+        assert!(mistyped.is_err());
+    }
+
     #[test]
     fn test_create_http_post_file() {
         let validator = create_validator(
@@ -976,6 +1801,43 @@ mod tests {
         assert_eq!(validator.name(), "http_get_compressed");
     }
 
+    #[test]
+    fn test_create_file_contents_match_regex_mode() {
+        let validator = create_validator(
+            "file_contents_match:string(/tmp/test.txt),string(^ok$),string(regex)",
+        )
+        .unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::FileContentsMatch(ref v) if v.mode == FileMatchMode::Regex
+        ));
+    }
+
+    #[test]
+    fn test_create_file_contents_match_rejects_invalid_regex() {
+        let err =
+            create_validator("file_contents_match:string(/tmp/test.txt),string([a-),string(regex)")
+                .err()
+                .unwrap();
+        assert!(err.starts_with("invalid regex '[a-'"));
+
+        // only regex mode treats the expected content as a pattern
+        assert!(create_validator(
+            "file_contents_match:string(/tmp/test.txt),string([a-),string(exact)"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_create_file_sha256() {
+        let validator = create_validator(
+            "file_sha256:string(/tmp/bin),string(ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad)",
+        )
+        .unwrap();
+        assert_eq!(validator.name(), "file_sha256");
+        assert!(create_validator("file_sha256:string(/tmp/bin),string(abc)").is_err());
+    }
+
     #[test]
     fn test_create_file_contents_match() {
         let validator =
@@ -983,6 +1845,88 @@ mod tests {
         assert_eq!(validator.name(), "file_contents_match");
     }
 
+    #[test]
+    fn test_create_can_compile_with_language() {
+        let validator = create_validator("can_compile:string(rust),bool(false)").unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::CanCompile(ref v)
+                if v.language == Some(CompileLanguage::Rust) && !v.expected_success
+        ));
+    }
+
+    #[test]
+    fn test_create_can_compile_omitted_expectation_defaults_to_success() {
+        let validator = create_validator("can_compile:string(go)").unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::CanCompile(ref v)
+                if v.language == Some(CompileLanguage::Go) && v.expected_success
+        ));
+        assert!(create_validator("can_compile:string(go),string(yes)").is_err());
+    }
+
+    #[test]
+    fn test_create_http_request_omitted_params_use_defaults() {
+        let validator = create_validator("http_request:string(GET),string(/health)").unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::HttpRequestWithBody(ref v)
+                if v.body.is_none() && v.expected_status == 200
+        ));
+
+        let validator =
+            create_validator("http_request:string(POST),string(/jobs),string({}),int(201)")
+                .unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::HttpRequestWithBody(ref v)
+                if v.body.as_deref() == Some("{}") && v.expected_status == 201
+        ));
+
+        let validator =
+            create_validator("http_request:string(POST),string(/jobs),string(data)").unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::HttpRequestWithBody(ref v) if v.expected_status == 200
+        ));
+    }
+
+    #[test]
+    fn test_create_can_compile_invalid_language() {
+        let result = create_validator("can_compile:string(cobol),bool(true)");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_create_command_exit() {
+        let validator = create_validator("command_exit:string(make test)").unwrap();
+        assert_eq!(validator.name(), "command_exit");
+        assert!(matches!(
+            validator,
+            RuntimeValidator::CommandExit(ref v)
+                if v.command == "make test" && v.expected_exit_code == Some(0)
+        ));
+
+        let validator = create_validator("command_exit:string(go vet ./...),int(2)").unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::CommandExit(ref v) if v.expected_exit_code == Some(2)
+        ));
+    }
+
+    #[test]
+    fn test_create_command_output() {
+        let validator = create_validator("command_output:string(./run.sh),string(PASS)").unwrap();
+        assert_eq!(validator.name(), "command_output");
+        assert!(matches!(
+            validator,
+            RuntimeValidator::CommandExit(ref v)
+                if v.expected_exit_code.is_none() && v.output_contains.as_deref() == Some("PASS")
+        ));
+        assert!(create_validator("command_output:string(./run.sh)").is_err());
+    }
+
     #[test]
     fn test_create_can_compile() {
         let validator = create_validator("can_compile:bool(true)").unwrap();
@@ -1023,6 +1967,61 @@ mod tests {
         assert_eq!(validator.name(), "http_get");
     }
 
+    #[test]
+    fn test_alias_rewrites_params_for_canonical_validator() {
+        let validator =
+            create_validator("http_query_param:string(q),string(rust),string(found)").unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::HttpGet(HttpGetValidator {
+                ref path,
+                expected_status: 200,
+                expected_body: Some(ref body),
+                ..
+            }) if path == "/search?q=rust" && body == "found"
+        ));
+
+        let validator = create_validator("http_file_not_found:string(nope.txt),int(404)").unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::HttpGet(HttpGetValidator {
+                ref path,
+                expected_status: 404,
+                expected_body: None,
+                ..
+            }) if path == "/files/nope.txt"
+        ));
+    }
+
+    #[test]
+    fn test_alias_keeps_braces_in_params() {
+        let validator = create_validator("http_echo:string({x}),string({x})").unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::HttpGet(HttpGetValidator { ref path, .. }) if path == "/echo/{x}"
+        ));
+
+        // a value that looks like a later placeholder is not substituted again
+        let validator =
+            create_validator("http_query_param:string({1}),string(v),string(ok)").unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::HttpGet(HttpGetValidator { ref path, .. }) if path == "/search?{1}=v"
+        ));
+    }
+
+    #[test]
+    fn test_alias_errors_name_the_alias() {
+        let err = create_validator("http_echo:string(hello)").err().unwrap();
+        assert_eq!(err, "http_echo param 1: missing");
+
+        let err = create_validator("http_path_root:string(ok)").err().unwrap();
+        assert!(err.starts_with("http_path_root (alias of http_get): "));
+        assert!(err.contains("expected int, got string 'ok'"));
+
+        assert!(create_validator("http_file_get").is_err());
+    }
+
     #[test]
     fn test_create_http_path_unknown() {
         let validator = create_validator("http_path_unknown:int(404)").unwrap();
@@ -1154,6 +2153,18 @@ mod tests {
         assert_eq!(validator.name(), "http_get_compressed");
     }
 
+    #[test]
+    fn test_create_http_gzip_body() {
+        let validator =
+            create_validator("http_gzip_body:string(/compressed),string(hello)").unwrap();
+        assert!(matches!(
+            validator,
+            RuntimeValidator::HttpGetCompressed(ref v)
+                if v.expected_body.as_deref() == Some("hello")
+        ));
+        assert!(create_validator("http_gzip_body:string(/compressed)").is_err());
+    }
+
     #[test]
     fn test_create_http_pipelining() {
         let validator = create_validator("http_pipelining:int(3)").unwrap();
@@ -1,11 +1,44 @@
-use crate::tasks::TestCase;
-use std::path::Path;
+use crate::tasks::{TestCase, ValidationContext};
+use crate::ui::format_bytes;
+use regex::Regex;
+use sha2::{Digest, Sha256};
 use tokio::fs;
 
+/// How file contents are compared against the expected value
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FileMatchMode {
+    /// byte-for-byte equality of the UTF-8 text
+    Exact,
+    /// equality after trimming surrounding whitespace
+    #[default]
+    Trimmed,
+    /// expected value is a regex that must match somewhere in the file
+    Regex,
+}
+
+impl std::str::FromStr for FileMatchMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "exact" => Ok(FileMatchMode::Exact),
+            "trimmed" => Ok(FileMatchMode::Trimmed),
+            "regex" => Ok(FileMatchMode::Regex),
+            _ => Err(format!(
+                "unknown file match mode '{}'. supported: exact, trimmed, regex",
+                s
+            )),
+        }
+    }
+}
+
 /// Validator: check if file contents match expected value
 pub struct FileContentsMatchValidator {
     pub path: String,
     pub expected_content: String,
+    pub mode: FileMatchMode,
+    /// compiled once in `with_mode`, so a bad pattern is rejected before running
+    regex: Option<Regex>,
 }
 
 impl FileContentsMatchValidator {
@@ -13,42 +46,143 @@ impl FileContentsMatchValidator {
         Self {
             path: path.to_string(),
             expected_content: expected_content.to_string(),
+            mode: FileMatchMode::default(),
+            regex: None,
         }
     }
 
-    pub async fn validate(&self) -> Result<TestCase, String> {
-        let path = Path::new(&self.path);
+    /// fails when regex mode is asked for and the expected content isn't a valid pattern
+    pub fn with_mode(mut self, mode: FileMatchMode) -> Result<Self, String> {
+        self.regex = match mode {
+            FileMatchMode::Regex => Some(
+                Regex::new(&self.expected_content)
+                    .map_err(|e| format!("invalid regex '{}': {}", self.expected_content, e))?,
+            ),
+            FileMatchMode::Exact | FileMatchMode::Trimmed => None,
+        };
+        self.mode = mode;
+        Ok(self)
+    }
+
+    pub async fn validate(&self, ctx: &ValidationContext) -> Result<TestCase, String> {
+        let path = ctx.resolve(&self.path);
 
         if !path.exists() {
-            return Ok(TestCase {
-                name: format!("file {} exists", self.path),
-                result: Err(format!("file '{}' does not exist", self.path)),
-            });
+            return Ok(TestCase::new(
+                format!("file {} exists", self.path),
+                Err(format!("file '{}' does not exist", self.path)),
+            ));
         }
 
-        let content = fs::read_to_string(path)
+        let bytes = fs::read(&path)
             .await
             .map_err(|e| format!("failed to read '{}': {}", self.path, e))?;
 
-        let content_trimmed = content.trim();
-        let expected_trimmed = self.expected_content.trim();
+        // text modes need UTF-8; binary files should use file_sha256 instead
+        let content = match String::from_utf8(bytes) {
+            Ok(content) => content,
+            Err(_) => {
+                return Ok(TestCase::new(
+                    format!("file '{}' content matches", self.path),
+                    Err(format!(
+                        "file '{}' is not valid UTF-8, use file_sha256 for binary files",
+                        self.path
+                    )),
+                ));
+            }
+        };
+
+        let result = match self.mode {
+            FileMatchMode::Regex => {
+                let re = self
+                    .regex
+                    .as_ref()
+                    .ok_or_else(|| format!("regex '{}' was not compiled", self.expected_content))?;
+                if re.is_match(&content) {
+                    Ok(format!(
+                        "file '{}' matches /{}/",
+                        self.path, self.expected_content
+                    ))
+                } else {
+                    let content_preview: String = content.trim().chars().take(50).collect();
+                    Err(format!(
+                        "content does not match /{}/:\n  got: '{}...'",
+                        self.expected_content, content_preview
+                    ))
+                }
+            }
+            FileMatchMode::Exact | FileMatchMode::Trimmed => {
+                let (content_cmp, expected_cmp) = if self.mode == FileMatchMode::Trimmed {
+                    (content.trim(), self.expected_content.trim())
+                } else {
+                    (content.as_str(), self.expected_content.as_str())
+                };
+
+                if content_cmp == expected_cmp {
+                    Ok(format!("file '{}' content matches expected", self.path))
+                } else {
+                    // show preview of mismatch
+                    let content_preview: String = content_cmp.chars().take(50).collect();
+                    let expected_preview: String = expected_cmp.chars().take(50).collect();
+                    Err(format!(
+                        "content mismatch:\n  expected: '{}...'\n  got: '{}...'",
+                        expected_preview, content_preview
+                    ))
+                }
+            }
+        };
 
-        let result = if content_trimmed == expected_trimmed {
-            Ok(format!("file '{}' content matches expected", self.path))
+        Ok(TestCase::new(
+            format!("file '{}' content matches", self.path),
+            result,
+        ))
+    }
+}
+
+/// Validator: check the SHA-256 digest of a file's raw bytes
+pub struct FileSha256Validator {
+    pub path: String,
+    pub expected_hex: String,
+}
+
+impl FileSha256Validator {
+    pub fn new(path: &str, expected_hex: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            expected_hex: expected_hex.trim().to_lowercase(),
+        }
+    }
+
+    pub async fn validate(&self, ctx: &ValidationContext) -> Result<TestCase, String> {
+        let name = format!("file '{}' sha256 matches", self.path);
+
+        let bytes = match fs::read(ctx.resolve(&self.path)).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                return Ok(TestCase::new(
+                    name,
+                    Err(format!("failed to read '{}': {}", self.path, e)),
+                ));
+            }
+        };
+
+        let actual = hex::encode(Sha256::digest(&bytes));
+        let result = if actual == self.expected_hex {
+            Ok(format!(
+                "sha256 {} ({})",
+                actual,
+                format_bytes(bytes.len() as u64)
+            ))
         } else {
-            // show preview of mismatch
-            let content_preview: String = content_trimmed.chars().take(50).collect();
-            let expected_preview: String = expected_trimmed.chars().take(50).collect();
             Err(format!(
-                "content mismatch:\n  expected: '{}...'\n  got: '{}...'",
-                expected_preview, content_preview
+                "sha256 mismatch ({}):\n  expected: {}\n  got: {}",
+                format_bytes(bytes.len() as u64),
+                self.expected_hex,
+                actual
             ))
         };
 
-        Ok(TestCase {
-            name: format!("file '{}' content matches", self.path),
-            result,
-        })
+        Ok(TestCase::new(name, result))
     }
 }
 
@@ -58,6 +192,19 @@ mod tests {
     use std::io::Write;
     use tempfile::NamedTempFile;
 
+    #[tokio::test]
+    async fn test_relative_path_resolves_against_project_path() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("answer.txt"), "42\n").unwrap();
+
+        let ctx = ValidationContext {
+            project_path: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let validator = FileContentsMatchValidator::new("answer.txt", "42");
+        assert!(validator.validate(&ctx).await.unwrap().passed());
+    }
+
     #[tokio::test]
     async fn test_file_contents_match_success() {
         let mut file = NamedTempFile::new().unwrap();
@@ -65,7 +212,10 @@ mod tests {
 
         let validator =
             FileContentsMatchValidator::new(file.path().to_str().unwrap(), "hello world");
-        let result = validator.validate().await.unwrap();
+        let result = validator
+            .validate(&ValidationContext::default())
+            .await
+            .unwrap();
         assert!(result.passed());
     }
 
@@ -76,15 +226,139 @@ mod tests {
 
         let validator =
             FileContentsMatchValidator::new(file.path().to_str().unwrap(), "goodbye world");
-        let result = validator.validate().await.unwrap();
+        let result = validator
+            .validate(&ValidationContext::default())
+            .await
+            .unwrap();
         assert!(!result.passed());
     }
 
     #[tokio::test]
     async fn test_file_not_exists() {
         let validator = FileContentsMatchValidator::new("/nonexistent/path.txt", "content");
-        let result = validator.validate().await.unwrap();
+        let result = validator
+            .validate(&ValidationContext::default())
+            .await
+            .unwrap();
         assert!(!result.passed());
         assert!(result.message().contains("does not exist"));
     }
+
+    #[tokio::test]
+    async fn test_file_contents_exact_mode_keeps_whitespace() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "hello world").unwrap();
+
+        let validator =
+            FileContentsMatchValidator::new(file.path().to_str().unwrap(), "hello world")
+                .with_mode(FileMatchMode::Exact)
+                .unwrap();
+        assert!(!validator
+            .validate(&ValidationContext::default())
+            .await
+            .unwrap()
+            .passed());
+
+        let validator =
+            FileContentsMatchValidator::new(file.path().to_str().unwrap(), "hello world\n")
+                .with_mode(FileMatchMode::Exact)
+                .unwrap();
+        assert!(validator
+            .validate(&ValidationContext::default())
+            .await
+            .unwrap()
+            .passed());
+    }
+
+    #[tokio::test]
+    async fn test_file_contents_regex_mode() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "listening on port 4221").unwrap();
+
+        let validator =
+            FileContentsMatchValidator::new(file.path().to_str().unwrap(), r"port \d{4}")
+                .with_mode(FileMatchMode::Regex)
+                .unwrap();
+        assert!(validator
+            .validate(&ValidationContext::default())
+            .await
+            .unwrap()
+            .passed());
+
+        let validator = FileContentsMatchValidator::new(file.path().to_str().unwrap(), r"^error")
+            .with_mode(FileMatchMode::Regex)
+            .unwrap();
+        assert!(!validator
+            .validate(&ValidationContext::default())
+            .await
+            .unwrap()
+            .passed());
+    }
+
+    #[tokio::test]
+    async fn test_file_contents_invalid_regex() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "anything").unwrap();
+
+        let err = FileContentsMatchValidator::new(file.path().to_str().unwrap(), "(unclosed")
+            .with_mode(FileMatchMode::Regex)
+            .err()
+            .unwrap();
+        assert!(err.starts_with("invalid regex '(unclosed'"));
+    }
+
+    #[tokio::test]
+    async fn test_file_contents_rejects_binary() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&[0xff, 0xfe, 0x00]).unwrap();
+
+        let validator = FileContentsMatchValidator::new(file.path().to_str().unwrap(), "x");
+        let result = validator
+            .validate(&ValidationContext::default())
+            .await
+            .unwrap();
+        assert!(result.message().contains("not valid UTF-8"));
+    }
+
+    #[tokio::test]
+    async fn test_file_sha256_match() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"abc").unwrap();
+
+        let validator = FileSha256Validator::new(
+            file.path().to_str().unwrap(),
+            "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD",
+        );
+        assert!(validator
+            .validate(&ValidationContext::default())
+            .await
+            .unwrap()
+            .passed());
+    }
+
+    #[tokio::test]
+    async fn test_file_sha256_mismatch() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&[0x00, 0x01, 0x02]).unwrap();
+
+        let validator = FileSha256Validator::new(
+            file.path().to_str().unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        );
+        let result = validator
+            .validate(&ValidationContext::default())
+            .await
+            .unwrap();
+        assert!(!result.passed());
+        assert!(result.message().contains("sha256 mismatch (3 B)"));
+    }
+
+    #[test]
+    fn test_file_match_mode_from_str() {
+        assert_eq!(
+            "regex".parse::<FileMatchMode>().unwrap(),
+            FileMatchMode::Regex
+        );
+        assert!("glob".parse::<FileMatchMode>().is_err());
+    }
 }
@@ -1,36 +1,80 @@
+use crate::api::user_agent;
 use crate::tasks::TestCase;
+use crate::ui::{format_bytes, format_duration};
 use serde_json::Value as JsonValue;
-use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::net::TcpStream;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 use tokio::time::{timeout, Duration};
 
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
-const DEFAULT_PORT: u16 = 4221;
+
+/// body characters kept in a `--detailed` transcript
+const TRANSCRIPT_BODY_PREVIEW: usize = 500;
+
+pub(super) const DEFAULT_PORT: u16 = 4221;
 
 /// HTTP response parsed into parts
 #[derive(Debug)]
 pub struct HttpResponse {
+    pub version: String,
     pub status_code: u16,
     pub status_text: String,
     pub headers: Vec<(String, String)>,
     pub body: String,
+    pub raw_body: Vec<u8>, // undecoded body bytes, for compressed responses
+    pub incomplete: bool,  // the read timed out before the server closed the connection
 }
 
 impl HttpResponse {
+    /// request line, status line, headers and a body preview, for `--detailed` output
+    pub fn transcript(&self, method: &str, path: &str) -> String {
+        let mut lines = vec![
+            format!("> {} {}", method, path),
+            format!(
+                "< {} {} {}",
+                self.version, self.status_code, self.status_text
+            )
+            .trim_end()
+            .to_string(),
+        ];
+        lines.extend(
+            self.headers
+                .iter()
+                .map(|(key, value)| format!("< {}: {}", key, value)),
+        );
+        if !self.body.is_empty() {
+            lines.push("<".to_string());
+            let preview: String = self.body.chars().take(TRANSCRIPT_BODY_PREVIEW).collect();
+            let truncated = preview.len() < self.body.len();
+            lines.extend(preview.lines().map(|line| format!("< {}", line)));
+            if truncated {
+                lines.push("< ...".to_string());
+            }
+        }
+        if self.incomplete {
+            lines.push("(read timed out; the response may be truncated)".to_string());
+        }
+        lines.join("\n")
+    }
+
     pub fn parse(raw: &str) -> Result<Self, String> {
+        if !raw.is_empty() && !raw.starts_with("HTTP/") {
+            return Err(format!(
+                "server did not speak HTTP (got: {})",
+                preview_bytes(raw.as_bytes())
+            ));
+        }
+
         let mut lines = raw.lines();
 
-        // parse status line: HTTP/1.1 200 OK
         let status_line = lines.next().ok_or("empty response")?;
-        let parts: Vec<&str> = status_line.splitn(3, ' ').collect();
-        if parts.len() < 2 {
-            return Err(format!("invalid status line: {}", status_line));
-        }
-
-        let status_code: u16 = parts[1]
-            .parse()
-            .map_err(|_| format!("invalid status code: {}", parts[1]))?;
-        let status_text = parts.get(2).unwrap_or(&"").to_string();
+        let (version, status_code, status_text) = parse_status_line(status_line)?;
 
         // parse headers until empty line
         let mut headers = Vec::new();
@@ -46,11 +90,15 @@ impl HttpResponse {
         // rest is body
         let body: String = lines.collect::<Vec<_>>().join("\n");
 
+        let raw_body = body.as_bytes().to_vec();
         Ok(HttpResponse {
+            version: version.to_string(),
             status_code,
-            status_text,
+            status_text: status_text.to_string(),
             headers,
             body,
+            raw_body,
+            incomplete: false,
         })
     }
 
@@ -67,28 +115,57 @@ impl HttpResponse {
     }
 }
 
-/// Send an HTTP request and get the response
-pub async fn http_request(
-    port: u16,
-    method: &str,
-    path: &str,
-    headers: &[(&str, &str)],
-    body: Option<&str>,
-) -> Result<HttpResponse, String> {
-    let addr = format!("127.0.0.1:{}", port);
+/// bytes shown when echoing something that isn't HTTP
+const NON_HTTP_PREVIEW: usize = 40;
+
+/// first few bytes, printable ascii kept and everything else escaped (`\x00`, `\r`)
+fn preview_bytes(bytes: &[u8]) -> String {
+    let mut preview: String = bytes
+        .iter()
+        .take(NON_HTTP_PREVIEW)
+        .flat_map(|b| std::ascii::escape_default(*b))
+        .map(char::from)
+        .collect();
+    if bytes.len() > NON_HTTP_PREVIEW {
+        preview.push_str("...");
+    }
+    format!("\"{}\"", preview)
+}
 
-    let connect_result = timeout(DEFAULT_TIMEOUT, TcpStream::connect(&addr)).await;
-    let mut stream = match connect_result {
-        Ok(Ok(s)) => s,
-        Ok(Err(e)) => return Err(format!("connection failed: {}", e)),
-        Err(_) => return Err("connection timeout".to_string()),
-    };
+/// split `HTTP/1.1 200 OK` into its parts; the reason phrase is optional and kept verbatim
+fn parse_status_line(line: &str) -> Result<(&str, u16, &str), String> {
+    let line = line.trim_end_matches('\r');
+    let (version, rest) = line
+        .split_once(' ')
+        .ok_or_else(|| format!("invalid status line: {}", preview_bytes(line.as_bytes())))?;
+    let (code, reason) = rest.split_once(' ').unwrap_or((rest, ""));
+
+    if code.len() != 3 || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!(
+            "invalid status code: {}",
+            preview_bytes(code.as_bytes())
+        ));
+    }
+    let status_code = code
+        .parse()
+        .map_err(|_| format!("invalid status code: {}", code))?;
+
+    Ok((version, status_code, reason))
+}
 
-    // build request
+/// raw HTTP/1.1 request text; identifies luxctl unless the caller sets its own User-Agent
+fn build_request(method: &str, path: &str, headers: &[(&str, &str)], body: Option<&str>) -> String {
     let mut request = format!("{} {} HTTP/1.1\r\n", method, path);
     request.push_str("Host: 127.0.0.1\r\n");
     request.push_str("Connection: close\r\n");
 
+    if !headers
+        .iter()
+        .any(|(key, _)| key.eq_ignore_ascii_case("user-agent"))
+    {
+        request.push_str(&format!("User-Agent: {}\r\n", user_agent()));
+    }
+
     for (key, value) in headers {
         request.push_str(&format!("{}: {}\r\n", key, value));
     }
@@ -103,23 +180,69 @@ pub async fn http_request(
         request.push_str(body_content);
     }
 
+    request
+}
+
+/// Send an HTTP request and get the response
+pub async fn http_request(
+    port: u16,
+    method: &str,
+    path: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> Result<HttpResponse, String> {
+    let addr = format!("127.0.0.1:{}", port);
+
+    let connect_result = timeout(DEFAULT_TIMEOUT, TcpStream::connect(&addr)).await;
+    let mut stream = match connect_result {
+        Ok(Ok(s)) => s,
+        Ok(Err(e)) => return Err(format!("connection failed: {}", e)),
+        Err(_) => return Err("connection timeout".to_string()),
+    };
+
+    let request = build_request(method, path, headers, body);
+
     stream
         .write_all(request.as_bytes())
         .await
         .map_err(|e| format!("failed to send request: {}", e))?;
 
-    // read response with timeout
+    read_response(&mut stream, DEFAULT_TIMEOUT).await
+}
+
+/// read until the server closes the connection; on timeout, parse whatever arrived
+async fn read_response<R>(reader: &mut R, read_timeout: Duration) -> Result<HttpResponse, String>
+where
+    R: AsyncReadExt + Unpin,
+{
     let mut response = Vec::new();
-    let read_result = timeout(DEFAULT_TIMEOUT, stream.read_to_end(&mut response)).await;
+    let read_result = timeout(read_timeout, reader.read_to_end(&mut response)).await;
 
-    match read_result {
-        Ok(Ok(_)) => {}
+    let timed_out = match read_result {
+        Ok(Ok(_)) => false,
         Ok(Err(e)) => return Err(format!("failed to read response: {}", e)),
-        Err(_) => return Err("read timeout".to_string()),
-    }
+        Err(_) if response.is_empty() => return Err("read timeout".to_string()),
+        Err(_) => true,
+    };
 
     let response_str = String::from_utf8_lossy(&response);
-    HttpResponse::parse(&response_str)
+    let mut parsed = HttpResponse::parse(&response_str).map_err(|err| {
+        if timed_out {
+            format!(
+                "read timeout after {} bytes, response may be incomplete: {}",
+                response.len(),
+                err
+            )
+        } else {
+            err
+        }
+    })?;
+    // the lossy string mangles binary bodies; keep the bytes as sent
+    if let Some(start) = response.windows(4).position(|w| w == b"\r\n\r\n") {
+        parsed.raw_body = response[start + 4..].to_vec();
+    }
+    parsed.incomplete = timed_out;
+    Ok(parsed)
 }
 
 /// Validator: check if server responds with expected status code
@@ -151,10 +274,62 @@ impl HttpStatusValidator {
             ))
         };
 
-        Ok(TestCase {
-            name: format!("http response status {}", self.expected_status),
+        Ok(TestCase::new(
+            format!("http response status {}", self.expected_status),
             result,
-        })
+        )
+        .with_details(response.transcript("GET", "/")))
+    }
+}
+
+/// How a response body is compared against an expected body
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BodyMatch {
+    /// byte-for-byte equality
+    Exact,
+    /// equality after trimming surrounding whitespace
+    #[default]
+    Trimmed,
+    /// trimmed, case-insensitive equality
+    IgnoreCase,
+    /// body contains the expected string anywhere
+    Contains,
+}
+
+impl BodyMatch {
+    pub fn matches(&self, expected: &str, body: &str) -> bool {
+        match self {
+            BodyMatch::Exact => body == expected,
+            BodyMatch::Trimmed => body.trim() == expected,
+            BodyMatch::IgnoreCase => body.trim().to_lowercase() == expected.to_lowercase(),
+            BodyMatch::Contains => body.contains(expected),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BodyMatch::Exact => "exact",
+            BodyMatch::Trimmed => "trimmed",
+            BodyMatch::IgnoreCase => "ignore_case",
+            BodyMatch::Contains => "contains",
+        }
+    }
+}
+
+impl std::str::FromStr for BodyMatch {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "exact" => Ok(BodyMatch::Exact),
+            "trimmed" => Ok(BodyMatch::Trimmed),
+            "ignore_case" => Ok(BodyMatch::IgnoreCase),
+            "contains" => Ok(BodyMatch::Contains),
+            _ => Err(format!(
+                "unknown body match mode '{}'. supported: exact, trimmed, ignore_case, contains",
+                s
+            )),
+        }
     }
 }
 
@@ -164,6 +339,7 @@ pub struct HttpGetValidator {
     pub path: String,
     pub expected_status: u16,
     pub expected_body: Option<String>,
+    pub body_match: BodyMatch,
 }
 
 impl HttpGetValidator {
@@ -173,13 +349,20 @@ impl HttpGetValidator {
             path: path.to_string(),
             expected_status,
             expected_body,
+            body_match: BodyMatch::default(),
         }
     }
 
+    pub fn with_body_match(mut self, body_match: BodyMatch) -> Self {
+        self.body_match = body_match;
+        self
+    }
+
     pub async fn validate(&self) -> Result<TestCase, String> {
         let response = http_request(self.port, "GET", &self.path, &[], None).await?;
 
         let mut errors = Vec::new();
+        let mut mismatch = None;
 
         if response.status_code != self.expected_status {
             errors.push(format!(
@@ -189,12 +372,27 @@ impl HttpGetValidator {
         }
 
         if let Some(ref expected) = self.expected_body {
-            let body_trimmed = response.body.trim();
-            if body_trimmed != expected {
-                errors.push(format!(
-                    "expected body '{}', got '{}'",
-                    expected, body_trimmed
-                ));
+            if !self.body_match.matches(expected, &response.body) {
+                let got = match self.body_match {
+                    BodyMatch::Exact => response.body.as_str(),
+                    _ => response.body.trim(),
+                };
+                if expected.contains('\n') || got.contains('\n') {
+                    // multi-line bodies are shown as a diff instead
+                    errors.push(format!(
+                        "body does not match expected ({})",
+                        self.body_match.as_str()
+                    ));
+                } else {
+                    let expectation = match self.body_match {
+                        BodyMatch::Trimmed => "expected body",
+                        BodyMatch::Exact => "expected exact body",
+                        BodyMatch::IgnoreCase => "expected body (ignoring case)",
+                        BodyMatch::Contains => "expected body containing",
+                    };
+                    errors.push(format!("{} '{}', got '{}'", expectation, expected, got));
+                }
+                mismatch = Some((expected.clone(), got.to_string()));
             }
         }
 
@@ -207,9 +405,15 @@ impl HttpGetValidator {
             Err(errors.join("; "))
         };
 
-        Ok(TestCase {
-            name: format!("GET {} returns {}", self.path, self.expected_status),
+        let test_case = TestCase::new(
+            format!("GET {} returns {}", self.path, self.expected_status),
             result,
+        )
+        .with_details(response.transcript("GET", &self.path));
+
+        Ok(match mismatch {
+            Some((expected, got)) => test_case.with_mismatch(&expected, &got),
+            None => test_case,
         })
     }
 }
@@ -262,8 +466,8 @@ impl HttpHeaderPresentValidator {
             ))
         };
 
-        Ok(TestCase {
-            name: format!(
+        Ok(TestCase::new(
+            format!(
                 "header '{}' {}",
                 self.header_name,
                 if self.should_exist {
@@ -273,16 +477,19 @@ impl HttpHeaderPresentValidator {
                 }
             ),
             result,
-        })
+        )
+        .with_details(response.transcript("GET", &self.path)))
     }
 }
 
 /// Validator: check header has specific value
+/// when should_match is false, passes unless the header equals the forbidden value
 pub struct HttpHeaderValueValidator {
     pub port: u16,
     pub path: String,
     pub header_name: String,
     pub expected_value: String,
+    pub should_match: bool,
 }
 
 impl HttpHeaderValueValidator {
@@ -292,28 +499,57 @@ impl HttpHeaderValueValidator {
             path: "/".to_string(),
             header_name: header_name.to_string(),
             expected_value: expected_value.to_string(),
+            should_match: true,
         }
     }
 
+    pub fn with_should_match(mut self, should_match: bool) -> Self {
+        self.should_match = should_match;
+        self
+    }
+
     pub async fn validate(&self) -> Result<TestCase, String> {
         let response = http_request(self.port, "GET", &self.path, &[], None).await?;
 
-        let result = match response.get_header(&self.header_name) {
-            Some(value) if value == self.expected_value => Ok(format!(
-                "header '{}' has value '{}'",
-                self.header_name, self.expected_value
-            )),
-            Some(value) => Err(format!(
-                "header '{}' expected '{}', got '{}'",
-                self.header_name, self.expected_value, value
-            )),
-            None => Err(format!("header '{}' not found", self.header_name)),
+        let actual = response.get_header(&self.header_name);
+        let result = if self.should_match {
+            match actual {
+                Some(value) if value == self.expected_value => Ok(format!(
+                    "header '{}' has value '{}'",
+                    self.header_name, self.expected_value
+                )),
+                Some(value) => Err(format!(
+                    "header '{}' expected '{}', got '{}'",
+                    self.header_name, self.expected_value, value
+                )),
+                None => Err(format!("header '{}' not found", self.header_name)),
+            }
+        } else {
+            match actual {
+                Some(value) if value == self.expected_value => Err(format!(
+                    "header '{}' must not be '{}'",
+                    self.header_name, self.expected_value
+                )),
+                Some(value) => Ok(format!(
+                    "header '{}' is '{}', not '{}'",
+                    self.header_name, value, self.expected_value
+                )),
+                None => Ok(format!(
+                    "header '{}' is absent, not '{}'",
+                    self.header_name, self.expected_value
+                )),
+            }
         };
 
-        Ok(TestCase {
-            name: format!("header '{}' = '{}'", self.header_name, self.expected_value),
+        let op = if self.should_match { "=" } else { "!=" };
+        Ok(TestCase::new(
+            format!(
+                "header '{}' {} '{}'",
+                self.header_name, op, self.expected_value
+            ),
             result,
-        })
+        )
+        .with_details(response.transcript("GET", &self.path)))
     }
 }
 
@@ -378,13 +614,14 @@ impl HttpGetWithHeaderValidator {
             Err(errors.join("; "))
         };
 
-        Ok(TestCase {
-            name: format!(
+        Ok(TestCase::new(
+            format!(
                 "GET {} with {}: {}",
                 self.path, self.request_header.0, self.request_header.1
             ),
             result,
-        })
+        )
+        .with_details(response.transcript("GET", &self.path)))
     }
 }
 
@@ -394,8 +631,12 @@ pub struct ConcurrentRequestsValidator {
     pub num_connections: u32,
     pub path: String,
     pub expected_status: u16,
+    /// requests allowed in flight at once, so huge counts don't open every socket together
+    pub max_in_flight: usize,
 }
 
+const DEFAULT_MAX_IN_FLIGHT: usize = 256;
+
 impl ConcurrentRequestsValidator {
     pub fn new(num_connections: u32, path: &str, expected_status: u16) -> Self {
         Self {
@@ -403,46 +644,56 @@ impl ConcurrentRequestsValidator {
             num_connections,
             path: path.to_string(),
             expected_status,
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
         }
     }
 
-    pub async fn validate(&self) -> Result<TestCase, String> {
-        let mut handles = Vec::new();
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = max_in_flight.max(1);
+        self
+    }
 
-        for i in 0..self.num_connections {
-            let port = self.port;
-            let path = self.path.clone();
-            let expected = self.expected_status;
+    pub async fn validate(&self) -> Result<TestCase, String> {
+        let port = self.port;
+        let path = Arc::new(self.path.clone());
+        let expected = self.expected_status;
 
-            let handle = tokio::spawn(async move {
+        let outcomes = run_bounded(self.num_connections, self.max_in_flight, move |i| {
+            let path = Arc::clone(&path);
+            async move {
                 let response = http_request(port, "GET", &path, &[], None).await?;
                 if response.status_code == expected {
-                    Ok(i)
+                    Ok(())
                 } else {
                     Err(format!(
                         "connection {} got status {} instead of {}",
                         i, response.status_code, expected
                     ))
                 }
-            });
-            handles.push(handle);
-        }
+            }
+        })
+        .await;
 
-        let mut successes = 0;
+        let mut latencies = Vec::new();
         let mut errors = Vec::new();
-
-        for handle in handles {
-            match handle.await {
-                Ok(Ok(_)) => successes += 1,
-                Ok(Err(e)) => errors.push(e),
-                Err(e) => errors.push(format!("task failed: {}", e)),
+        for (outcome, latency) in outcomes {
+            match outcome {
+                Ok(()) => latencies.push(latency),
+                Err(e) => errors.push(e),
             }
         }
+        let successes = latencies.len() as u32;
+        let latency_summary = format!(
+            "p50 {}, p95 {}, p99 {}",
+            format_duration(percentile(&mut latencies, 50)),
+            format_duration(percentile(&mut latencies, 95)),
+            format_duration(percentile(&mut latencies, 99))
+        );
 
         let result = if successes == self.num_connections {
             Ok(format!(
-                "all {} concurrent requests succeeded",
-                self.num_connections
+                "all {} concurrent requests succeeded ({})",
+                self.num_connections, latency_summary
             ))
         } else {
             // limit error output to first 3 errors
@@ -455,19 +706,60 @@ impl ConcurrentRequestsValidator {
                     errors.len() - 3
                 )
             };
+            let rate = if self.num_connections == 0 {
+                0.0
+            } else {
+                successes as f64 * 100.0 / self.num_connections as f64
+            };
             Err(format!(
-                "{}/{} requests succeeded. {}",
-                successes, self.num_connections, error_summary
+                "{}/{} requests succeeded ({:.1}%, {}). {}",
+                successes, self.num_connections, rate, latency_summary, error_summary
             ))
         };
 
-        Ok(TestCase {
-            name: format!("{} concurrent requests", self.num_connections),
+        Ok(TestCase::new(
+            format!("{} concurrent requests", self.num_connections),
             result,
-        })
+        ))
     }
 }
 
+/// run `total` requests with at most `cap` in flight, returning each outcome with its latency
+async fn run_bounded<F, Fut>(
+    total: u32,
+    cap: usize,
+    request: F,
+) -> Vec<(Result<(), String>, Duration)>
+where
+    F: Fn(u32) -> Fut,
+    Fut: Future<Output = Result<(), String>> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(cap.max(1)));
+    let mut set = JoinSet::new();
+
+    for i in 0..total {
+        // waiting for a permit before spawning also bounds the number of tasks
+        let permit = match Arc::clone(&semaphore).acquire_owned().await {
+            Ok(permit) => permit,
+            Err(_) => break,
+        };
+        let fut = request(i);
+        set.spawn(async move {
+            let started = Instant::now();
+            let outcome = fut.await;
+            drop(permit);
+            (outcome, started.elapsed())
+        });
+    }
+
+    let mut outcomes = Vec::with_capacity(total as usize);
+    while let Some(joined) = set.join_next().await {
+        outcomes
+            .push(joined.unwrap_or_else(|e| (Err(format!("task failed: {}", e)), Duration::ZERO)));
+    }
+    outcomes
+}
+
 /// Validator: POST request with file content
 pub struct HttpPostFileValidator {
     pub port: u16,
@@ -501,10 +793,11 @@ impl HttpPostFileValidator {
             ))
         };
 
-        Ok(TestCase {
-            name: format!("POST {} returns {}", self.path, self.expected_status),
+        Ok(TestCase::new(
+            format!("POST {} returns {}", self.path, self.expected_status),
             result,
-        })
+        )
+        .with_details(response.transcript("POST", &self.path)))
     }
 }
 
@@ -530,7 +823,8 @@ impl HttpGetFileValidator {
         let result = if response.status_code == self.expected_status {
             let content_info = response
                 .get_header("content-length")
-                .map(|len| format!(" ({} bytes)", len))
+                .and_then(|len| len.parse::<u64>().ok())
+                .map(|len| format!(" ({})", format_bytes(len)))
                 .unwrap_or_default();
             Ok(format!(
                 "GET {} returned {}{} OK",
@@ -543,10 +837,11 @@ impl HttpGetFileValidator {
             ))
         };
 
-        Ok(TestCase {
-            name: format!("GET file {} returns {}", self.path, self.expected_status),
+        Ok(TestCase::new(
+            format!("GET file {} returns {}", self.path, self.expected_status),
             result,
-        })
+        )
+        .with_details(response.transcript("GET", &self.path)))
     }
 }
 
@@ -555,6 +850,7 @@ pub struct HttpGetCompressedValidator {
     pub port: u16,
     pub path: String,
     pub encoding: String,
+    pub expected_body: Option<String>,
 }
 
 impl HttpGetCompressedValidator {
@@ -563,20 +859,39 @@ impl HttpGetCompressedValidator {
             port: DEFAULT_PORT,
             path: path.to_string(),
             encoding: encoding.to_string(),
+            expected_body: None,
         }
     }
 
+    /// also assert the decompressed body (trimmed) equals `expected`
+    pub fn with_expected_body(mut self, expected: &str) -> Self {
+        self.expected_body = Some(expected.to_string());
+        self
+    }
+
     pub async fn validate(&self) -> Result<TestCase, String> {
-        let headers = [("Accept-Encoding", self.encoding.as_str())];
+        let name = match self.expected_body {
+            Some(ref expected) => format!(
+                "GET {} with compression {} decodes to '{}'",
+                self.path, self.encoding, expected
+            ),
+            None => format!("GET {} with compression {}", self.path, self.encoding),
+        };
+
+        let coding = match self.encoding.parse::<ContentCoding>() {
+            Ok(coding) => coding,
+            Err(e) => return Ok(TestCase::new(name, Err(e))),
+        };
+
+        let headers = [("Accept-Encoding", coding.as_str())];
         let response = http_request(self.port, "GET", &self.path, &headers, None).await?;
 
         let content_encoding = response.get_header("content-encoding");
 
         let result = match content_encoding {
-            Some(actual) if actual.to_lowercase() == self.encoding.to_lowercase() => Ok(format!(
-                "server returned Content-Encoding: {}",
-                self.encoding
-            )),
+            Some(actual) if actual.eq_ignore_ascii_case(coding.as_str()) => {
+                self.check_decoded(coding, &response.raw_body)
+            }
             Some(actual) => Err(format!(
                 "expected Content-Encoding '{}', got '{}'",
                 self.encoding, actual
@@ -587,10 +902,90 @@ impl HttpGetCompressedValidator {
             )),
         };
 
-        Ok(TestCase {
-            name: format!("GET {} with compression {}", self.path, self.encoding),
-            result,
-        })
+        Ok(TestCase::new(name, result).with_details(response.transcript("GET", &self.path)))
+    }
+}
+
+impl HttpGetCompressedValidator {
+    fn check_decoded(&self, coding: ContentCoding, raw_body: &[u8]) -> Result<String, String> {
+        let decoded = coding.decode(raw_body).map_err(|e| {
+            format!(
+                "Content-Encoding is {} but the body does not decompress: {}",
+                self.encoding, e
+            )
+        })?;
+        let text = String::from_utf8_lossy(&decoded);
+
+        match self.expected_body {
+            Some(ref expected) if text.trim() != expected => Err(format!(
+                "decompressed body expected '{}', got '{}'",
+                expected,
+                text.trim()
+            )),
+            _ => Ok(format!(
+                "server returned Content-Encoding: {} ({} bytes decompressed to {})",
+                self.encoding,
+                raw_body.len(),
+                decoded.len()
+            )),
+        }
+    }
+}
+
+/// Content-Encoding values the compression validator can decode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentCoding {
+    Gzip,
+    Deflate,
+    Brotli,
+}
+
+impl ContentCoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentCoding::Gzip => "gzip",
+            ContentCoding::Deflate => "deflate",
+            ContentCoding::Brotli => "br",
+        }
+    }
+
+    pub fn decode(&self, body: &[u8]) -> Result<Vec<u8>, String> {
+        use std::io::Read;
+
+        let mut decoded = Vec::new();
+        let read = match self {
+            ContentCoding::Gzip => flate2::read::GzDecoder::new(body).read_to_end(&mut decoded),
+            ContentCoding::Deflate => {
+                // http deflate is zlib-wrapped, but some servers send raw deflate
+                flate2::read::ZlibDecoder::new(body)
+                    .read_to_end(&mut decoded)
+                    .or_else(|_| {
+                        decoded.clear();
+                        flate2::read::DeflateDecoder::new(body).read_to_end(&mut decoded)
+                    })
+            }
+            ContentCoding::Brotli => {
+                brotli::Decompressor::new(body, 4096).read_to_end(&mut decoded)
+            }
+        };
+        read.map_err(|e| e.to_string())?;
+        Ok(decoded)
+    }
+}
+
+impl std::str::FromStr for ContentCoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "gzip" => Ok(ContentCoding::Gzip),
+            "deflate" => Ok(ContentCoding::Deflate),
+            "br" => Ok(ContentCoding::Brotli),
+            _ => Err(format!(
+                "unsupported encoding '{}'. supported: gzip, deflate, br",
+                s
+            )),
+        }
     }
 }
 
@@ -634,13 +1029,14 @@ impl HttpJsonExistsValidator {
             Err(format!("missing required fields: {:?}", missing_fields))
         };
 
-        Ok(TestCase {
-            name: format!(
+        Ok(TestCase::new(
+            format!(
                 "{} {} returns JSON with {:?}",
                 self.method, self.path, self.fields
             ),
             result,
-        })
+        )
+        .with_details(response.transcript(&self.method, &self.path)))
     }
 }
 
@@ -671,6 +1067,7 @@ impl HttpJsonFieldValidator {
             .map_err(|e| format!("invalid JSON response: {}", e))?;
 
         let actual_value = json.get(&self.field);
+        let mut mismatch = None;
 
         let result = match actual_value {
             Some(value) => {
@@ -687,6 +1084,7 @@ impl HttpJsonFieldValidator {
                         self.field, self.expected_value
                     ))
                 } else {
+                    mismatch = Some((pretty_json(&self.expected_value), pretty_json(&value_str)));
                     Err(format!(
                         "field '{}' expected '{}', got '{}'",
                         self.field, self.expected_value, value_str
@@ -696,16 +1094,32 @@ impl HttpJsonFieldValidator {
             None => Err(format!("field '{}' not found in JSON response", self.field)),
         };
 
-        Ok(TestCase {
-            name: format!(
+        let test_case = TestCase::new(
+            format!(
                 "{} {} field '{}' = '{}'",
                 self.method, self.path, self.field, self.expected_value
             ),
             result,
+        )
+        .with_details(response.transcript(&self.method, &self.path));
+
+        Ok(match mismatch {
+            Some((expected, got)) => test_case.with_mismatch(&expected, &got),
+            None => test_case,
         })
     }
 }
 
+/// indent JSON objects and arrays so they diff line by line; other text is kept as is
+fn pretty_json(text: &str) -> String {
+    match serde_json::from_str::<JsonValue>(text) {
+        Ok(value @ (JsonValue::Object(_) | JsonValue::Array(_))) => {
+            serde_json::to_string_pretty(&value).unwrap_or_else(|_| text.to_string())
+        }
+        _ => text.to_string(),
+    }
+}
+
 /// Validator: POST JSON body and check response status and optional body
 pub struct HttpPostJsonValidator {
     pub port: u16,
@@ -777,15 +1191,16 @@ impl HttpPostJsonValidator {
             Err(errors.join("; "))
         };
 
-        Ok(TestCase {
-            name: format!("POST {} returns {}", self.path, self.expected_status),
+        Ok(TestCase::new(
+            format!("POST {} returns {}", self.path, self.expected_status),
             result,
-        })
+        )
+        .with_details(response.transcript("POST", &self.path)))
     }
 }
 
 /// Validator: send rapid requests to test rate limiting
-/// expects some requests to be rejected with 429
+/// expects some requests to be rejected with 429 (or a configured status)
 pub struct RateLimitValidator {
     pub port: u16,
     pub path: String,
@@ -793,6 +1208,7 @@ pub struct RateLimitValidator {
     pub requests: u32,
     pub window_ms: u64,
     pub expected_rejected: u32,
+    pub rejection_status: u16,
 }
 
 impl RateLimitValidator {
@@ -810,74 +1226,131 @@ impl RateLimitValidator {
             requests,
             window_ms,
             expected_rejected,
+            rejection_status: 429,
         }
     }
 
+    pub fn with_rejection_status(mut self, status: u16) -> Self {
+        self.rejection_status = status;
+        self
+    }
+
     pub async fn validate(&self) -> Result<TestCase, String> {
-        let mut handles = Vec::new();
+        let mut set = JoinSet::new();
         let start = std::time::Instant::now();
 
-        // send all requests within the time window
+        // launch requests on a fixed schedule so slow responses don't skew the window
+        let period = Duration::from_micros(self.window_ms * 1000 / u64::from(self.requests.max(1)));
+        let mut ticker = if period.is_zero() {
+            None
+        } else {
+            Some(tokio::time::interval(period))
+        };
+
         for _ in 0..self.requests {
+            if let Some(ref mut ticker) = ticker {
+                ticker.tick().await;
+            }
+
             let port = self.port;
             let path = self.path.clone();
             let method = self.method.clone();
 
-            let handle =
-                tokio::spawn(async move { http_request(port, &method, &path, &[], None).await });
-            handles.push(handle);
-
-            // small delay to spread requests across the window
-            if self.window_ms > 0 {
-                let delay_per_request = self.window_ms / u64::from(self.requests);
-                tokio::time::sleep(Duration::from_millis(delay_per_request)).await;
-            }
+            set.spawn(async move {
+                let sent = std::time::Instant::now();
+                let result = http_request(port, &method, &path, &[], None).await;
+                (result, sent.elapsed())
+            });
         }
 
-        let elapsed = start.elapsed();
+        let launch_elapsed = start.elapsed();
         let mut rejected_count = 0u32;
         let mut success_count = 0u32;
+        let mut other_statuses: BTreeMap<u16, u32> = BTreeMap::new();
         let mut errors = Vec::new();
+        let mut latencies = Vec::with_capacity(self.requests as usize);
 
-        for handle in handles {
-            match handle.await {
-                Ok(Ok(response)) => {
-                    if response.status_code == 429 {
+        while let Some(joined) = set.join_next().await {
+            match joined {
+                Ok((Ok(response), latency)) => {
+                    latencies.push(latency);
+                    if response.status_code == self.rejection_status {
                         rejected_count += 1;
                     } else if response.status_code == 200 || response.status_code == 201 {
                         success_count += 1;
+                    } else {
+                        *other_statuses.entry(response.status_code).or_insert(0) += 1;
                     }
                 }
-                Ok(Err(e)) => errors.push(e),
+                Ok((Err(e), _)) => errors.push(e),
                 Err(e) => errors.push(format!("task failed: {}", e)),
             }
         }
 
+        let elapsed = start.elapsed();
+        let observed_rate = if launch_elapsed.as_secs_f64() > 0.0 {
+            f64::from(self.requests) / launch_elapsed.as_secs_f64()
+        } else {
+            f64::from(self.requests)
+        };
+        let p95 = percentile(&mut latencies, 95);
+
+        // e.g. "120 ok, 80 rejected(429), 3 other(503)"
+        let mut breakdown = format!(
+            "{} ok, {} rejected({})",
+            success_count, rejected_count, self.rejection_status
+        );
+        for (status, count) in &other_statuses {
+            breakdown.push_str(&format!(", {} other({})", count, status));
+        }
+        if !errors.is_empty() {
+            breakdown.push_str(&format!(", {} errors", errors.len()));
+        }
+
         let result = if rejected_count >= self.expected_rejected {
             Ok(format!(
-                "rate limiting working: {}/{} requests rejected (expected >= {}), {} succeeded, completed in {:?}",
-                rejected_count, self.requests, self.expected_rejected, success_count, elapsed
+                "rate limiting working: {}/{} requests rejected (expected >= {}); {}; \
+                 {:.1} req/s observed, p95 latency {}, completed in {}",
+                rejected_count,
+                self.requests,
+                self.expected_rejected,
+                breakdown,
+                observed_rate,
+                format_duration(p95),
+                format_duration(elapsed)
             ))
         } else {
             Err(format!(
-                "expected at least {} rejected requests, got {}. {} succeeded, {} errors",
+                "expected at least {} rejected requests, got {}. {} \
+                 ({:.1} req/s observed, p95 latency {})",
                 self.expected_rejected,
                 rejected_count,
-                success_count,
-                errors.len()
+                breakdown,
+                observed_rate,
+                format_duration(p95)
             ))
         };
 
-        Ok(TestCase {
-            name: format!(
+        Ok(TestCase::new(
+            format!(
                 "rate limit {} requests in {}ms",
                 self.requests, self.window_ms
             ),
             result,
-        })
+        ))
     }
 }
 
+/// nearest-rank percentile, sorts the samples in place
+fn percentile(samples: &mut [Duration], pct: usize) -> Duration {
+    if samples.is_empty() {
+        return Duration::ZERO;
+    }
+    samples.sort();
+    let rank = (samples.len() * pct).div_ceil(100).max(1);
+    samples[rank - 1]
+}
+
 /// Validator: check Content-Type header for a specific path
 pub struct HttpContentTypeValidator {
     pub port: u16,
@@ -909,10 +1382,115 @@ impl HttpContentTypeValidator {
             None => Err("Content-Type header not present".to_string()),
         };
 
-        Ok(TestCase {
-            name: format!("GET {} Content-Type = '{}'", self.path, self.expected_mime),
+        Ok(TestCase::new(
+            format!("GET {} Content-Type = '{}'", self.path, self.expected_mime),
             result,
-        })
+        )
+        .with_details(response.transcript("GET", &self.path)))
+    }
+}
+
+/// Validator: answer a CORS preflight (OPTIONS) for an origin and method
+pub struct HttpCorsValidator {
+    pub port: u16,
+    pub path: String,
+    pub origin: String,
+    pub method: String,
+}
+
+impl HttpCorsValidator {
+    pub fn new(path: &str, origin: &str, method: &str) -> Self {
+        Self {
+            port: DEFAULT_PORT,
+            path: path.to_string(),
+            origin: origin.to_string(),
+            method: method.to_uppercase(),
+        }
+    }
+
+    pub async fn validate(&self) -> Result<TestCase, String> {
+        let headers = [
+            ("Origin", self.origin.as_str()),
+            ("Access-Control-Request-Method", self.method.as_str()),
+        ];
+        let response = http_request(self.port, "OPTIONS", &self.path, &headers, None).await?;
+
+        let mut errors = Vec::new();
+
+        match response.get_header("access-control-allow-origin") {
+            Some(origin) if origin == "*" || origin == self.origin => {}
+            Some(origin) => errors.push(format!(
+                "Access-Control-Allow-Origin is '{}', expected '{}' or '*'",
+                origin, self.origin
+            )),
+            None => errors.push("Access-Control-Allow-Origin header not present".to_string()),
+        }
+
+        match response.get_header("access-control-allow-methods") {
+            Some(methods)
+                if methods
+                    .split(',')
+                    .any(|m| m.trim() == "*" || m.trim().eq_ignore_ascii_case(&self.method)) => {}
+            Some(methods) => errors.push(format!(
+                "Access-Control-Allow-Methods '{}' does not include {}",
+                methods, self.method
+            )),
+            None => errors.push("Access-Control-Allow-Methods header not present".to_string()),
+        }
+
+        let result = if errors.is_empty() {
+            Ok(format!(
+                "preflight allows {} from {}",
+                self.method, self.origin
+            ))
+        } else {
+            Err(errors.join("; "))
+        };
+
+        Ok(TestCase::new(
+            format!(
+                "OPTIONS {} allows {} from {}",
+                self.path, self.method, self.origin
+            ),
+            result,
+        )
+        .with_details(response.transcript("OPTIONS", &self.path)))
+    }
+}
+
+/// Validator: check the HTTP version in the response status line
+pub struct HttpVersionValidator {
+    pub port: u16,
+    pub path: String,
+    pub expected_version: String,
+}
+
+impl HttpVersionValidator {
+    pub fn new(path: &str, expected_version: &str) -> Self {
+        Self {
+            port: DEFAULT_PORT,
+            path: path.to_string(),
+            expected_version: expected_version.to_string(),
+        }
+    }
+
+    pub async fn validate(&self) -> Result<TestCase, String> {
+        let response = http_request(self.port, "GET", &self.path, &[], None).await?;
+
+        let result = if response.version == self.expected_version {
+            Ok(format!("server responded with {}", response.version))
+        } else {
+            Err(format!(
+                "expected {} in the status line, got '{}'",
+                self.expected_version, response.version
+            ))
+        };
+
+        Ok(TestCase::new(
+            format!("GET {} responds with {}", self.path, self.expected_version),
+            result,
+        )
+        .with_details(response.transcript("GET", &self.path)))
     }
 }
 
@@ -991,10 +1569,10 @@ impl HttpKeepaliveValidator {
             ))
         };
 
-        Ok(TestCase {
-            name: format!("{} requests on keep-alive connection", self.num_requests),
+        Ok(TestCase::new(
+            format!("{} requests on keep-alive connection", self.num_requests),
             result,
-        })
+        ))
     }
 }
 
@@ -1051,10 +1629,10 @@ impl HttpChunkedValidator {
             .contains("transfer-encoding: chunked");
 
         if !has_chunked_header {
-            return Ok(TestCase {
-                name: format!("GET {} chunked transfer", self.path),
-                result: Err("Transfer-Encoding: chunked header not found".to_string()),
-            });
+            return Ok(TestCase::new(
+                format!("GET {} chunked transfer", self.path),
+                Err("Transfer-Encoding: chunked header not found".to_string()),
+            ));
         }
 
         // count chunks by looking for hex size lines followed by \r\n
@@ -1102,10 +1680,10 @@ impl HttpChunkedValidator {
             None => Err("no chunks received".to_string()),
         };
 
-        Ok(TestCase {
-            name: format!("GET {} chunked transfer", self.path),
+        Ok(TestCase::new(
+            format!("GET {} chunked transfer", self.path),
             result,
-        })
+        ))
     }
 }
 
@@ -1179,22 +1757,177 @@ impl HttpPipeliningValidator {
             ))
         };
 
-        Ok(TestCase {
-            name: format!("{} pipelined requests", self.num_requests),
+        Ok(TestCase::new(
+            format!("{} pipelined requests", self.num_requests),
             result,
-        })
+        ))
+    }
+}
+
+/// read exactly one response off a persistent connection, framed by Content-Length
+async fn read_one_response(stream: &mut BufReader<TcpStream>) -> Result<HttpResponse, String> {
+    let mut head = String::new();
+    loop {
+        let mut line = String::new();
+        let n = timeout(DEFAULT_TIMEOUT, stream.read_line(&mut line))
+            .await
+            .map_err(|_| "read timeout".to_string())?
+            .map_err(|e| format!("failed to read response: {}", e))?;
+        if n == 0 {
+            return Err("connection closed before a full response arrived".to_string());
+        }
+        if line == "\r\n" || line == "\n" {
+            break;
+        }
+        head.push_str(&line);
+    }
+
+    let mut response = HttpResponse::parse(&head)?;
+    let length =
+        match response.get_header("content-length") {
+            Some(value) => value
+                .parse::<usize>()
+                .map_err(|_| format!("invalid Content-Length '{}'", value))?,
+            None if response.status_code == 204 || response.status_code == 304 => 0,
+            None => return Err(
+                "response has no Content-Length, so it can't be framed on a persistent connection"
+                    .to_string(),
+            ),
+        };
+
+    let mut body = vec![0u8; length];
+    timeout(DEFAULT_TIMEOUT, stream.read_exact(&mut body))
+        .await
+        .map_err(|_| "read timeout".to_string())?
+        .map_err(|e| format!("body shorter than Content-Length {}: {}", length, e))?;
+
+    response.body = String::from_utf8_lossy(&body).to_string();
+    response.raw_body = body;
+    Ok(response)
+}
+
+/// Validator: two different requests on one connection, each answered and framed correctly
+pub struct HttpPipelineValidator {
+    pub port: u16,
+    pub requests: Vec<(String, u16)>,
+}
+
+impl HttpPipelineValidator {
+    pub fn new(first: (&str, u16), second: (&str, u16)) -> Self {
+        Self {
+            port: DEFAULT_PORT,
+            requests: vec![
+                (first.0.to_string(), first.1),
+                (second.0.to_string(), second.1),
+            ],
+        }
+    }
+
+    pub async fn validate(&self) -> Result<TestCase, String> {
+        let addr = format!("127.0.0.1:{}", self.port);
+        let stream = timeout(DEFAULT_TIMEOUT, TcpStream::connect(&addr))
+            .await
+            .map_err(|_| "connection timeout")?
+            .map_err(|e| format!("failed to connect: {}", e))?;
+        let mut stream = BufReader::new(stream);
+
+        let mut errors = Vec::new();
+        for (i, (path, expected_status)) in self.requests.iter().enumerate() {
+            let request = format!(
+                "GET {} HTTP/1.1\r\nHost: 127.0.0.1\r\nConnection: keep-alive\r\n\r\n",
+                path
+            );
+            if let Err(e) = stream.get_mut().write_all(request.as_bytes()).await {
+                errors.push(format!(
+                    "request {} (GET {}): send failed: {}",
+                    i + 1,
+                    path,
+                    e
+                ));
+                break;
+            }
+
+            match read_one_response(&mut stream).await {
+                Ok(response) if response.status_code == *expected_status => {}
+                Ok(response) => errors.push(format!(
+                    "request {} (GET {}): expected status {}, got {}",
+                    i + 1,
+                    path,
+                    expected_status,
+                    response.status_code
+                )),
+                Err(e) => {
+                    errors.push(format!("request {} (GET {}): {}", i + 1, path, e));
+                    break;
+                }
+            }
+        }
+
+        let summary = self
+            .requests
+            .iter()
+            .map(|(path, status)| format!("{} -> {}", path, status))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let result = if errors.is_empty() {
+            Ok(format!("one connection answered {}", summary))
+        } else {
+            Err(errors.join("; "))
+        };
+
+        Ok(TestCase::new(
+            format!("requests on one connection: {}", summary),
+            result,
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::validators::test_support::{serve_counting, serve_once, serve_pipelined};
+
+    #[test]
+    fn test_build_request_sets_user_agent() {
+        let request = build_request("GET", "/", &[], None);
+        assert!(request.contains(&format!("User-Agent: {}\r\n", user_agent())));
+
+        let request = build_request("GET", "/", &[("user-agent", "curl/8.0")], None);
+        assert!(request.contains("user-agent: curl/8.0\r\n"));
+        assert!(!request.contains("luxctl/"));
+    }
+
+    #[tokio::test]
+    async fn test_run_bounded_caps_in_flight() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let outcomes = run_bounded(10, 2, |_| {
+            let in_flight = Arc::clone(&in_flight);
+            let peak = Arc::clone(&peak);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(())
+            }
+        })
+        .await;
+
+        assert_eq!(outcomes.len(), 10);
+        assert!(outcomes.iter().all(|(o, _)| o.is_ok()));
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
 
     #[test]
     fn test_parse_http_response() {
         let raw = "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 5\r\n\r\nhello";
         let response = HttpResponse::parse(raw).unwrap();
 
+        assert_eq!(response.version, "HTTP/1.1");
         assert_eq!(response.status_code, 200);
         assert_eq!(response.status_text, "OK");
         assert_eq!(response.get_header("content-type"), Some("text/plain"));
@@ -1212,6 +1945,22 @@ mod tests {
         assert!(response.body.is_empty());
     }
 
+    #[test]
+    fn test_transcript_truncates_long_bodies() {
+        let body = "x".repeat(TRANSCRIPT_BODY_PREVIEW + 10);
+        let raw = format!("HTTP/1.1 404 Not Found\r\nX-Custom: value\r\n\r\n{}", body);
+        let transcript = HttpResponse::parse(&raw)
+            .unwrap()
+            .transcript("GET", "/missing");
+
+        let lines: Vec<&str> = transcript.lines().collect();
+        assert_eq!(lines[0], "> GET /missing");
+        assert_eq!(lines[1], "< HTTP/1.1 404 Not Found");
+        assert_eq!(lines[2], "< x-custom: value");
+        assert_eq!(lines[4].len(), TRANSCRIPT_BODY_PREVIEW + 2);
+        assert_eq!(lines.last(), Some(&"< ..."));
+    }
+
     #[test]
     fn test_has_header() {
         let raw = "HTTP/1.1 200 OK\r\nX-Custom: value\r\n\r\n";
@@ -1221,4 +1970,491 @@ mod tests {
         assert!(response.has_header("x-custom")); // case insensitive
         assert!(!response.has_header("X-Missing"));
     }
+
+    #[tokio::test]
+    async fn test_header_value_match() {
+        let port = serve_once("HTTP/1.1 200 OK\r\nServer: lux\r\n\r\n").await;
+        let mut validator = HttpHeaderValueValidator::new("Server", "lux");
+        validator.port = port;
+        assert!(validator.validate().await.unwrap().passed());
+    }
+
+    #[tokio::test]
+    async fn test_header_value_match_fails_when_absent() {
+        let port = serve_once("HTTP/1.1 200 OK\r\n\r\n").await;
+        let mut validator = HttpHeaderValueValidator::new("Server", "lux");
+        validator.port = port;
+        assert!(!validator.validate().await.unwrap().passed());
+    }
+
+    #[tokio::test]
+    async fn test_header_value_negated_fails_on_forbidden_value() {
+        let port = serve_once("HTTP/1.1 200 OK\r\nServer: lux/1.0\r\n\r\n").await;
+        let mut validator =
+            HttpHeaderValueValidator::new("Server", "lux/1.0").with_should_match(false);
+        validator.port = port;
+        let result = validator.validate().await.unwrap();
+        assert!(!result.passed());
+        assert!(result.message().contains("must not be"));
+    }
+
+    #[tokio::test]
+    async fn test_header_value_negated_passes_on_other_value() {
+        let port = serve_once("HTTP/1.1 200 OK\r\nServer: lux\r\n\r\n").await;
+        let mut validator =
+            HttpHeaderValueValidator::new("Server", "lux/1.0").with_should_match(false);
+        validator.port = port;
+        assert!(validator.validate().await.unwrap().passed());
+    }
+
+    #[tokio::test]
+    async fn test_header_value_negated_passes_when_absent() {
+        let port = serve_once("HTTP/1.1 200 OK\r\n\r\n").await;
+        let mut validator =
+            HttpHeaderValueValidator::new("Server", "lux/1.0").with_should_match(false);
+        validator.port = port;
+        assert!(validator.validate().await.unwrap().passed());
+    }
+
+    #[test]
+    fn test_body_match_exact() {
+        assert!(BodyMatch::Exact.matches("hello", "hello"));
+        assert!(!BodyMatch::Exact.matches("hello", "hello\n"));
+    }
+
+    #[test]
+    fn test_body_match_trimmed() {
+        assert!(BodyMatch::Trimmed.matches("hello", "  hello\r\n"));
+        assert!(!BodyMatch::Trimmed.matches("hello", "Hello"));
+    }
+
+    #[test]
+    fn test_body_match_ignore_case() {
+        assert!(BodyMatch::IgnoreCase.matches("Hello", " hELLo \n"));
+        assert!(!BodyMatch::IgnoreCase.matches("Hello", "Help"));
+    }
+
+    #[test]
+    fn test_body_match_contains() {
+        assert!(BodyMatch::Contains.matches("World", "Hello, World!"));
+        assert!(!BodyMatch::Contains.matches("world", "Hello, World!"));
+    }
+
+    #[test]
+    fn test_body_match_from_str() {
+        assert_eq!("exact".parse::<BodyMatch>().unwrap(), BodyMatch::Exact);
+        assert_eq!(
+            "IGNORE_CASE".parse::<BodyMatch>().unwrap(),
+            BodyMatch::IgnoreCase
+        );
+        assert!("fuzzy".parse::<BodyMatch>().is_err());
+        assert_eq!(BodyMatch::default(), BodyMatch::Trimmed);
+    }
+
+    #[tokio::test]
+    async fn test_http_get_contains_substring_of_body() {
+        let port =
+            serve_once("HTTP/1.1 200 OK\r\nContent-Length: 24\r\n\r\n<h1>Hello, World!</h1>\r\n")
+                .await;
+        let mut validator = HttpGetValidator::new("/", 200, Some("Hello, World".to_string()))
+            .with_body_match(BodyMatch::Contains);
+        validator.port = port;
+        assert!(validator.validate().await.unwrap().passed());
+    }
+
+    #[tokio::test]
+    async fn test_http_get_multiline_body_attaches_mismatch() {
+        let port = serve_once("HTTP/1.1 200 OK\r\nContent-Length: 7\r\n\r\na\nb\nc\n").await;
+        let mut validator = HttpGetValidator::new("/", 200, Some("a\nx\nc".to_string()));
+        validator.port = port;
+
+        let result = validator.validate().await.unwrap();
+        assert!(!result.passed());
+        assert_eq!(result.message(), "body does not match expected (trimmed)");
+        let mismatch = result.mismatch.unwrap();
+        assert_eq!(mismatch.expected, "a\nx\nc");
+        assert_eq!(mismatch.actual, "a\nb\nc");
+    }
+
+    #[test]
+    fn test_pretty_json_indents_objects_only() {
+        assert_eq!(pretty_json(r#"{"a":1}"#), "{\n  \"a\": 1\n}");
+        assert_eq!(pretty_json("42"), "42");
+        assert_eq!(pretty_json("not json"), "not json");
+    }
+
+    #[tokio::test]
+    async fn test_http_cors_permissive_origin() {
+        let port = serve_once(
+            "HTTP/1.1 204 No Content\r\nAccess-Control-Allow-Origin: *\r\n\
+             Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\r\n",
+        )
+        .await;
+        let mut validator = HttpCorsValidator::new("/api", "https://example.com", "post");
+        validator.port = port;
+        let result = validator.validate().await.unwrap();
+        assert!(result.passed(), "{}", result.message());
+    }
+
+    #[tokio::test]
+    async fn test_http_cors_rejecting_origin() {
+        let port = serve_once(
+            "HTTP/1.1 204 No Content\r\nAccess-Control-Allow-Origin: https://other.dev\r\n\
+             Access-Control-Allow-Methods: GET\r\n\r\n",
+        )
+        .await;
+        let mut validator = HttpCorsValidator::new("/api", "https://example.com", "POST");
+        validator.port = port;
+        let result = validator.validate().await.unwrap();
+        assert!(!result.passed());
+        assert!(result
+            .message()
+            .contains("expected 'https://example.com' or '*'"));
+        assert!(result.message().contains("does not include POST"));
+    }
+
+    #[test]
+    fn test_parse_status_line_without_reason_phrase() {
+        let response = HttpResponse::parse("HTTP/1.1 204\r\n\r\n").unwrap();
+        assert_eq!(response.version, "HTTP/1.1");
+        assert_eq!(response.status_code, 204);
+        assert_eq!(response.status_text, "");
+
+        let response = HttpResponse::parse("HTTP/1.1 204 \r\n\r\n").unwrap();
+        assert_eq!(response.status_code, 204);
+        assert_eq!(response.status_text, "");
+    }
+
+    #[test]
+    fn test_parse_status_line_keeps_multi_word_reason() {
+        let response = HttpResponse::parse("HTTP/1.1 404 Not Found Here\r\n\r\n").unwrap();
+        assert_eq!(response.status_code, 404);
+        assert_eq!(response.status_text, "Not Found Here");
+    }
+
+    #[test]
+    fn test_parse_status_line_rejects_bad_code() {
+        assert!(HttpResponse::parse("HTTP/1.1\r\n\r\n").is_err());
+        assert!(HttpResponse::parse("HTTP/1.1 20\r\n\r\n").is_err());
+        assert!(HttpResponse::parse("HTTP/1.1 200OK\r\n\r\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_plain_text() {
+        let err = HttpResponse::parse("hello\n").err().unwrap();
+        assert_eq!(err, "server did not speak HTTP (got: \"hello\\n\")");
+    }
+
+    #[test]
+    fn test_parse_rejects_binary_with_bounded_preview() {
+        let bytes: Vec<u8> = (0..4096u32).map(|i| (i * 7 % 256) as u8).collect();
+        let raw = String::from_utf8_lossy(&bytes);
+        let err = HttpResponse::parse(&raw).err().unwrap();
+
+        assert!(err.starts_with("server did not speak HTTP (got: \"\\x00\\x07"));
+        assert!(err.ends_with("...\")"));
+        assert!(err.len() < 250);
+        assert!(err.chars().all(|c| c.is_ascii_graphic() || c == ' '));
+    }
+
+    #[tokio::test]
+    async fn test_read_response_keeps_partial_response_on_timeout() {
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        server
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\npart")
+            .await
+            .unwrap();
+
+        // server stays open without sending the rest of the body
+        let response = read_response(&mut client, Duration::from_millis(50))
+            .await
+            .unwrap();
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.body, "part");
+        assert!(response.incomplete);
+        assert!(response
+            .transcript("GET", "/")
+            .ends_with("(read timed out; the response may be truncated)"));
+        drop(server);
+    }
+
+    #[tokio::test]
+    async fn test_read_response_closed_connection_is_complete() {
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        server
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\npart")
+            .await
+            .unwrap();
+        drop(server);
+
+        let response = read_response(&mut client, Duration::from_millis(50))
+            .await
+            .unwrap();
+        assert!(!response.incomplete);
+        assert!(!response.transcript("GET", "/").contains("timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_read_response_timeout_with_nothing_read() {
+        let (mut client, _server) = tokio::io::duplex(1024);
+        let err = read_response(&mut client, Duration::from_millis(50))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err, "read timeout");
+    }
+
+    #[tokio::test]
+    async fn test_read_response_timeout_with_partial_status_line() {
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        server.write_all(b"HTTP/1.1").await.unwrap();
+
+        let err = read_response(&mut client, Duration::from_millis(50))
+            .await
+            .err()
+            .unwrap();
+        assert!(err.starts_with("read timeout after 8 bytes, response may be incomplete"));
+        drop(server);
+    }
+
+    #[test]
+    fn test_parse_http_1_0_version() {
+        let response = HttpResponse::parse("HTTP/1.0 200 OK\r\n\r\n").unwrap();
+        assert_eq!(response.version, "HTTP/1.0");
+    }
+
+    #[tokio::test]
+    async fn test_http_version_matches() {
+        let port = serve_once("HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").await;
+        let mut validator = HttpVersionValidator::new("/", "HTTP/1.1");
+        validator.port = port;
+        assert!(validator.validate().await.unwrap().passed());
+    }
+
+    #[tokio::test]
+    async fn test_http_version_mismatch() {
+        let port = serve_once("HTTP/1.0 200 OK\r\nContent-Length: 0\r\n\r\n").await;
+        let mut validator = HttpVersionValidator::new("/", "HTTP/1.1");
+        validator.port = port;
+        let result = validator.validate().await.unwrap();
+        assert!(!result.passed());
+        assert_eq!(
+            result.message(),
+            "expected HTTP/1.1 in the status line, got 'HTTP/1.0'"
+        );
+    }
+
+    /// answer every request on one connection: /a -> 200 "alpha", anything else -> 404
+    async fn serve_paths(second_response: Option<&'static str>) -> u16 {
+        serve_pipelined(move |n, request_line| match (n, second_response) {
+            (1, Some(raw)) => raw.to_string(),
+            _ if request_line.starts_with("GET /a ") => {
+                "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nalpha".to_string()
+            }
+            _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 4\r\n\r\nnope".to_string(),
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_http_pipeline_two_paths_one_connection() {
+        let port = serve_paths(None).await;
+        let mut validator = HttpPipelineValidator::new(("/a", 200), ("/b", 404));
+        validator.port = port;
+        let result = validator.validate().await.unwrap();
+        assert!(result.passed(), "{}", result.message());
+    }
+
+    #[tokio::test]
+    async fn test_http_pipeline_catches_corrupt_second_response() {
+        // second response is not a valid status line
+        let port = serve_paths(Some("garbage\r\n\r\n")).await;
+        let mut validator = HttpPipelineValidator::new(("/a", 200), ("/b", 404));
+        validator.port = port;
+        let result = validator.validate().await.unwrap();
+        assert!(!result.passed());
+        assert!(result.message().starts_with("request 2 (GET /b)"));
+    }
+
+    fn gzip(text: &str) -> Vec<u8> {
+        use std::io::Write;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn compressed_response(encoding: &str, body: &[u8]) -> Vec<u8> {
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Encoding: {}\r\nContent-Length: {}\r\n\r\n",
+            encoding,
+            body.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(body);
+        response
+    }
+
+    #[tokio::test]
+    async fn test_http_gzip_body_decompresses() {
+        let port = serve_once(compressed_response("gzip", &gzip("hello"))).await;
+        let mut validator =
+            HttpGetCompressedValidator::new("/", "gzip").with_expected_body("hello");
+        validator.port = port;
+        let result = validator.validate().await.unwrap();
+        assert!(result.passed(), "{}", result.message());
+    }
+
+    #[tokio::test]
+    async fn test_http_gzip_body_wrong_content() {
+        let port = serve_once(compressed_response("gzip", &gzip("goodbye"))).await;
+        let mut validator =
+            HttpGetCompressedValidator::new("/", "gzip").with_expected_body("hello");
+        validator.port = port;
+        let result = validator.validate().await.unwrap();
+        assert!(!result.passed());
+        assert!(result.message().contains("got 'goodbye'"));
+    }
+
+    fn deflate(text: &str) -> Vec<u8> {
+        use std::io::Write;
+        let mut encoder =
+            flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn brotli(text: &str) -> Vec<u8> {
+        use std::io::Write;
+        let mut compressed = Vec::new();
+        {
+            let mut writer = brotli::CompressorWriter::new(&mut compressed, 4096, 5, 22);
+            writer.write_all(text.as_bytes()).unwrap();
+        }
+        compressed
+    }
+
+    #[tokio::test]
+    async fn test_http_deflate_body_decompresses() {
+        let port = serve_once(compressed_response("deflate", &deflate("hello"))).await;
+        let mut validator =
+            HttpGetCompressedValidator::new("/", "deflate").with_expected_body("hello");
+        validator.port = port;
+        let result = validator.validate().await.unwrap();
+        assert!(result.passed(), "{}", result.message());
+    }
+
+    #[tokio::test]
+    async fn test_http_brotli_body_decompresses() {
+        let port = serve_once(compressed_response("br", &brotli("hello"))).await;
+        let mut validator = HttpGetCompressedValidator::new("/", "br").with_expected_body("hello");
+        validator.port = port;
+        let result = validator.validate().await.unwrap();
+        assert!(result.passed(), "{}", result.message());
+    }
+
+    #[tokio::test]
+    async fn test_http_compressed_unsupported_encoding() {
+        let validator = HttpGetCompressedValidator::new("/", "zstd");
+        let result = validator.validate().await.unwrap();
+        assert!(!result.passed());
+        assert!(result
+            .message()
+            .contains("unsupported encoding 'zstd'. supported: gzip, deflate, br"));
+    }
+
+    #[test]
+    fn test_content_coding_decode_roundtrip() {
+        assert_eq!(ContentCoding::Gzip.decode(&gzip("hi")).unwrap(), b"hi");
+        assert_eq!(
+            ContentCoding::Deflate.decode(&deflate("hi")).unwrap(),
+            b"hi"
+        );
+        assert_eq!(ContentCoding::Brotli.decode(&brotli("hi")).unwrap(), b"hi");
+        assert!(ContentCoding::Brotli.decode(b"not brotli").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_http_gzip_header_with_plain_body_fails() {
+        let port = serve_once(compressed_response("gzip", b"hello")).await;
+        let mut validator = HttpGetCompressedValidator::new("/", "gzip");
+        validator.port = port;
+        let result = validator.validate().await.unwrap();
+        assert!(!result.passed());
+        assert!(result.message().contains("does not decompress"));
+    }
+
+    /// answer every connection: the first `allowed` get 200, the rest 429
+    async fn serve_with_threshold(allowed: usize) -> u16 {
+        serve_statuses(move |n| if n < allowed { 200 } else { 429 }).await
+    }
+
+    /// answer the nth connection with whatever status `status_for(n)` picks
+    async fn serve_statuses(status_for: impl Fn(usize) -> u16 + Send + Sync + 'static) -> u16 {
+        let (port, _) = serve_counting(move |n| {
+            let response = format!("HTTP/1.1 {} X\r\nContent-Length: 0\r\n\r\n", status_for(n));
+            Some(response.into_bytes())
+        })
+        .await;
+        port
+    }
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let mut samples: Vec<Duration> = (1..=20).map(Duration::from_millis).collect();
+        assert_eq!(percentile(&mut samples, 95), Duration::from_millis(19));
+        assert_eq!(percentile(&mut [], 95), Duration::ZERO);
+        assert_eq!(
+            percentile(&mut [Duration::from_millis(7)], 95),
+            Duration::from_millis(7)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_detects_rejections_above_threshold() {
+        let port = serve_with_threshold(5).await;
+        let mut validator = RateLimitValidator::new("/", "GET", 20, 200, 10);
+        validator.port = port;
+        let started = std::time::Instant::now();
+        let result = validator.validate().await.unwrap();
+        assert!(result.passed(), "{}", result.message());
+        assert!(result.message().contains("15/20 requests rejected"));
+        assert!(result.message().contains("p95 latency"));
+        // launches are paced across the window rather than all at once
+        assert!(started.elapsed() >= Duration::from_millis(180));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_fails_when_too_few_rejected() {
+        let port = serve_with_threshold(18).await;
+        let mut validator = RateLimitValidator::new("/", "GET", 20, 0, 10);
+        validator.port = port;
+        let result = validator.validate().await.unwrap();
+        assert!(!result.passed());
+        assert!(result.message().contains("got 2"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_breakdown_with_mixed_statuses() {
+        // 200, 429, 503 repeating
+        let port = serve_statuses(|n| [200, 429, 503][n % 3]).await;
+        let mut validator = RateLimitValidator::new("/", "GET", 9, 0, 3);
+        validator.port = port;
+        let result = validator.validate().await.unwrap();
+        assert!(result.passed(), "{}", result.message());
+        assert!(result
+            .message()
+            .contains("3 ok, 3 rejected(429), 3 other(503)"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_configurable_rejection_status() {
+        let port = serve_statuses(|n| [200, 429, 503][n % 3]).await;
+        let mut validator = RateLimitValidator::new("/", "GET", 9, 0, 3).with_rejection_status(503);
+        validator.port = port;
+        let result = validator.validate().await.unwrap();
+        assert!(result.passed(), "{}", result.message());
+        assert!(result
+            .message()
+            .contains("3 ok, 3 rejected(503), 3 other(429)"));
+    }
 }
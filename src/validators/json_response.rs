@@ -1,10 +1,41 @@
+use super::http::http_request;
 use crate::tasks::TestCase;
+use serde_json::Value as JsonValue;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
 
+/// How strictly a response body is compared against an expected JSON document
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsonMatch {
+    /// deep equality
+    #[default]
+    Exact,
+    /// expected keys must be present and match; extra keys are allowed
+    Subset,
+    /// deep equality, but arrays may be in any order
+    Unordered,
+}
+
+impl std::str::FromStr for JsonMatch {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "exact" => Ok(JsonMatch::Exact),
+            "subset" => Ok(JsonMatch::Subset),
+            "unordered" | "ignore_order" => Ok(JsonMatch::Unordered),
+            _ => Err(format!(
+                "unknown json match mode '{}'. supported: exact, subset, unordered",
+                s
+            )),
+        }
+    }
+}
+
 pub struct JsonResponseValidator {
     endpoint: String,
-    port: u16,
+    pub port: u16,
+    expected: Option<(JsonValue, JsonMatch)>,
 }
 
 impl Default for JsonResponseValidator {
@@ -18,10 +49,31 @@ impl JsonResponseValidator {
         Self {
             endpoint: "/api/v1/hello".to_string(),
             port: 8000,
+            expected: None,
         }
     }
 
+    pub fn with_endpoint(mut self, endpoint: &str) -> Self {
+        self.endpoint = endpoint.to_string();
+        self
+    }
+
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// compare the whole body against `expected` instead of checking the content-type
+    pub fn with_expected_document(mut self, expected: JsonValue, mode: JsonMatch) -> Self {
+        self.expected = Some((expected, mode));
+        self
+    }
+
     pub async fn validate(&self) -> Result<TestCase, String> {
+        if let Some((expected, mode)) = &self.expected {
+            return self.validate_document(expected, *mode).await;
+        }
+
         let addr = format!("127.0.0.1:{}", self.port);
         let mut stream = TcpStream::connect(&addr)
             .await
@@ -57,9 +109,155 @@ impl JsonResponseValidator {
             Err("missing or incorrect content-type header".to_string())
         };
 
-        Ok(TestCase {
-            name: "response has json content-type header".to_string(),
-            result: test_result,
-        })
+        Ok(TestCase::new(
+            "response has json content-type header",
+            test_result,
+        ))
+    }
+}
+
+impl JsonResponseValidator {
+    async fn validate_document(
+        &self,
+        expected: &JsonValue,
+        mode: JsonMatch,
+    ) -> Result<TestCase, String> {
+        let response = http_request(self.port, "GET", &self.endpoint, &[], None).await?;
+        let name = format!("GET {} matches expected JSON", self.endpoint);
+
+        let actual: JsonValue = match serde_json::from_str(&response.body) {
+            Ok(v) => v,
+            Err(e) => {
+                return Ok(TestCase::new(
+                    name,
+                    Err(format!("invalid JSON response: {}", e)),
+                ))
+            }
+        };
+
+        let result = compare_json(expected, &actual, mode, "$")
+            .map(|()| format!("response body matches expected JSON ({:?})", mode).to_lowercase());
+        let failed = result.is_err();
+
+        let test_case = TestCase::new(name, result);
+        if failed {
+            let pretty = |v: &JsonValue| serde_json::to_string_pretty(v).unwrap_or_default();
+            return Ok(test_case.with_mismatch(&pretty(expected), &pretty(&actual)));
+        }
+        Ok(test_case)
+    }
+}
+
+/// compare two documents; the error names the first differing path, e.g. "$.items[2].id"
+pub fn compare_json(
+    expected: &JsonValue,
+    actual: &JsonValue,
+    mode: JsonMatch,
+    path: &str,
+) -> Result<(), String> {
+    match (expected, actual) {
+        (JsonValue::Object(e), JsonValue::Object(a)) => {
+            for (key, ev) in e {
+                let child = format!("{}.{}", path, key);
+                match a.get(key) {
+                    Some(av) => compare_json(ev, av, mode, &child)?,
+                    None => return Err(format!("{}: missing", child)),
+                }
+            }
+            if mode != JsonMatch::Subset {
+                if let Some(extra) = a.keys().find(|k| !e.contains_key(*k)) {
+                    return Err(format!("{}.{}: unexpected key", path, extra));
+                }
+            }
+            Ok(())
+        }
+        (JsonValue::Array(e), JsonValue::Array(a)) => {
+            if e.len() != a.len() {
+                return Err(format!(
+                    "{}: expected {} items, got {}",
+                    path,
+                    e.len(),
+                    a.len()
+                ));
+            }
+            if mode == JsonMatch::Unordered {
+                let mut used = vec![false; a.len()];
+                for (i, ev) in e.iter().enumerate() {
+                    let found = a
+                        .iter()
+                        .enumerate()
+                        .position(|(j, av)| !used[j] && compare_json(ev, av, mode, path).is_ok());
+                    match found {
+                        Some(j) => used[j] = true,
+                        None => return Err(format!("{}[{}]: no matching item", path, i)),
+                    }
+                }
+                return Ok(());
+            }
+            for (i, (ev, av)) in e.iter().zip(a).enumerate() {
+                compare_json(ev, av, mode, &format!("{}[{}]", path, i))?;
+            }
+            Ok(())
+        }
+        _ if expected == actual => Ok(()),
+        _ => Err(format!("{}: expected {}, got {}", path, expected, actual)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_exact_rejects_extra_keys() {
+        let expected = json!({"a": 1});
+        let actual = json!({"a": 1, "b": 2});
+        assert_eq!(
+            compare_json(&expected, &actual, JsonMatch::Exact, "$").unwrap_err(),
+            "$.b: unexpected key"
+        );
+        assert!(compare_json(&expected, &json!({"a": 1}), JsonMatch::Exact, "$").is_ok());
+    }
+
+    #[test]
+    fn test_subset_allows_extra_keys() {
+        let expected = json!({"user": {"name": "lux"}});
+        let actual = json!({"user": {"name": "lux", "id": 7}, "ok": true});
+        assert!(compare_json(&expected, &actual, JsonMatch::Subset, "$").is_ok());
+
+        let wrong = json!({"user": {"name": "other"}});
+        assert_eq!(
+            compare_json(&expected, &wrong, JsonMatch::Subset, "$").unwrap_err(),
+            "$.user.name: expected \"lux\", got \"other\""
+        );
+        assert_eq!(
+            compare_json(&expected, &json!({}), JsonMatch::Subset, "$").unwrap_err(),
+            "$.user: missing"
+        );
+    }
+
+    #[test]
+    fn test_unordered_arrays() {
+        let expected = json!({"ids": [1, 2, 3]});
+        let actual = json!({"ids": [3, 1, 2]});
+        assert!(compare_json(&expected, &actual, JsonMatch::Unordered, "$").is_ok());
+        assert_eq!(
+            compare_json(&expected, &actual, JsonMatch::Exact, "$").unwrap_err(),
+            "$.ids[0]: expected 1, got 3"
+        );
+        assert!(compare_json(
+            &expected,
+            &json!({"ids": [1, 1, 2]}),
+            JsonMatch::Unordered,
+            "$"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_json_match_from_str() {
+        assert_eq!("subset".parse::<JsonMatch>().unwrap(), JsonMatch::Subset);
+        assert!("loose".parse::<JsonMatch>().is_err());
     }
 }
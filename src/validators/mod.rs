@@ -1,3 +1,4 @@
+pub mod command;
 pub mod compile;
 pub mod docker;
 pub mod endpoint;
@@ -8,27 +9,33 @@ pub mod json_response;
 pub mod parser;
 pub mod port;
 pub mod process;
+pub mod runner;
 pub mod scenario;
+#[cfg(test)]
+pub(crate) mod test_support;
 
-pub use compile::CanCompileValidator;
+pub use command::CommandExitValidator;
+pub use compile::{CanCompileValidator, CompileLanguage};
 pub use docker::{DockerExecutor, DockerValidator, Expectation};
-pub use endpoint::EndpointValidator;
-pub use factory::{create_validator, RuntimeValidator};
-pub use file::FileContentsMatchValidator;
+pub use endpoint::{EndpointValidator, EndpointsValidator};
+pub use factory::{create_validator, create_validator_with, known_validators, RuntimeValidator};
+pub use file::{FileContentsMatchValidator, FileMatchMode, FileSha256Validator};
 pub use http::{
-    ConcurrentRequestsValidator, HttpChunkedValidator, HttpContentTypeValidator,
-    HttpGetCompressedValidator, HttpGetFileValidator, HttpGetValidator, HttpGetWithHeaderValidator,
-    HttpHeaderPresentValidator, HttpHeaderValueValidator, HttpJsonExistsValidator,
-    HttpJsonFieldValidator, HttpKeepaliveValidator, HttpPipeliningValidator, HttpPostFileValidator,
-    HttpPostJsonValidator, HttpStatusValidator, RateLimitValidator,
+    BodyMatch, ConcurrentRequestsValidator, ContentCoding, HttpChunkedValidator,
+    HttpContentTypeValidator, HttpCorsValidator, HttpGetCompressedValidator, HttpGetFileValidator,
+    HttpGetValidator, HttpGetWithHeaderValidator, HttpHeaderPresentValidator,
+    HttpHeaderValueValidator, HttpJsonExistsValidator, HttpJsonFieldValidator,
+    HttpKeepaliveValidator, HttpPipelineValidator, HttpPipeliningValidator, HttpPostFileValidator,
+    HttpPostJsonValidator, HttpStatusValidator, HttpVersionValidator, RateLimitValidator,
 };
-pub use json_response::JsonResponseValidator;
+pub use json_response::{JsonMatch, JsonResponseValidator};
 pub use parser::{parse_validator, ParamValue, ParsedValidator};
 pub use port::PortValidator;
 pub use process::{ConcurrentAccessValidator, GracefulShutdownValidator};
+pub use runner::{run_all, run_all_with, run_validator, run_validator_with};
 pub use scenario::{
     HttpHealthCheck, HttpJsonFieldNested, HttpJsonFieldValue, HttpRequestWithBody, HttpStatusCheck,
-    JobPriorityVerified, JobProcessingVerified, JobResultVerified, JobRetryVerified,
-    JobSubmissionVerified, JobTimeoutReasonVerified, JobTimeoutVerified, WorkerPoolConcurrent,
-    WorkerScaleDown, WorkerScaleUp,
+    JobDeadLetterVerified, JobFifoVerified, JobPriorityVerified, JobProcessingVerified,
+    JobResultVerified, JobRetryVerified, JobSubmissionVerified, JobTimeoutReasonVerified,
+    JobTimeoutVerified, WorkerPoolConcurrent, WorkerScaleDown, WorkerScaleUp,
 };
@@ -5,6 +5,7 @@ use std::fmt;
 pub enum ParamValue {
     Bool(bool),
     Int(i64),
+    Float(f64),
     String(String),
 }
 
@@ -13,6 +14,7 @@ impl fmt::Display for ParamValue {
         match self {
             ParamValue::Bool(b) => write!(f, "{}", b),
             ParamValue::Int(i) => write!(f, "{}", i),
+            ParamValue::Float(x) => write!(f, "{}", x),
             ParamValue::String(s) => write!(f, "{}", s),
         }
     }
@@ -33,6 +35,13 @@ impl ParamValue {
         }
     }
 
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            ParamValue::Float(x) => Some(*x),
+            _ => None,
+        }
+    }
+
     pub fn as_string(&self) -> Option<&str> {
         match self {
             ParamValue::String(s) => Some(s),
@@ -40,6 +49,23 @@ impl ParamValue {
         }
     }
 
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ParamValue::Bool(_) => "bool",
+            ParamValue::Int(_) => "int",
+            ParamValue::Float(_) => "float",
+            ParamValue::String(_) => "string",
+        }
+    }
+
+    /// type and value for error messages, e.g. `string '/'`
+    fn describe(&self) -> String {
+        match self {
+            ParamValue::String(s) => format!("string '{}'", s),
+            other => format!("{} {}", other.type_name(), other),
+        }
+    }
+
     pub fn into_string(self) -> Option<String> {
         match self {
             ParamValue::String(s) => Some(s),
@@ -53,6 +79,8 @@ impl ParamValue {
 pub struct ParsedValidator {
     pub name: String,
     pub params: Vec<ParamValue>,
+    /// `name=type(value)` params, in the order given
+    pub named: Vec<(String, ParamValue)>,
 }
 
 impl ParsedValidator {
@@ -61,26 +89,93 @@ impl ParsedValidator {
         self.params.get(index)
     }
 
-    /// Get parameter as specific type with better error messages
-    pub fn param_as_int(&self, index: usize) -> Result<i64, String> {
+    /// "<validator> param <which>: expected <type>, got <type> <value>"
+    fn type_error(&self, which: &str, expected: &str, got: &ParamValue) -> String {
+        format!(
+            "{} param {}: expected {}, got {}",
+            self.name,
+            which,
+            expected,
+            got.describe()
+        )
+    }
+
+    fn require(&self, index: usize) -> Result<&ParamValue, String> {
         self.param(index)
-            .ok_or_else(|| format!("missing parameter at index {}", index))?
+            .ok_or_else(|| format!("{} param {}: missing", self.name, index))
+    }
+
+    /// Get parameter as specific type; errors name the validator, param and actual value
+    pub fn param_as_int(&self, index: usize) -> Result<i64, String> {
+        let value = self.require(index)?;
+        value
             .as_int()
-            .ok_or_else(|| format!("parameter {} is not an integer", index))
+            .ok_or_else(|| self.type_error(&index.to_string(), "int", value))
+    }
+
+    pub fn param_as_float(&self, index: usize) -> Result<f64, String> {
+        let value = self.require(index)?;
+        value
+            .as_float()
+            .ok_or_else(|| self.type_error(&index.to_string(), "float", value))
     }
 
     pub fn param_as_string(&self, index: usize) -> Result<&str, String> {
-        self.param(index)
-            .ok_or_else(|| format!("missing parameter at index {}", index))?
+        let value = self.require(index)?;
+        value
             .as_string()
-            .ok_or_else(|| format!("parameter {} is not a string", index))
+            .ok_or_else(|| self.type_error(&index.to_string(), "string", value))
     }
 
     pub fn param_as_bool(&self, index: usize) -> Result<bool, String> {
-        self.param(index)
-            .ok_or_else(|| format!("missing parameter at index {}", index))?
+        let value = self.require(index)?;
+        value
             .as_bool()
-            .ok_or_else(|| format!("parameter {} is not a boolean", index))
+            .ok_or_else(|| self.type_error(&index.to_string(), "bool", value))
+    }
+
+    /// Fill omitted trailing params from `defaults`, which start at index
+    /// `first_optional`; params before that stay required
+    pub fn fill_defaults(&mut self, first_optional: usize, defaults: &[ParamValue]) {
+        if self.params.len() < first_optional {
+            return;
+        }
+        let given = self.params.len() - first_optional;
+        self.params.extend(defaults.iter().skip(given).cloned());
+    }
+
+    /// Get a named parameter, e.g. `total` in `total=int(100)`
+    pub fn param_named(&self, name: &str) -> Option<&ParamValue> {
+        self.named
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value)
+    }
+
+    fn require_named(&self, name: &str) -> Result<&ParamValue, String> {
+        self.param_named(name)
+            .ok_or_else(|| format!("{} param '{}': missing", self.name, name))
+    }
+
+    pub fn param_named_as_int(&self, name: &str) -> Result<i64, String> {
+        let value = self.require_named(name)?;
+        value
+            .as_int()
+            .ok_or_else(|| self.type_error(&format!("'{}'", name), "int", value))
+    }
+
+    pub fn param_named_as_string(&self, name: &str) -> Result<&str, String> {
+        let value = self.require_named(name)?;
+        value
+            .as_string()
+            .ok_or_else(|| self.type_error(&format!("'{}'", name), "string", value))
+    }
+
+    pub fn param_named_as_bool(&self, name: &str) -> Result<bool, String> {
+        let value = self.require_named(name)?;
+        value
+            .as_bool()
+            .ok_or_else(|| self.type_error(&format!("'{}'", name), "bool", value))
     }
 }
 
@@ -106,6 +201,19 @@ fn parse_typed_param(input: &str) -> Result<ParamValue, String> {
         return Ok(ParamValue::Int(value));
     }
 
+    if let Some(inner) = input
+        .strip_prefix("float(")
+        .and_then(|s| s.strip_suffix(')'))
+    {
+        let value: f64 = inner
+            .trim()
+            .parse()
+            .ok()
+            .filter(|x: &f64| x.is_finite())
+            .ok_or_else(|| format!("invalid float value: {}", inner))?;
+        return Ok(ParamValue::Float(value));
+    }
+
     if let Some(inner) = input
         .strip_prefix("string(")
         .and_then(|s| s.strip_suffix(')'))
@@ -114,7 +222,7 @@ fn parse_typed_param(input: &str) -> Result<ParamValue, String> {
     }
 
     Err(format!(
-        "invalid parameter format: {}. expected bool(...), int(...), or string(...)",
+        "invalid parameter format: {}. expected bool(...), int(...), float(...), or string(...)",
         input
     ))
 }
@@ -122,7 +230,7 @@ fn parse_typed_param(input: &str) -> Result<ParamValue, String> {
 /// Parse a validator string like "tcp_listening:int(4221)"
 /// Format: validator_name:param1,param2,...
 pub fn parse_validator(input: &str) -> Result<ParsedValidator, String> {
-    let input = input.trim();
+    let input = strip_comment(input).trim();
 
     // split on first colon to get name and params
     let (name, params_str) = match input.split_once(':') {
@@ -134,19 +242,61 @@ pub fn parse_validator(input: &str) -> Result<ParsedValidator, String> {
         return Err("validator name cannot be empty".to_string());
     }
 
-    let params = match params_str {
-        Some(p) if !p.is_empty() => parse_params(p)?,
-        _ => Vec::new(),
-    };
+    let mut params = Vec::new();
+    let mut named: Vec<(String, ParamValue)> = Vec::new();
+    if let Some(p) = params_str.filter(|p| !p.is_empty()) {
+        for (param_name, value) in parse_params(p)? {
+            match param_name {
+                Some(n) if named.iter().any(|(existing, _)| *existing == n) => {
+                    return Err(format!("duplicate parameter '{}'", n));
+                }
+                Some(n) => named.push((n, value)),
+                None => params.push(value),
+            }
+        }
+    }
 
     Ok(ParsedValidator {
         name: name.to_string(),
         params,
+        named,
     })
 }
 
-/// Parse comma-separated parameters, handling nested parentheses
-fn parse_params(input: &str) -> Result<Vec<ParamValue>, String> {
+/// Drop a trailing `# comment`; a `#` inside a param like `string(#top)` is kept
+fn strip_comment(input: &str) -> &str {
+    let mut depth = 0;
+    for (i, ch) in input.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            '#' if depth == 0 => return &input[..i],
+            _ => {}
+        }
+    }
+    input
+}
+
+/// Parse a positional "int(4221)" or a named "port=int(4221)" parameter
+fn parse_param(input: &str) -> Result<(Option<String>, ParamValue), String> {
+    let input = input.trim();
+    // only an `=` before the first `(` names the param; later ones belong to the value
+    let head = input.split('(').next().unwrap_or_default();
+    let Some((name, value)) = input.split_once('=').filter(|_| head.contains('=')) else {
+        return Ok((None, parse_typed_param(input)?));
+    };
+
+    let name = name.trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("invalid parameter name: '{}'", name));
+    }
+    Ok((Some(name.to_string()), parse_typed_param(value)?))
+}
+
+/// Parse comma-separated parameters, handling nested parentheses.
+/// `string()` is an empty string; an empty slot between commas is an error
+/// (skipping it would shift later indices), only a trailing comma is ignored
+fn parse_params(input: &str) -> Result<Vec<(Option<String>, ParamValue)>, String> {
     let mut params = Vec::new();
     let mut current = String::new();
     let mut depth = 0;
@@ -162,9 +312,10 @@ fn parse_params(input: &str) -> Result<Vec<ParamValue>, String> {
                 current.push(ch);
             }
             ',' if depth == 0 => {
-                if !current.trim().is_empty() {
-                    params.push(parse_typed_param(&current)?);
+                if current.trim().is_empty() {
+                    return Err(format!("empty parameter at index {}", params.len()));
                 }
+                params.push(parse_param(&current)?);
                 current.clear();
             }
             _ => current.push(ch),
@@ -173,7 +324,7 @@ fn parse_params(input: &str) -> Result<Vec<ParamValue>, String> {
 
     // handle last parameter
     if !current.trim().is_empty() {
-        params.push(parse_typed_param(&current)?);
+        params.push(parse_param(&current)?);
     }
 
     Ok(params)
@@ -207,6 +358,40 @@ mod tests {
         assert_eq!(result, ParamValue::Int(-42));
     }
 
+    #[test]
+    fn test_parse_float() {
+        assert_eq!(
+            parse_typed_param("float(0.95)").unwrap(),
+            ParamValue::Float(0.95)
+        );
+        assert_eq!(
+            parse_typed_param("float(-1.5)").unwrap(),
+            ParamValue::Float(-1.5)
+        );
+        assert_eq!(
+            parse_typed_param("float(2)").unwrap(),
+            ParamValue::Float(2.0)
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_float() {
+        assert!(parse_typed_param("float(abc)").is_err());
+        assert!(parse_typed_param("float(NaN)").is_err());
+        assert!(parse_typed_param("float()").is_err());
+    }
+
+    #[test]
+    fn test_param_as_float() {
+        let parsed = parse_validator("latency:float(0.25),int(3)").unwrap();
+        assert_eq!(parsed.param_as_float(0).unwrap(), 0.25);
+        assert_eq!(parsed.params[0].to_string(), "0.25");
+        assert_eq!(
+            parsed.param_as_float(1).unwrap_err(),
+            "latency param 1: expected float, got int 3"
+        );
+    }
+
     #[test]
     fn test_parse_string() {
         let result = parse_typed_param("string(/echo/hello)").unwrap();
@@ -219,6 +404,120 @@ mod tests {
         assert_eq!(result, ParamValue::String("hello world".to_string()));
     }
 
+    #[test]
+    fn test_param_errors_name_validator_and_value() {
+        let parsed = parse_validator("http_get:string(/),string(/)").unwrap();
+        assert_eq!(
+            parsed.param_as_int(1).unwrap_err(),
+            "http_get param 1: expected int, got string '/'"
+        );
+        assert_eq!(
+            parsed.param_as_string(2).unwrap_err(),
+            "http_get param 2: missing"
+        );
+
+        let parsed = parse_validator("can_compile:int(1)").unwrap();
+        assert_eq!(
+            parsed.param_as_bool(0).unwrap_err(),
+            "can_compile param 0: expected bool, got int 1"
+        );
+    }
+
+    #[test]
+    fn test_fill_defaults_only_fills_omitted_trailing_params() {
+        let defaults = [ParamValue::Int(4), ParamValue::Int(1000)];
+
+        let mut parsed = parse_validator("pool:string(x),int(8)").unwrap();
+        parsed.fill_defaults(1, &defaults);
+        assert_eq!(parsed.params[1], ParamValue::Int(8));
+        assert_eq!(parsed.params[2], ParamValue::Int(1000));
+
+        // a missing required param is left for the factory to report
+        let mut parsed = parse_validator("pool").unwrap();
+        parsed.fill_defaults(1, &defaults);
+        assert!(parsed.params.is_empty());
+    }
+
+    #[test]
+    fn test_parse_validator_named_params() {
+        let result =
+            parse_validator("rate_limit:path=string(/x),method=string(POST),total=int(100)")
+                .unwrap();
+        assert_eq!(result.name, "rate_limit");
+        assert!(result.params.is_empty());
+        assert_eq!(result.param_named_as_string("path").unwrap(), "/x");
+        assert_eq!(result.param_named_as_string("method").unwrap(), "POST");
+        assert_eq!(result.param_named_as_int("total").unwrap(), 100);
+        assert!(result.param_named("window").is_none());
+    }
+
+    #[test]
+    fn test_parse_validator_positional_and_named_params() {
+        let result =
+            parse_validator("http_get:string(/echo),status=int(200),string(hello)").unwrap();
+        assert_eq!(result.params.len(), 2);
+        assert_eq!(result.param_as_string(0).unwrap(), "/echo");
+        assert_eq!(result.param_as_string(1).unwrap(), "hello");
+        assert_eq!(result.param_named_as_int("status").unwrap(), 200);
+        // an `=` inside a value is not a name
+        let result = parse_validator("http_get:string(/q?a=1)").unwrap();
+        assert_eq!(result.param_as_string(0).unwrap(), "/q?a=1");
+        assert!(result.named.is_empty());
+    }
+
+    #[test]
+    fn test_parse_validator_named_param_errors() {
+        let result = parse_validator("rate_limit:path=string(/x)").unwrap();
+        assert_eq!(
+            result.param_named_as_int("total").unwrap_err(),
+            "rate_limit param 'total': missing"
+        );
+        assert_eq!(
+            result.param_named_as_int("path").unwrap_err(),
+            "rate_limit param 'path': expected int, got string '/x'"
+        );
+        assert!(parse_validator("rate_limit:total=int(1),total=int(2)").is_err());
+        assert!(parse_validator("rate_limit:=int(1)").is_err());
+    }
+
+    #[test]
+    fn test_parse_validator_empty_string_param_keeps_indices() {
+        let result = parse_validator("http_post_file:string(/x),string(),int(200)").unwrap();
+        assert_eq!(result.params.len(), 3);
+        assert_eq!(result.params[1], ParamValue::String(String::new()));
+        assert_eq!(result.param_as_int(2).unwrap(), 200);
+    }
+
+    #[test]
+    fn test_parse_validator_empty_slot_is_an_error() {
+        assert_eq!(
+            parse_validator("http_post_file:string(/x),,int(200)").unwrap_err(),
+            "empty parameter at index 1"
+        );
+        assert!(parse_validator("http_get:,int(200)").is_err());
+        // a trailing separator is still fine
+        let result = parse_validator("http_get:string(/),int(200), ").unwrap();
+        assert_eq!(result.params.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_validator_strips_comment() {
+        let result = parse_validator("tcp_listening:int(4221) # server port").unwrap();
+        assert_eq!(result.name, "tcp_listening");
+        assert_eq!(result.params, vec![ParamValue::Int(4221)]);
+
+        let result = parse_validator("\n  can_compile   # builds\n").unwrap();
+        assert_eq!(result.name, "can_compile");
+        assert!(result.params.is_empty());
+    }
+
+    #[test]
+    fn test_parse_validator_keeps_hash_inside_params() {
+        let result = parse_validator("http_get:string(/docs#intro),int(200) # anchor").unwrap();
+        assert_eq!(result.param_as_string(0).unwrap(), "/docs#intro");
+        assert_eq!(result.params.len(), 2);
+    }
+
     #[test]
     fn test_parse_validator_no_params() {
         let result = parse_validator("can_compile").unwrap();
@@ -1,33 +1,192 @@
 use crate::tasks::TestCase;
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
 use tokio::net::TcpStream;
 use tokio::time::{timeout, Duration};
 
+const DEFAULT_HOST: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
 pub struct PortValidator {
     port: u16,
+    host: IpAddr,
+    expect_listening: bool,
 }
 
 impl PortValidator {
     pub fn new(port: u16) -> Self {
-        Self { port }
+        Self {
+            port,
+            host: DEFAULT_HOST,
+            expect_listening: true,
+        }
+    }
+
+    /// check the server accepts connections on a specific interface
+    pub fn with_host(mut self, host: IpAddr) -> Self {
+        self.host = host;
+        self
+    }
+
+    /// assert the port is closed instead of open
+    pub fn not_listening(mut self) -> Self {
+        self.expect_listening = false;
+        self
     }
 
     pub fn port(&self) -> u16 {
         self.port
     }
 
+    pub fn host(&self) -> IpAddr {
+        self.host
+    }
+
+    pub fn expects_listening(&self) -> bool {
+        self.expect_listening
+    }
+
     pub async fn validate(&self) -> Result<TestCase, String> {
-        let addr = format!("127.0.0.1:{}", self.port);
-        let result = timeout(Duration::from_secs(2), TcpStream::connect(&addr)).await;
+        self.validate_via(resolve_connect_host(self.host, local_interface_addr))
+            .await
+    }
 
-        let test_result = match result {
-            Ok(Ok(_)) => Ok(format!("successfully connected to port {}", self.port)),
-            Ok(Err(e)) => Err(format!("connection failed: {}", e)),
-            Err(_) => Err("connection timeout after 2 seconds".to_string()),
+    /// connect through `connect_host`; None means a 0.0.0.0 bind can't be told apart
+    async fn validate_via(&self, connect_host: Option<IpAddr>) -> Result<TestCase, String> {
+        let name = match (self.expect_listening, self.host == DEFAULT_HOST) {
+            (true, true) => format!("server listening on port {}", self.port),
+            (true, false) => format!("server listening on {}:{}", self.host, self.port),
+            (false, _) => format!("nothing listening on port {}", self.port),
         };
 
-        Ok(TestCase {
-            name: format!("server listening on port {}", self.port),
-            result: test_result,
-        })
+        let Some(connect_host) = connect_host else {
+            return Ok(TestCase::skip(
+                &name,
+                "no non-loopback network interface found, so a server bound to \
+                 127.0.0.1 only can't be told apart from one bound to 0.0.0.0",
+            ));
+        };
+
+        let addr = (connect_host, self.port);
+        let result = timeout(Duration::from_secs(2), TcpStream::connect(addr)).await;
+
+        let test_result = match (self.expect_listening, result) {
+            (true, Ok(Ok(_))) => Ok(format!(
+                "successfully connected to {}:{}",
+                connect_host, self.port
+            )),
+            (true, Ok(Err(e))) => Err(format!("connection failed: {}", e)),
+            (true, Err(_)) => Err("connection timeout after 2 seconds".to_string()),
+            (false, Ok(Ok(_))) => Err(format!("port {} is still accepting connections", self.port)),
+            (false, _) => Ok(format!("port {} is not listening", self.port)),
+        };
+
+        Ok(TestCase::new(name, test_result))
+    }
+}
+
+/// connecting to 0.0.0.0 is routed to loopback, which a 127.0.0.1-only server
+/// would also accept. use a non-loopback local address so the check only
+/// passes when the server is bound to all interfaces. None when there is no
+/// such address (no default route, sandboxes): falling back to loopback would
+/// pass exactly the servers this check exists to catch.
+fn resolve_connect_host(
+    host: IpAddr,
+    interface: impl FnOnce() -> Option<IpAddr>,
+) -> Option<IpAddr> {
+    if !host.is_unspecified() {
+        return Some(host);
+    }
+    interface()
+}
+
+/// address of the interface used for outbound traffic (no packets are sent)
+fn local_interface_addr() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:80").ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    if ip.is_loopback() || ip.is_unspecified() {
+        None
+    } else {
+        Some(ip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_listening_on_explicit_host() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let validator = PortValidator::new(port).with_host("127.0.0.1".parse().unwrap());
+        let result = validator.validate().await.unwrap();
+        assert!(result.passed(), "{}", result.message());
+    }
+
+    #[tokio::test]
+    async fn test_not_listening_passes_on_closed_port() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let result = PortValidator::new(port)
+            .not_listening()
+            .validate()
+            .await
+            .unwrap();
+        assert!(result.passed(), "{}", result.message());
+        assert_eq!(result.name, format!("nothing listening on port {}", port));
+    }
+
+    #[tokio::test]
+    async fn test_not_listening_fails_on_open_port() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let result = PortValidator::new(port)
+            .not_listening()
+            .validate()
+            .await
+            .unwrap();
+        assert!(!result.passed());
+        assert!(result.message().contains("still accepting"));
+    }
+
+    #[test]
+    fn test_resolve_connect_host_keeps_specific_address() {
+        let host: IpAddr = "127.0.0.1".parse().unwrap();
+        assert_eq!(resolve_connect_host(host, || None), Some(host));
+    }
+
+    #[test]
+    fn test_resolve_connect_host_avoids_loopback_for_unspecified() {
+        let unspecified = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        let interface: IpAddr = "192.168.1.20".parse().unwrap();
+        assert_eq!(
+            resolve_connect_host(unspecified, || Some(interface)),
+            Some(interface)
+        );
+        // no usable interface: no loopback fallback
+        assert_eq!(resolve_connect_host(unspecified, || None), None);
+
+        if let Some(resolved) = resolve_connect_host(unspecified, local_interface_addr) {
+            assert!(!resolved.is_loopback());
+            assert!(!resolved.is_unspecified());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unspecified_host_without_interface_is_skipped() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let validator = PortValidator::new(port).with_host(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+        let result = validator.validate_via(None).await.unwrap();
+        assert!(result.is_skipped());
+        assert!(result
+            .message()
+            .contains("no non-loopback network interface"));
     }
 }
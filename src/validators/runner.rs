@@ -0,0 +1,112 @@
+//! run validators from their DSL strings without the CLI, for tools embedding lux.
+//!
+//! these run with a default `ValidationContext`: ports come from the DSL params or
+//! each module's default, and file/build/process validators work in the current
+//! directory. use `run_all_with` to point them at a project.
+
+use super::factory::create_validator_with;
+use crate::tasks::{TestCase, TestResults, ValidationContext};
+use std::time::Instant;
+
+/// parse and run a single validator, e.g. `tcp_listening:int(4221)`
+pub async fn run_validator(dsl: &str) -> Result<TestCase, String> {
+    run_validator_with(dsl, &ValidationContext::default()).await
+}
+
+/// run validators in order; invalid or erroring ones are recorded as failed cases
+pub async fn run_all(dsls: &[String]) -> TestResults {
+    run_all_with(dsls, &ValidationContext::default()).await
+}
+
+pub async fn run_validator_with(dsl: &str, ctx: &ValidationContext) -> Result<TestCase, String> {
+    let validator = create_validator_with(dsl, ctx)?;
+    let started = Instant::now();
+    let mut test_case = validator.validate(ctx).await?;
+    test_case.duration = started.elapsed();
+    Ok(test_case)
+}
+
+pub async fn run_all_with(dsls: &[String], ctx: &ValidationContext) -> TestResults {
+    let mut results = TestResults::new();
+    for dsl in dsls {
+        let started = Instant::now();
+        let test_case = match run_validator_with(dsl, ctx).await {
+            Ok(test_case) => test_case,
+            Err(err) => TestCase::new(dsl.clone(), Err(err)).with_duration(started.elapsed()),
+        };
+        results.add(test_case);
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::test_support::serve_counting;
+
+    // same responses as tests/test-server, on a free port
+    async fn spawn_test_server() -> u16 {
+        let (port, _) = serve_counting(|_| {
+            Some(
+                b"HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 13\r\n\r\nHello, World!"
+                    .to_vec(),
+            )
+        })
+        .await;
+        port
+    }
+
+    #[tokio::test]
+    async fn test_run_all_against_test_server() {
+        let port = spawn_test_server().await;
+        let dsls = vec![
+            format!("tcp_listening:int({})", port),
+            format!("tcp_not_listening:int({})", port),
+        ];
+
+        let results = run_all(&dsls).await;
+        assert_eq!(results.total(), 2);
+        assert_eq!(results.passed(), 1);
+        assert!(results.tests[0].passed());
+        assert!(!results.tests[1].passed());
+    }
+
+    #[tokio::test]
+    async fn test_run_all_checks_http_responses() {
+        let port = spawn_test_server().await;
+        let ctx = ValidationContext {
+            port: Some(port),
+            ..Default::default()
+        };
+        let dsls = vec![
+            "http_get:string(/),int(200),string(Hello, World!)".to_string(),
+            "http_get:string(/),int(200),string(Goodbye)".to_string(),
+            "http_header_value:string(Content-Type),string(text/plain)".to_string(),
+        ];
+
+        let results = run_all_with(&dsls, &ctx).await;
+        assert_eq!(results.total(), 3);
+        assert!(results.tests[0].passed());
+        assert!(!results.tests[1].passed());
+        assert!(results.tests[1].message().contains("Hello, World!"));
+        assert!(results.tests[2].passed());
+    }
+
+    #[tokio::test]
+    async fn test_run_all_records_invalid_dsl() {
+        let results = run_all(&["tcp_listening:int(".to_string()]).await;
+        assert_eq!(results.total(), 1);
+        assert_eq!(results.tests[0].name, "tcp_listening:int(");
+        assert!(!results.tests[0].passed());
+    }
+
+    #[tokio::test]
+    async fn test_run_validator() {
+        let port = spawn_test_server().await;
+        let test_case = run_validator(&format!("tcp_listening:int({})", port))
+            .await
+            .unwrap();
+        assert!(test_case.passed());
+        assert!(run_validator("tcp_listening:int(").await.is_err());
+    }
+}
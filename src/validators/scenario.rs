@@ -1,5 +1,6 @@
-use super::http::http_request;
+use super::http::{http_request, HttpResponse};
 use crate::tasks::TestCase;
+use crate::ui::format_duration;
 use serde_json::Value as JsonValue;
 use tokio::time::{sleep, Duration};
 
@@ -26,6 +27,46 @@ fn json_value_to_string(value: &JsonValue) -> String {
     }
 }
 
+/// Helper to pull the job id out of a POST /jobs response
+fn submitted_job_id(response: Result<HttpResponse, String>) -> Result<String, String> {
+    let response = response?;
+    if !(200..300).contains(&response.status_code) {
+        return Err(format!("POST /jobs returned {}", response.status_code));
+    }
+    let json: JsonValue =
+        serde_json::from_str(&response.body).map_err(|e| format!("invalid JSON: {}", e))?;
+    json.get("id")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| "missing id".to_string())
+}
+
+/// Helper to make sure every submission landed, so lost POSTs aren't blamed on the workers
+fn tally_submissions(results: Vec<Result<String, String>>) -> Result<Vec<String>, String> {
+    let attempted = results.len();
+    let mut job_ids = Vec::new();
+    let mut first_error = None;
+    for result in results {
+        match result {
+            Ok(id) => job_ids.push(id),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    match first_error {
+        None => Ok(job_ids),
+        Some(e) => Err(format!(
+            "lost {} of {} job submissions ({} accepted), first error: {}",
+            attempted - job_ids.len(),
+            attempted,
+            job_ids.len(),
+            e
+        )),
+    }
+}
+
 /// Scenario: Submit a job and verify it was stored
 /// 1. POST /jobs with payload
 /// 2. Extract job_id from response
@@ -56,13 +97,13 @@ impl JobSubmissionVerified {
         let post_response = http_request(self.port, "POST", "/jobs", &headers, Some(&body)).await?;
 
         if post_response.status_code != 201 {
-            return Ok(TestCase {
-                name: "job submission verified".to_string(),
-                result: Err(format!(
+            return Ok(TestCase::new(
+                "job submission verified",
+                Err(format!(
                     "POST /jobs expected 201, got {}",
                     post_response.status_code
                 )),
-            });
+            ));
         }
 
         // step 2: extract job_id
@@ -79,13 +120,13 @@ impl JobSubmissionVerified {
         let get_response = http_request(self.port, "GET", &get_path, &[], None).await?;
 
         if get_response.status_code != 200 {
-            return Ok(TestCase {
-                name: "job submission verified".to_string(),
-                result: Err(format!(
+            return Ok(TestCase::new(
+                "job submission verified",
+                Err(format!(
                     "GET {} expected 200, got {} - job not stored",
                     get_path, get_response.status_code
                 )),
-            });
+            ));
         }
 
         // step 4: verify job data
@@ -95,19 +136,19 @@ impl JobSubmissionVerified {
         let stored_id = get_json.get("id").and_then(|v| v.as_str()).unwrap_or("");
 
         if stored_id != job_id {
-            return Ok(TestCase {
-                name: "job submission verified".to_string(),
-                result: Err(format!(
+            return Ok(TestCase::new(
+                "job submission verified",
+                Err(format!(
                     "stored job id '{}' doesn't match submitted '{}'",
                     stored_id, job_id
                 )),
-            });
+            ));
         }
 
-        Ok(TestCase {
-            name: "job submission verified".to_string(),
-            result: Ok(format!("job {} submitted and verified in storage", job_id)),
-        })
+        Ok(TestCase::new(
+            "job submission verified",
+            Ok(format!("job {} submitted and verified in storage", job_id)),
+        ))
     }
 }
 
@@ -145,13 +186,13 @@ impl JobProcessingVerified {
         let post_response = http_request(self.port, "POST", "/jobs", &headers, Some(&body)).await?;
 
         if post_response.status_code != 201 {
-            return Ok(TestCase {
-                name: "job processing verified".to_string(),
-                result: Err(format!(
+            return Ok(TestCase::new(
+                "job processing verified",
+                Err(format!(
                     "POST /jobs expected 201, got {}",
                     post_response.status_code
                 )),
-            });
+            ));
         }
 
         let json: JsonValue = serde_json::from_str(&post_response.body)
@@ -170,13 +211,13 @@ impl JobProcessingVerified {
         let get_response = http_request(self.port, "GET", &get_path, &[], None).await?;
 
         if get_response.status_code != 200 {
-            return Ok(TestCase {
-                name: "job processing verified".to_string(),
-                result: Err(format!(
+            return Ok(TestCase::new(
+                "job processing verified",
+                Err(format!(
                     "GET {} returned {}",
                     get_path, get_response.status_code
                 )),
-            });
+            ));
         }
 
         let get_json: JsonValue =
@@ -197,10 +238,10 @@ impl JobProcessingVerified {
             ))
         };
 
-        Ok(TestCase {
-            name: format!("job processing → {}", self.expected_status),
+        Ok(TestCase::new(
+            format!("job processing → {}", self.expected_status),
             result,
-        })
+        ))
     }
 }
 
@@ -214,6 +255,10 @@ pub struct WorkerPoolConcurrent {
     pub job_count: u32,
     pub job_duration_ms: u64,
     pub max_total_ms: u64,
+    /// jobs that must be seen processing at once to call it concurrent
+    pub min_concurrent: u32,
+    pub samples: u32,
+    pub sample_interval_ms: u64,
 }
 
 impl WorkerPoolConcurrent {
@@ -224,12 +269,45 @@ impl WorkerPoolConcurrent {
             job_count,
             job_duration_ms: 500, // jobs should take this long
             max_total_ms,
+            min_concurrent: Self::concurrency_threshold(worker_count, job_count),
+            samples: 5,
+            sample_interval_ms: 50,
+        }
+    }
+
+    /// three quarters of the jobs that could run at once, never less than 2
+    pub fn concurrency_threshold(worker_count: u32, job_count: u32) -> u32 {
+        (worker_count.min(job_count) * 3).div_ceil(4).max(2)
+    }
+
+    pub fn with_min_concurrent(mut self, min_concurrent: u32) -> Self {
+        self.min_concurrent = min_concurrent;
+        self
+    }
+
+    pub fn with_sampling(mut self, samples: u32, interval_ms: u64) -> Self {
+        self.samples = samples.max(1);
+        self.sample_interval_ms = interval_ms;
+        self
+    }
+
+    async fn count_processing(&self, job_ids: &[String]) -> u32 {
+        let mut processing_count = 0;
+        for job_id in job_ids {
+            let get_path = format!("/jobs/{}", job_id);
+            if let Ok(response) = http_request(self.port, "GET", &get_path, &[], None).await {
+                if let Ok(json) = serde_json::from_str::<JsonValue>(&response.body) {
+                    if json.get("status").and_then(|v| v.as_str()) == Some("processing") {
+                        processing_count += 1;
+                    }
+                }
+            }
         }
+        processing_count
     }
 
     pub async fn validate(&self) -> Result<TestCase, String> {
         let start = std::time::Instant::now();
-        let mut job_ids = Vec::new();
 
         // step 1: submit all jobs simultaneously
         let mut handles = Vec::new();
@@ -247,43 +325,25 @@ impl WorkerPoolConcurrent {
             handles.push(handle);
         }
 
-        // collect job IDs
+        // collect job IDs, a lost submission is not a concurrency failure
+        let mut results = Vec::new();
         for handle in handles {
-            if let Ok(Ok(response)) = handle.await {
-                if let Ok(json) = serde_json::from_str::<JsonValue>(&response.body) {
-                    if let Some(id) = json.get("id").and_then(|v| v.as_str()) {
-                        job_ids.push(id.to_string());
-                    }
-                }
-            }
-        }
-
-        if job_ids.len() != self.job_count as usize {
-            return Ok(TestCase {
-                name: "worker pool concurrent".to_string(),
-                result: Err(format!(
-                    "only {} of {} jobs submitted successfully",
-                    job_ids.len(),
-                    self.job_count
-                )),
-            });
+            let response = handle
+                .await
+                .unwrap_or_else(|e| Err(format!("submission task failed: {}", e)));
+            results.push(submitted_job_id(response));
         }
 
-        // step 2: immediately check how many are processing
-        sleep(Duration::from_millis(50)).await; // tiny delay for jobs to start
+        let job_ids = match tally_submissions(results) {
+            Ok(ids) => ids,
+            Err(e) => return Ok(TestCase::new("worker pool concurrent", Err(e))),
+        };
 
-        let mut processing_count = 0;
-        for job_id in &job_ids {
-            let get_path = format!("/jobs/{}", job_id);
-            if let Ok(response) = http_request(self.port, "GET", &get_path, &[], None).await {
-                if let Ok(json) = serde_json::from_str::<JsonValue>(&response.body) {
-                    if let Some(status) = json.get("status").and_then(|v| v.as_str()) {
-                        if status == "processing" {
-                            processing_count += 1;
-                        }
-                    }
-                }
-            }
+        // step 2: sample a few times and keep the peak, a single early look is racy
+        let mut peak = 0;
+        for _ in 0..self.samples {
+            sleep(Duration::from_millis(self.sample_interval_ms)).await;
+            peak = peak.max(self.count_processing(&job_ids).await);
         }
 
         // step 3: wait for all to complete
@@ -294,32 +354,34 @@ impl WorkerPoolConcurrent {
 
         // step 4: verify concurrency
         // if workers are concurrent, total time should be ~job_duration, not job_duration * job_count
-        let result = if processing_count >= 2 {
+        let result = if peak >= self.min_concurrent {
             if elapsed_ms <= self.max_total_ms {
                 Ok(format!(
-                    "concurrent processing confirmed: {} jobs processing simultaneously, completed in {}ms",
-                    processing_count, elapsed_ms
+                    "concurrent processing confirmed: peak of {} jobs processing simultaneously, completed in {}",
+                    peak,
+                    format_duration(elapsed)
                 ))
             } else {
                 Err(format!(
-                    "jobs processed but took {}ms (max allowed: {}ms) - workers may not be concurrent",
-                    elapsed_ms, self.max_total_ms
+                    "jobs processed but took {} (max allowed: {}) - workers may not be concurrent",
+                    format_duration(elapsed),
+                    format_duration(Duration::from_millis(self.max_total_ms))
                 ))
             }
         } else {
             Err(format!(
-                "only {} job(s) processing at same time - expected concurrent processing with {} workers",
-                processing_count, self.worker_count
+                "peak of {} job(s) processing at same time - expected at least {} with {} workers",
+                peak, self.min_concurrent, self.worker_count
             ))
         };
 
-        Ok(TestCase {
-            name: format!(
+        Ok(TestCase::new(
+            format!(
                 "{} workers processing {} jobs",
                 self.worker_count, self.job_count
             ),
             result,
-        })
+        ))
     }
 }
 
@@ -354,10 +416,10 @@ impl JobResultVerified {
         let post_response = http_request(self.port, "POST", "/jobs", &headers, Some(&body)).await?;
 
         if post_response.status_code != 201 {
-            return Ok(TestCase {
-                name: format!("job result: {}", self.job_type),
-                result: Err(format!("POST failed with {}", post_response.status_code)),
-            });
+            return Ok(TestCase::new(
+                format!("job result: {}", self.job_type),
+                Err(format!("POST failed with {}", post_response.status_code)),
+            ));
         }
 
         let json: JsonValue = serde_json::from_str(&post_response.body)
@@ -394,10 +456,10 @@ impl JobResultVerified {
             ))
         };
 
-        Ok(TestCase {
-            name: format!("job result: {} → {}", self.job_type, self.expected_result),
+        Ok(TestCase::new(
+            format!("job result: {} → {}", self.job_type, self.expected_result),
             result,
-        })
+        ))
     }
 }
 
@@ -491,13 +553,13 @@ impl JobPriorityVerified {
             _ => Err("jobs missing completed_at timestamp".to_string()),
         };
 
-        Ok(TestCase {
-            name: format!(
+        Ok(TestCase::new(
+            format!(
                 "priority {} before {}",
                 self.high_priority, self.low_priority
             ),
             result,
-        })
+        ))
     }
 }
 
@@ -560,10 +622,7 @@ impl JobTimeoutVerified {
             ))
         };
 
-        Ok(TestCase {
-            name: "job timeout".to_string(),
-            result,
-        })
+        Ok(TestCase::new("job timeout", result))
     }
 }
 
@@ -623,10 +682,7 @@ impl JobTimeoutReasonVerified {
             ))
         };
 
-        Ok(TestCase {
-            name: "job timeout reason".to_string(),
-            result,
-        })
+        Ok(TestCase::new("job timeout reason", result))
     }
 }
 
@@ -684,13 +740,217 @@ impl JobRetryVerified {
             Err("job retries not tracked - expected retries > 0".to_string())
         };
 
-        Ok(TestCase {
-            name: "job retry tracking".to_string(),
-            result,
-        })
+        Ok(TestCase::new("job retry tracking", result))
+    }
+}
+
+/// Scenario: A job that always fails ends up dead after exactly max_retries
+/// 1. POST a permanently failing job with max_retries
+/// 2. Poll until it reaches a terminal state (or give up)
+/// 3. Verify status is failed/dead and retries == max_retries
+pub struct JobDeadLetterVerified {
+    pub port: u16,
+    pub job_type: String,
+    pub max_retries: u32,
+    pub max_wait_ms: u64,
+    pub poll_interval_ms: u64,
+}
+
+const TERMINAL_FAILURE_STATES: &[&str] = &["failed", "dead", "dead_letter"];
+
+impl JobDeadLetterVerified {
+    pub fn new(job_type: &str, max_retries: u32) -> Self {
+        Self {
+            port: DEFAULT_PORT,
+            job_type: job_type.to_string(),
+            max_retries,
+            max_wait_ms: 10_000,
+            poll_interval_ms: 250,
+        }
+    }
+
+    pub async fn validate(&self) -> Result<TestCase, String> {
+        let body = format!(
+            r#"{{"type":"{}","payload":"test","max_retries":{}}}"#,
+            self.job_type, self.max_retries
+        );
+        let headers = [("Content-Type", "application/json")];
+        let post_response = http_request(self.port, "POST", "/jobs", &headers, Some(&body)).await?;
+
+        let json: JsonValue = serde_json::from_str(&post_response.body)
+            .map_err(|e| format!("invalid JSON: {}", e))?;
+        let job_id = json
+            .get("id")
+            .and_then(|v| v.as_str())
+            .ok_or("missing id")?;
+
+        // poll until the job gives up, a server that retries forever never gets there
+        let get_path = format!("/jobs/{}", job_id);
+        let start = std::time::Instant::now();
+        let (status, retries) = loop {
+            sleep(Duration::from_millis(self.poll_interval_ms)).await;
+
+            let response = http_request(self.port, "GET", &get_path, &[], None).await?;
+            let job: JsonValue =
+                serde_json::from_str(&response.body).map_err(|e| format!("invalid JSON: {}", e))?;
+            let status = job
+                .get("status")
+                .and_then(|v| v.as_str())
+                .map(str::to_string);
+            let retries = job
+                .get("retries")
+                .or_else(|| job.get("retry_count"))
+                .and_then(|v| v.as_u64());
+
+            let terminal = status
+                .as_deref()
+                .is_some_and(|s| TERMINAL_FAILURE_STATES.contains(&s));
+            if terminal || start.elapsed().as_millis() as u64 >= self.max_wait_ms {
+                break (status, retries);
+            }
+        };
+
+        Ok(TestCase::new(
+            format!("job dead-lettered after {} retries", self.max_retries),
+            check_dead_letter(status.as_deref(), retries, self.max_retries),
+        ))
     }
 }
 
+/// the job must be in a terminal failure state having used every retry, no fewer and no more
+fn check_dead_letter(
+    status: Option<&str>,
+    retries: Option<u64>,
+    max_retries: u32,
+) -> Result<String, String> {
+    let status = status.ok_or("job missing status")?;
+    if !TERMINAL_FAILURE_STATES.contains(&status) {
+        return Err(format!(
+            "job still '{}' - expected a terminal state ({})",
+            status,
+            TERMINAL_FAILURE_STATES.join(", ")
+        ));
+    }
+
+    let retries = retries.ok_or("job missing retries")?;
+    match retries.cmp(&(max_retries as u64)) {
+        std::cmp::Ordering::Equal => Ok(format!("job {} after {} retries", status, retries)),
+        std::cmp::Ordering::Less => Err(format!(
+            "job {} after {} retries - gave up before max_retries ({})",
+            status, retries, max_retries
+        )),
+        std::cmp::Ordering::Greater => Err(format!(
+            "job {} after {} retries - exceeded max_retries ({})",
+            status, retries, max_retries
+        )),
+    }
+}
+
+/// Scenario: Jobs without a priority complete in submission order
+/// 1. POST N short jobs with identifiable payloads
+/// 2. Wait for all to finish
+/// 3. GET each and verify completed_at never goes backwards
+pub struct JobFifoVerified {
+    pub port: u16,
+    pub job_count: u32,
+    pub job_duration_ms: u64,
+}
+
+impl JobFifoVerified {
+    pub fn new(job_count: u32) -> Self {
+        Self {
+            port: DEFAULT_PORT,
+            job_count,
+            job_duration_ms: 50,
+        }
+    }
+
+    pub async fn validate(&self) -> Result<TestCase, String> {
+        let headers = [("Content-Type", "application/json")];
+
+        // step 1: submit one at a time so the submission order is unambiguous
+        let mut job_ids = Vec::new();
+        for i in 0..self.job_count {
+            let body = format!(
+                r#"{{"type":"sleep","payload":"fifo-{}","duration_ms":{}}}"#,
+                i, self.job_duration_ms
+            );
+            let response = http_request(self.port, "POST", "/jobs", &headers, Some(&body)).await?;
+            let json: JsonValue =
+                serde_json::from_str(&response.body).map_err(|e| format!("invalid JSON: {}", e))?;
+            let id = json
+                .get("id")
+                .and_then(|v| v.as_str())
+                .ok_or("missing id")?;
+            job_ids.push(id.to_string());
+        }
+
+        // step 2: wait long enough for a single worker to drain the queue
+        let wait_ms = self.job_duration_ms * self.job_count as u64 + 500;
+        sleep(Duration::from_millis(wait_ms)).await;
+
+        // step 3: collect completion timestamps in submission order
+        let mut completions = Vec::new();
+        for job_id in &job_ids {
+            let get_path = format!("/jobs/{}", job_id);
+            let response = http_request(self.port, "GET", &get_path, &[], None).await?;
+            let json: JsonValue =
+                serde_json::from_str(&response.body).map_err(|e| format!("invalid JSON: {}", e))?;
+            let completed_at = get_nested_field(&json, "completed_at")
+                .filter(|v| !v.is_null())
+                .map(json_value_to_string);
+            completions.push((job_id.clone(), completed_at));
+        }
+
+        Ok(TestCase::new(
+            format!("{} jobs complete in FIFO order", self.job_count),
+            check_fifo_order(&completions),
+        ))
+    }
+}
+
+/// completion timestamps, in submission order, must be non-decreasing
+fn check_fifo_order(completions: &[(String, Option<String>)]) -> Result<String, String> {
+    let missing: Vec<&str> = completions
+        .iter()
+        .filter(|(_, completed_at)| completed_at.is_none())
+        .map(|(id, _)| id.as_str())
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!(
+            "{} of {} jobs missing completed_at: {}",
+            missing.len(),
+            completions.len(),
+            missing.join(", ")
+        ));
+    }
+
+    // compare instants, not strings: "00:00:00.5Z" sorts before "00:00:00Z" as text
+    let mut parsed = Vec::with_capacity(completions.len());
+    for (id, completed_at) in completions {
+        let at = completed_at.as_deref().unwrap_or_default();
+        let instant = chrono::DateTime::parse_from_rfc3339(at)
+            .map_err(|e| format!("job {} has an invalid completed_at '{}': {}", id, at, e))?;
+        parsed.push((id, at, instant));
+    }
+
+    for pair in parsed.windows(2) {
+        let (prev_id, prev_at, prev_instant) = &pair[0];
+        let (id, at, instant) = &pair[1];
+        if instant < prev_instant {
+            return Err(format!(
+                "job {} completed at {} before job {} submitted ahead of it (completed at {})",
+                id, at, prev_id, prev_at
+            ));
+        }
+    }
+
+    Ok(format!(
+        "{} jobs completed in submission order",
+        completions.len()
+    ))
+}
+
 /// Scenario: Worker scale up under load
 pub struct WorkerScaleUp {
     pub port: u16,
@@ -721,10 +981,16 @@ impl WorkerScaleUp {
             .unwrap_or(0) as u32;
 
         // step 2: submit many jobs to trigger scale up
+        let mut results = Vec::new();
         for i in 0..self.job_count {
             let body = format!(r#"{{"type":"sleep","payload":"{}","duration_ms":2000}}"#, i);
             let headers = [("Content-Type", "application/json")];
-            let _ = http_request(self.port, "POST", "/jobs", &headers, Some(&body)).await;
+            let response = http_request(self.port, "POST", "/jobs", &headers, Some(&body)).await;
+            results.push(submitted_job_id(response));
+        }
+
+        if let Err(e) = tally_submissions(results) {
+            return Ok(TestCase::new("worker scale up", Err(e)));
         }
 
         // step 3: wait for auto-scaling
@@ -752,10 +1018,7 @@ impl WorkerScaleUp {
             ))
         };
 
-        Ok(TestCase {
-            name: "worker scale up".to_string(),
-            result,
-        })
+        Ok(TestCase::new("worker scale up", result))
     }
 }
 
@@ -802,10 +1065,7 @@ impl WorkerScaleDown {
             ))
         };
 
-        Ok(TestCase {
-            name: "worker scale down".to_string(),
-            result,
-        })
+        Ok(TestCase::new("worker scale down", result))
     }
 }
 
@@ -857,10 +1117,10 @@ impl HttpRequestWithBody {
             ))
         };
 
-        Ok(TestCase {
-            name: format!("{} {} → {}", self.method, self.path, self.expected_status),
+        Ok(TestCase::new(
+            format!("{} {} → {}", self.method, self.path, self.expected_status),
             result,
-        })
+        ))
     }
 }
 
@@ -894,10 +1154,10 @@ impl HttpJsonFieldNested {
             Err(format!("field '{}' not found", self.field_path))
         };
 
-        Ok(TestCase {
-            name: format!("JSON field: {}", self.field_path),
+        Ok(TestCase::new(
+            format!("JSON field: {}", self.field_path),
             result,
-        })
+        ))
     }
 }
 
@@ -925,22 +1185,21 @@ impl HttpHealthCheck {
         let response = http_request(self.port, "GET", &self.path, &[], None).await?;
 
         if response.status_code != self.expected_status {
-            return Ok(TestCase {
-                name: format!("GET {} → {}", self.path, self.expected_status),
-                result: Err(format!(
+            return Ok(TestCase::new(
+                format!("GET {} → {}", self.path, self.expected_status),
+                Err(format!(
                     "expected status {}, got {}",
                     self.expected_status, response.status_code
                 )),
-            });
+            ));
         }
 
         let json: JsonValue =
             serde_json::from_str(&response.body).map_err(|e| format!("invalid JSON: {}", e))?;
 
-        let actual = json
-            .get(&self.expected_field)
-            .and_then(|v| v.as_str())
-            .unwrap_or("");
+        let actual = get_nested_field(&json, &self.expected_field)
+            .map(json_value_to_string)
+            .unwrap_or_default();
 
         let result = if actual == self.expected_value {
             Ok(format!(
@@ -954,13 +1213,13 @@ impl HttpHealthCheck {
             ))
         };
 
-        Ok(TestCase {
-            name: format!(
+        Ok(TestCase::new(
+            format!(
                 "GET {} → {} ({}={})",
                 self.path, self.expected_status, self.expected_field, self.expected_value
             ),
             result,
-        })
+        ))
     }
 }
 
@@ -1003,13 +1262,13 @@ impl HttpJsonFieldValue {
             ))
         };
 
-        Ok(TestCase {
-            name: format!(
+        Ok(TestCase::new(
+            format!(
                 "GET {} field '{}' = '{}'",
                 self.path, self.field, self.expected_value
             ),
             result,
-        })
+        ))
     }
 }
 
@@ -1044,9 +1303,223 @@ impl HttpStatusCheck {
             ))
         };
 
-        Ok(TestCase {
-            name: format!("GET {} → {}", self.path, self.expected_status),
+        Ok(TestCase::new(
+            format!("GET {} → {}", self.path, self.expected_status),
             result,
-        })
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::test_support::{json_ok, serve_once};
+
+    async fn serve_json(body: &'static str) -> u16 {
+        serve_once(json_ok(body)).await
+    }
+
+    fn health_check(port: u16, field: &str, value: &str) -> HttpHealthCheck {
+        let mut check = HttpHealthCheck::new("/health", 200, field, value);
+        check.port = port;
+        check
+    }
+
+    #[tokio::test]
+    async fn test_nested_field_matches_json_field_value() {
+        let body = r#"{"db":{"status":"ok"}}"#;
+
+        let port = serve_json(body).await;
+        let health = health_check(port, "db.status", "ok")
+            .validate()
+            .await
+            .unwrap();
+        assert!(health.passed());
+
+        let port = serve_json(body).await;
+        let mut field_value = HttpJsonFieldValue::new("/health", "db.status", "ok");
+        field_value.port = port;
+        let field_value = field_value.validate().await.unwrap();
+        assert!(field_value.passed());
+    }
+
+    #[tokio::test]
+    async fn test_health_check_numeric_field() {
+        let port = serve_json(r#"{"status":1}"#).await;
+        let result = health_check(port, "status", "1").validate().await.unwrap();
+        assert!(result.passed());
+    }
+
+    #[tokio::test]
+    async fn test_health_check_boolean_field() {
+        let port = serve_json(r#"{"healthy":true}"#).await;
+        let result = health_check(port, "healthy", "true")
+            .validate()
+            .await
+            .unwrap();
+        assert!(result.passed());
+
+        let port = serve_json(r#"{"healthy":false}"#).await;
+        let result = health_check(port, "healthy", "true")
+            .validate()
+            .await
+            .unwrap();
+        assert!(result.failed());
+        assert_eq!(result.message(), "expected healthy='true', got 'false'");
+    }
+
+    fn completion(id: &str, at: Option<&str>) -> (String, Option<String>) {
+        (id.to_string(), at.map(str::to_string))
+    }
+
+    #[test]
+    fn test_tally_submissions_all_accepted() {
+        let results = vec![Ok("a".to_string()), Ok("b".to_string())];
+        assert_eq!(tally_submissions(results).unwrap(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_tally_submissions_reports_lost_count() {
+        let results = vec![
+            Ok("a".to_string()),
+            Err("connection refused".to_string()),
+            Ok("b".to_string()),
+            Err("POST /jobs returned 503".to_string()),
+        ];
+        assert_eq!(
+            tally_submissions(results).unwrap_err(),
+            "lost 2 of 4 job submissions (2 accepted), first error: connection refused"
+        );
+    }
+
+    #[test]
+    fn test_submitted_job_id_rejects_error_status() {
+        let response = HttpResponse {
+            version: "HTTP/1.1".to_string(),
+            status_code: 503,
+            status_text: "Service Unavailable".to_string(),
+            headers: Vec::new(),
+            body: r#"{"id":"x"}"#.to_string(),
+            raw_body: Vec::new(),
+            incomplete: false,
+        };
+        assert_eq!(
+            submitted_job_id(Ok(response)).unwrap_err(),
+            "POST /jobs returned 503"
+        );
+    }
+
+    #[test]
+    fn test_dead_letter_requires_exact_retries() {
+        assert_eq!(
+            check_dead_letter(Some("dead"), Some(3), 3).unwrap(),
+            "job dead after 3 retries"
+        );
+        assert!(check_dead_letter(Some("failed"), Some(3), 3).is_ok());
+        assert!(check_dead_letter(Some("failed"), Some(1), 3)
+            .unwrap_err()
+            .contains("gave up before max_retries (3)"));
+        assert!(check_dead_letter(Some("failed"), Some(4), 3)
+            .unwrap_err()
+            .contains("exceeded max_retries (3)"));
+    }
+
+    #[test]
+    fn test_dead_letter_requires_terminal_state() {
+        assert!(check_dead_letter(Some("pending"), Some(3), 3)
+            .unwrap_err()
+            .starts_with("job still 'pending'"));
+        assert_eq!(
+            check_dead_letter(None, Some(3), 3).unwrap_err(),
+            "job missing status"
+        );
+        assert_eq!(
+            check_dead_letter(Some("dead"), None, 3).unwrap_err(),
+            "job missing retries"
+        );
+    }
+
+    #[test]
+    fn test_fifo_order_accepts_non_decreasing() {
+        let completions = vec![
+            completion("a", Some("2024-01-01T00:00:01Z")),
+            completion("b", Some("2024-01-01T00:00:01Z")),
+            completion("c", Some("2024-01-01T00:00:02Z")),
+        ];
+        assert_eq!(
+            check_fifo_order(&completions).unwrap(),
+            "3 jobs completed in submission order"
+        );
+    }
+
+    #[test]
+    fn test_fifo_order_rejects_out_of_order() {
+        let completions = vec![
+            completion("a", Some("2024-01-01T00:00:02Z")),
+            completion("b", Some("2024-01-01T00:00:01Z")),
+        ];
+        let err = check_fifo_order(&completions).unwrap_err();
+        assert!(err.starts_with("job b completed at 2024-01-01T00:00:01Z before job a"));
+    }
+
+    #[test]
+    fn test_fifo_order_compares_instants() {
+        // fractional seconds and mixed offsets sort wrongly as strings
+        let completions = vec![
+            completion("a", Some("2024-01-01T00:00:00Z")),
+            completion("b", Some("2024-01-01T00:00:00.5Z")),
+            completion("c", Some("2024-01-01T02:00:01+02:00")),
+        ];
+        assert!(check_fifo_order(&completions).is_ok());
+
+        let completions = vec![
+            completion("a", Some("2024-01-01T00:00:01Z")),
+            completion("b", Some("2024-01-01T01:00:00+02:00")),
+        ];
+        let err = check_fifo_order(&completions).unwrap_err();
+        assert!(err.starts_with("job b completed at 2024-01-01T01:00:00+02:00 before job a"));
+    }
+
+    #[test]
+    fn test_fifo_order_rejects_unparseable_completed_at() {
+        let completions = vec![
+            completion("a", Some("2024-01-01T00:00:01Z")),
+            completion("b", Some("yesterday")),
+        ];
+        let err = check_fifo_order(&completions).unwrap_err();
+        assert!(err.starts_with("job b has an invalid completed_at 'yesterday'"));
+    }
+
+    #[test]
+    fn test_fifo_order_reports_missing_completed_at() {
+        let completions = vec![
+            completion("a", Some("2024-01-01T00:00:01Z")),
+            completion("b", None),
+            completion("c", None),
+        ];
+        assert_eq!(
+            check_fifo_order(&completions).unwrap_err(),
+            "2 of 3 jobs missing completed_at: b, c"
+        );
+    }
+
+    #[test]
+    fn test_concurrency_threshold_from_worker_count() {
+        assert_eq!(WorkerPoolConcurrent::concurrency_threshold(2, 10), 2);
+        assert_eq!(WorkerPoolConcurrent::concurrency_threshold(4, 10), 3);
+        assert_eq!(WorkerPoolConcurrent::concurrency_threshold(8, 10), 6);
+        // fewer jobs than workers caps what can run at once
+        assert_eq!(WorkerPoolConcurrent::concurrency_threshold(8, 4), 3);
+        assert_eq!(WorkerPoolConcurrent::concurrency_threshold(1, 5), 2);
+    }
+
+    #[test]
+    fn test_worker_pool_builders() {
+        let v = WorkerPoolConcurrent::new(4, 8, 2000)
+            .with_min_concurrent(4)
+            .with_sampling(0, 20);
+        assert_eq!(v.min_concurrent, 4);
+        assert_eq!(v.samples, 1);
+        assert_eq!(v.sample_interval_ms, 20);
     }
 }
@@ -0,0 +1,85 @@
+//! throwaway tcp servers for validator and run tests
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+/// a 200 response carrying `body` as json
+pub(crate) fn json_ok(body: &str) -> String {
+    format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        body.len(),
+        body
+    )
+}
+
+/// bind an ephemeral port and answer a single connection with a canned response
+pub(crate) async fn serve_once(response: impl Into<Vec<u8>>) -> u16 {
+    let response = response.into();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        if let Ok((mut socket, _)) = listener.accept().await {
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await;
+            let _ = socket.write_all(&response).await;
+        }
+    });
+    port
+}
+
+/// answer the nth connection with `respond(n)`, or hang up when it returns `None`;
+/// also hands back the number of connections accepted so far
+pub(crate) async fn serve_counting<F>(respond: F) -> (u16, Arc<AtomicUsize>)
+where
+    F: Fn(usize) -> Option<Vec<u8>> + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&hits);
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let response = respond(counter.fetch_add(1, Ordering::SeqCst));
+            tokio::spawn(async move {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                if let Some(response) = response {
+                    let _ = socket.write_all(&response).await;
+                }
+            });
+        }
+    });
+    (port, hits)
+}
+
+/// answer every request on the first connection with `respond(n, request_line)`
+pub(crate) async fn serve_pipelined<F>(respond: F) -> u16
+where
+    F: Fn(usize, &str) -> String + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let Ok((socket, _)) = listener.accept().await else {
+            return;
+        };
+        let mut reader = BufReader::new(socket);
+        for n in 0.. {
+            let mut request_line = String::new();
+            if reader.read_line(&mut request_line).await.unwrap_or(0) == 0 {
+                return;
+            }
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).await.unwrap_or(0) == 0 || line == "\r\n" {
+                    break;
+                }
+            }
+            let response = respond(n, &request_line);
+            let _ = reader.get_mut().write_all(response.as_bytes()).await;
+        }
+    });
+    port
+}
@@ -0,0 +1,23 @@
+use std::process::Command;
+
+// `luxctl run` must reach the runner: with no config in LUX_HOME it reports a
+// setup error (exit 2) instead of silently succeeding
+#[test]
+fn test_run_invokes_runner() {
+    let home = tempfile::tempdir().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_luxctl"))
+        .args(["--no-color", "run", "1"])
+        .env("LUX_HOME", home.path())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        format!("{}{}", stdout, stderr).contains("config"),
+        "unexpected output: {}{}",
+        stdout,
+        stderr
+    );
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+/// largest body read_request will allocate; a bigger Content-Length is a 400
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// files uploaded with `POST /files/{name}`, served back by `GET /files/{name}`
+type Files = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+/// a parsed request: request line, headers and a body read to Content-Length
+#[derive(Debug)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// accept connections forever, one request per connection
+pub async fn serve(listener: TcpListener) {
+    let files: Files = Arc::default();
+    while let Ok((socket, addr)) = listener.accept().await {
+        println!("connection from {}", addr);
+        let files = Arc::clone(&files);
+        tokio::spawn(async move {
+            handle(socket, files).await;
+        });
+    }
+}
+
+async fn handle(mut socket: TcpStream, files: Files) {
+    let request = match read_request(&mut socket).await {
+        Ok(Some(request)) => request,
+        Ok(None) => return,
+        Err(err) => {
+            println!("bad request: {}", err);
+            let _ = socket
+                .write_all(&response(400, "Bad Request", "text/plain", b""))
+                .await;
+            return;
+        }
+    };
+    println!("request: {} {}", request.method, request.path);
+
+    let _ = socket.write_all(&route(&request, &files)).await;
+}
+
+/// read headers up to the blank line, then exactly Content-Length body bytes
+pub async fn read_request(socket: &mut TcpStream) -> Result<Option<Request>, String> {
+    let mut reader = BufReader::new(socket);
+
+    let mut request_line = String::new();
+    let n = reader
+        .read_line(&mut request_line)
+        .await
+        .map_err(|e| e.to_string())?;
+    if n == 0 {
+        return Ok(None);
+    }
+
+    // "GET /path HTTP/1.1"
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("GET").to_string();
+    let path = parts.next().unwrap_or("/").to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        let n = reader
+            .read_line(&mut line)
+            .await
+            .map_err(|e| e.to_string())?;
+        let line = line.trim_end();
+        if n == 0 || line.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            headers.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    let mut request = Request {
+        method,
+        path,
+        headers,
+        body: Vec::new(),
+    };
+
+    let length = match request.header("content-length") {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|_| format!("invalid Content-Length '{}'", value))?,
+        None => 0,
+    };
+    if length > MAX_BODY_BYTES {
+        return Err(format!(
+            "Content-Length {} exceeds the {} byte limit",
+            length, MAX_BODY_BYTES
+        ));
+    }
+    request.body = vec![0u8; length];
+    reader
+        .read_exact(&mut request.body)
+        .await
+        .map_err(|e| format!("body shorter than Content-Length {}: {}", length, e))?;
+
+    Ok(Some(request))
+}
+
+fn route(request: &Request, files: &Files) -> Vec<u8> {
+    let method = request.method.as_str();
+    let path = request.path.as_str();
+
+    if let Some(name) = path.strip_prefix("/files/") {
+        return match method {
+            "POST" => {
+                if let Ok(mut files) = files.lock() {
+                    files.insert(name.to_string(), request.body.clone());
+                }
+                response(201, "Created", "text/plain", b"")
+            }
+            "GET" => match files.lock().ok().and_then(|f| f.get(name).cloned()) {
+                Some(content) => response(200, "OK", "application/octet-stream", &content),
+                None => response(404, "Not Found", "text/plain", b""),
+            },
+            _ => response(405, "Method Not Allowed", "text/plain", b""),
+        };
+    }
+
+    if let (Some(echoed), "GET") = (path.strip_prefix("/echo/"), method) {
+        return response(200, "OK", "text/plain", echoed.as_bytes());
+    }
+
+    match (method, path) {
+        ("GET", "/") => response(200, "OK", "text/plain", b"Hello, World!"),
+        _ => response(404, "Not Found", "text/plain", b""),
+    }
+}
+
+fn response(status: u16, reason: &str, content_type: &str, body: &[u8]) -> Vec<u8> {
+    let mut out = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\r\n",
+        status,
+        reason,
+        content_type,
+        body.len()
+    )
+    .into_bytes();
+    out.extend_from_slice(body);
+    out
+}
@@ -0,0 +1,97 @@
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+async fn spawn_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(test_server::serve(listener));
+    port
+}
+
+/// send raw request bytes, possibly in several writes, and read until close
+async fn send(port: u16, chunks: &[&[u8]]) -> String {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+    for chunk in chunks {
+        stream.write_all(chunk).await.unwrap();
+        stream.flush().await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+#[tokio::test]
+async fn root_says_hello() {
+    let port = spawn_server().await;
+    let response = send(port, &[b"GET / HTTP/1.1\r\nHost: x\r\n\r\n"]).await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.ends_with("\r\n\r\nHello, World!"));
+}
+
+#[tokio::test]
+async fn echo_returns_path_segment() {
+    let port = spawn_server().await;
+    let response = send(port, &[b"GET /echo/abc HTTP/1.1\r\n\r\n"]).await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("Content-Length: 3\r\n"));
+    assert!(response.ends_with("\r\n\r\nabc"));
+}
+
+#[tokio::test]
+async fn unknown_path_is_404() {
+    let port = spawn_server().await;
+    let response = send(port, &[b"GET /nope HTTP/1.1\r\n\r\n"]).await;
+    assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+}
+
+#[tokio::test]
+async fn post_file_reads_whole_body() {
+    let port = spawn_server().await;
+    let body = "x".repeat(4000);
+    let head = format!(
+        "POST /files/big.txt HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+        body.len()
+    );
+    // body arrives after the headers and in pieces, larger than one read buffer
+    let (first, rest) = body.as_bytes().split_at(1500);
+    let response = send(port, &[head.as_bytes(), first, rest]).await;
+    assert!(response.starts_with("HTTP/1.1 201 Created\r\n"));
+
+    let stored = send(port, &[b"GET /files/big.txt HTTP/1.1\r\n\r\n"]).await;
+    assert!(stored.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(stored.ends_with(&body));
+}
+
+#[tokio::test]
+async fn missing_file_is_404() {
+    let port = spawn_server().await;
+    let response = send(port, &[b"GET /files/none.txt HTTP/1.1\r\n\r\n"]).await;
+    assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+}
+
+#[tokio::test]
+async fn bad_content_length_is_400() {
+    let port = spawn_server().await;
+    let response = send(
+        port,
+        &[b"POST /files/a HTTP/1.1\r\nContent-Length: lots\r\n\r\n"],
+    )
+    .await;
+    assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+}
+
+#[tokio::test]
+async fn oversized_content_length_is_400() {
+    let port = spawn_server().await;
+    let response = send(
+        port,
+        &[b"POST /files/a HTTP/1.1\r\nContent-Length: 99999999999\r\n\r\n"],
+    )
+    .await;
+    assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+
+    // the server is still up for the next connection
+    let response = send(port, &[b"GET / HTTP/1.1\r\n\r\n"]).await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+}